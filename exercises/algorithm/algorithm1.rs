@@ -4,7 +4,9 @@
     单链表合并
 */
 
-use std::fmt::{self, Display, Formatter};
+use std::fmt::{self, Debug, Display, Formatter};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
 use std::ptr::NonNull;
 use std::vec::*;
 
@@ -22,11 +24,15 @@ impl<T> Node<T> {
         }
     }
 }
-#[derive(Debug)]
+
+// Every node is allocated with `Box::into_raw` and owned by exactly one
+// list, which frees it with `Box::from_raw` when it is removed or dropped.
 struct LinkedList<T> {
     length: u32,
     start: Option<NonNull<Node<T>>>,
     end: Option<NonNull<Node<T>>>,
+    // Tells the drop checker that the list owns values of `T`
+    marker: PhantomData<Box<Node<T>>>,
 }
 
 impl<T> Default for LinkedList<T> {
@@ -41,9 +47,18 @@ impl<T> LinkedList<T> {
             length: 0,
             start: None,
             end: None,
+            marker: PhantomData,
         }
     }
 
+    pub fn len(&self) -> usize {
+        self.length as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start.is_none()
+    }
+
     pub fn add(&mut self, obj: T) {
         let mut node = Box::new(Node::new(obj));
         node.next = None;
@@ -56,19 +71,100 @@ impl<T> LinkedList<T> {
         self.length += 1;
     }
 
-    pub fn get(&mut self, index: i32) -> Option<&T> {
-        self.get_ith_node(self.start, index)
+    pub fn push_front(&mut self, obj: T) {
+        let mut node = Box::new(Node::new(obj));
+        node.next = self.start;
+        let node_ptr = Some(unsafe { NonNull::new_unchecked(Box::into_raw(node)) });
+        if self.end.is_none() {
+            self.end = node_ptr;
+        }
+        self.start = node_ptr;
+        self.length += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.start.map(|start_ptr| {
+            let node = unsafe { Box::from_raw(start_ptr.as_ptr()) };
+            self.start = node.next;
+            if self.start.is_none() {
+                self.end = None;
+            }
+            self.length -= 1;
+            node.val
+        })
+    }
+
+    // The node at the index, if the list is long enough
+    fn ith_node(&self, index: usize) -> Option<NonNull<Node<T>>> {
+        let mut node = self.start;
+        for _ in 0..index {
+            node = unsafe { (*node?.as_ptr()).next };
+        }
+        node
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.ith_node(index)
+            .map(|node| unsafe { &(*node.as_ptr()).val })
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.ith_node(index)
+            .map(|node| unsafe { &mut (*node.as_ptr()).val })
+    }
+
+    // Insert the value so that it ends up at the index, like `Vec::insert`.
+    // Panics if the index is greater than the length of the list.
+    pub fn insert(&mut self, index: usize, obj: T) {
+        let len = self.len();
+        assert!(index <= len, "insertion index (is {index}) should be <= len (is {len})");
+        if index == 0 {
+            return self.push_front(obj);
+        }
+        if index == len {
+            return self.add(obj);
+        }
+        let prev_ptr = self.ith_node(index - 1).unwrap();
+        let mut node = Box::new(Node::new(obj));
+        unsafe {
+            node.next = (*prev_ptr.as_ptr()).next;
+            (*prev_ptr.as_ptr()).next = Some(NonNull::new_unchecked(Box::into_raw(node)));
+        }
+        self.length += 1;
+    }
+
+    // Remove the value at the index, or return `None` if there is none
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index == 0 {
+            return self.pop_front();
+        }
+        let prev_ptr = self.ith_node(index - 1)?;
+        let node_ptr = unsafe { (*prev_ptr.as_ptr()).next? };
+        let node = unsafe { Box::from_raw(node_ptr.as_ptr()) };
+        unsafe { (*prev_ptr.as_ptr()).next = node.next };
+        if node.next.is_none() {
+            self.end = Some(prev_ptr);
+        }
+        self.length -= 1;
+        Some(node.val)
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.start,
+            len: self.len(),
+            marker: PhantomData,
+        }
     }
 
-    fn get_ith_node(&mut self, node: Option<NonNull<Node<T>>>, index: i32) -> Option<&T> {
-        match node {
-            None => None,
-            Some(next_ptr) => match index {
-                0 => Some(unsafe { &(*next_ptr.as_ptr()).val }),
-                _ => self.get_ith_node(unsafe { (*next_ptr.as_ptr()).next }, index - 1),
-            },
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.start,
+            len: self.len(),
+            marker: PhantomData,
         }
     }
+
 	pub fn merge(list_a:LinkedList<T>,list_b:LinkedList<T>) -> Self
     where
         T: std::cmp::PartialOrd + Clone,
@@ -107,33 +203,160 @@ impl<T> LinkedList<T> {
 	}
 }
 
-impl<T> Display for LinkedList<T>
-where
-    T: Display,
-{
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        match self.start {
-            Some(node) => write!(f, "{}", unsafe { node.as_ref() }),
-            None => Ok(()),
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+struct Iter<'a, T> {
+    next: Option<NonNull<Node<T>>>,
+    len: usize,
+    marker: PhantomData<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.next.map(|node_ptr| {
+            let node = unsafe { &*node_ptr.as_ptr() };
+            self.next = node.next;
+            self.len -= 1;
+            &node.val
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+struct IterMut<'a, T> {
+    next: Option<NonNull<Node<T>>>,
+    len: usize,
+    marker: PhantomData<&'a mut Node<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.next.map(|node_ptr| {
+            let node = unsafe { &mut *node_ptr.as_ptr() };
+            self.next = node.next;
+            self.len -= 1;
+            &mut node.val
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+struct IntoIter<T>(LinkedList<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = LinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for obj in iter {
+            self.add(obj);
         }
     }
 }
 
-impl<T> Display for Node<T>
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
+impl<T: Debug> Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T> Display for LinkedList<T>
 where
     T: Display,
 {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        match self.next {
-            Some(node) => write!(f, "{}, {}", self.val, unsafe { node.as_ref() }),
-            None => write!(f, "{}", self.val),
+        for (i, val) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", val)?;
         }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::LinkedList;
+    use std::rc::Rc;
 
     #[test]
     fn create_numeric_list() {
@@ -170,10 +393,10 @@ mod tests {
 			list_b.add(vec_b[i]);
 		}
 		println!("list a {} list b {}", list_a,list_b);
-		let mut list_c = LinkedList::<i32>::merge(list_a,list_b);
+		let list_c = LinkedList::<i32>::merge(list_a,list_b);
 		println!("merged List is {}", list_c);
 		for i in 0..target_vec.len(){
-			assert_eq!(target_vec[i],*list_c.get(i as i32).unwrap());
+			assert_eq!(target_vec[i],*list_c.get(i).unwrap());
 		}
 	}
 	#[test]
@@ -191,10 +414,99 @@ mod tests {
 			list_b.add(vec_b[i]);
 		}
 		println!("list a {} list b {}", list_a,list_b);
-		let mut list_c = LinkedList::<i32>::merge(list_a,list_b);
+		let list_c = LinkedList::<i32>::merge(list_a,list_b);
 		println!("merged List is {}", list_c);
 		for i in 0..target_vec.len(){
-			assert_eq!(target_vec[i],*list_c.get(i as i32).unwrap());
+			assert_eq!(target_vec[i],*list_c.get(i).unwrap());
 		}
 	}
-}
\ No newline at end of file
+
+    #[test]
+    fn push_and_pop_front() {
+        let mut list = LinkedList::new();
+        list.push_front(2);
+        list.push_front(1);
+        list.add(3);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(list.pop_front(), Some(3));
+        assert_eq!(list.pop_front(), None);
+        assert!(list.is_empty());
+        // The end is reset with the last node, so adding starts over
+        list.add(4);
+        assert_eq!(list.get(0), Some(&4));
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut list: LinkedList<i32> = vec![1, 3].into_iter().collect();
+        list.insert(1, 2);
+        list.insert(0, 0);
+        list.insert(4, 4);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+        assert_eq!(list.remove(4), Some(4));
+        assert_eq!(list.remove(1), Some(1));
+        assert_eq!(list.remove(0), Some(0));
+        assert_eq!(list.remove(2), None);
+        // Removing the last node moves the end back
+        list.add(5);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [2, 3, 5]);
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_past_the_end() {
+        let mut list = LinkedList::new();
+        list.insert(1, "out of bounds");
+    }
+
+    #[test]
+    fn get_through_shared_reference() {
+        let mut list: LinkedList<String> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+        let shared = &list;
+        assert_eq!(shared.get(1).map(String::as_str), Some("b"));
+        assert_eq!(shared.get(2), None);
+        list.get_mut(0).unwrap().push('!');
+        assert_eq!(list.get(0).map(String::as_str), Some("a!"));
+    }
+
+    #[test]
+    fn iterators() {
+        let mut list: LinkedList<i32> = (1..=3).collect();
+        assert_eq!(list.iter().len(), 3);
+        for val in &mut list {
+            *val *= 10;
+        }
+        list.extend(vec![40, 50]);
+        assert_eq!(list.to_string(), "10, 20, 30, 40, 50");
+        let mut into_iter = list.into_iter();
+        assert_eq!(into_iter.next(), Some(10));
+        assert_eq!(into_iter.len(), 4);
+        assert_eq!(into_iter.collect::<Vec<_>>(), [20, 30, 40, 50]);
+    }
+
+    #[test]
+    fn clone_and_compare() {
+        let list: LinkedList<String> = ["x", "y"].iter().map(|s| s.to_string()).collect();
+        let mut copy = list.clone();
+        assert_eq!(list, copy);
+        copy.add("z".to_string());
+        assert_ne!(list, copy);
+        assert_eq!(format!("{:?}", list), r#"["x", "y"]"#);
+    }
+
+    #[test]
+    fn drop_frees_every_value() {
+        let counter = Rc::new(());
+        let mut list: LinkedList<Rc<()>> = (0..5).map(|_| Rc::clone(&counter)).collect();
+        list.remove(2);
+        assert_eq!(Rc::strong_count(&counter), 5);
+        let mut into_iter = list.clone().into_iter();
+        into_iter.next();
+        drop(into_iter);
+        drop(list);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
@@ -4,92 +4,1377 @@
     // 图的实现
 */
 
-use std::collections::{HashMap, HashSet};
-use std::fmt;
+// 图的表示在 graph 模块里，algorithm5 和 algorithm6 也用它
+mod graph;
 
-// 图的节点不存在的错误
+// 随机测试用的数在 random 模块里，其他算法题也用它
+#[cfg(test)]
+mod random;
+
+pub use graph::{
+    AdjacencyList, AdjacencyMatrix, Directed, DirectedGraph, EdgeType, Graph, NodeNotInGraph,
+    Storage, Undirected, UndirectedGraph,
+};
+
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::Add;
+
+// 边的权重：可以相加、可以比较大小，并且有一个零
+pub trait Weight: Copy + Ord + Add<Output = Self> + Debug {
+    const ZERO: Self;
+}
+
+macro_rules! impl_weight {
+    ($($t:ty),*) => {
+        $(impl Weight for $t {
+            const ZERO: Self = 0;
+        })*
+    };
+}
+
+impl_weight!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+// 为什么找不到最短路径
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathError<N> {
+    NodeNotInGraph,
+    // Dijkstra only works without negative weights
+    NegativeWeight { from: N, to: N },
+    // The nodes of a cycle whose weights add up to less than zero, in order,
+    // which makes every path through it shorter the more often it goes round
+    NegativeCycle(Vec<N>),
+}
+
+impl<N> From<NodeNotInGraph> for PathError<N> {
+    fn from(_: NodeNotInGraph) -> Self {
+        PathError::NodeNotInGraph
+    }
+}
+
+// 一条路径：依次经过的节点和权重之和
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, W> {
+    pub nodes: Vec<N>,
+    pub cost: W,
+}
+
+// 单源最短路径：从起点到每个能到达的节点的距离，以及最短路径上的前一个节点
 #[derive(Debug, Clone)]
-pub struct NodeNotInGraph;
-impl fmt::Display for NodeNotInGraph {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "accessing a node that is not in the graph")
+pub struct ShortestPaths<N, W> {
+    source: N,
+    reached: HashMap<N, (W, Option<N>)>,
+}
+
+impl<N, W> ShortestPaths<N, W>
+where
+    N: Eq + Hash + Clone,
+    W: Weight,
+{
+    // Collect the distances and predecessors found for the node indices
+    fn new<E, S>(
+        graph: &Graph<N, W, E, S>,
+        source: usize,
+        distance: &[Option<W>],
+        previous: &[Option<usize>],
+    ) -> Self
+    where
+        E: EdgeType,
+        S: Storage<W>,
+    {
+        let reached = distance
+            .iter()
+            .enumerate()
+            .filter_map(|(index, distance)| {
+                let previous = previous[index].map(|previous| graph.node(previous).clone());
+                Some((graph.node(index).clone(), ((*distance)?, previous)))
+            })
+            .collect();
+        ShortestPaths {
+            source: graph.node(source).clone(),
+            reached,
+        }
+    }
+
+    pub fn source(&self) -> &N {
+        &self.source
+    }
+
+    // The length of the shortest path to the node, if it can be reached
+    pub fn distance(&self, to: &N) -> Option<W> {
+        self.reached.get(to).map(|(distance, _)| *distance)
+    }
+
+    // The shortest path to the node, if it can be reached
+    pub fn path(&self, to: &N) -> Option<Path<N, W>> {
+        let cost = self.distance(to)?;
+        let mut nodes = vec![to.clone()];
+        while let Some((_, Some(previous))) = self.reached.get(nodes.last().unwrap()) {
+            nodes.push(previous.clone());
+        }
+        nodes.reverse();
+        Some(Path { nodes, cost })
+    }
+}
+
+// Every edge as node indices, an undirected edge once in each direction
+fn edge_indices<N, W, E, S>(graph: &Graph<N, W, E, S>) -> Vec<(usize, usize, W)>
+where
+    N: Eq + Hash + Clone,
+    W: Weight,
+    E: EdgeType,
+    S: Storage<W>,
+{
+    (0..graph.node_count())
+        .flat_map(|from| {
+            graph
+                .neighbor_indices(from)
+                .map(move |(to, weight)| (from, to, *weight))
+        })
+        .collect()
+}
+
+// Dijkstra 算法：每次取出距离最小的未确定节点，用它的边更新邻居的距离。
+// 权重不能为负，O((V + E) log V)。
+pub fn dijkstra<N, W, E, S>(
+    graph: &Graph<N, W, E, S>,
+    source: &N,
+) -> Result<ShortestPaths<N, W>, PathError<N>>
+where
+    N: Eq + Hash + Clone,
+    W: Weight,
+    E: EdgeType,
+    S: Storage<W>,
+{
+    let source = graph.index(source)?;
+    if let Some((from, to, _)) = edge_indices(graph)
+        .into_iter()
+        .find(|&(_, _, weight)| weight < W::ZERO)
+    {
+        return Err(PathError::NegativeWeight {
+            from: graph.node(from).clone(),
+            to: graph.node(to).clone(),
+        });
+    }
+
+    let mut distance = vec![None; graph.node_count()];
+    let mut previous = vec![None; graph.node_count()];
+    let mut done = vec![false; graph.node_count()];
+    let mut heap = BinaryHeap::new();
+    distance[source] = Some(W::ZERO);
+    heap.push(Reverse((W::ZERO, source)));
+    while let Some(Reverse((current_distance, current))) = heap.pop() {
+        // 同一个节点可能被放入堆中多次，只处理距离最小的那次
+        if done[current] {
+            continue;
+        }
+        done[current] = true;
+        for (neighbor, &weight) in graph.neighbor_indices(current) {
+            let candidate = current_distance + weight;
+            if distance[neighbor].is_none_or(|known| candidate < known) {
+                distance[neighbor] = Some(candidate);
+                previous[neighbor] = Some(current);
+                heap.push(Reverse((candidate, neighbor)));
+            }
+        }
+    }
+    Ok(ShortestPaths::new(graph, source, &distance, &previous))
+}
+
+// Bellman-Ford 算法：把所有的边松弛 V - 1 次。如果第 V 次还能让某个距离变短，
+// 就有一个从起点能到达的负权环。O(VE)。
+pub fn bellman_ford<N, W, E, S>(
+    graph: &Graph<N, W, E, S>,
+    source: &N,
+) -> Result<ShortestPaths<N, W>, PathError<N>>
+where
+    N: Eq + Hash + Clone,
+    W: Weight,
+    E: EdgeType,
+    S: Storage<W>,
+{
+    let source = graph.index(source)?;
+    let edges = edge_indices(graph);
+    let count = graph.node_count();
+    let mut distance: Vec<Option<W>> = vec![None; count];
+    let mut previous = vec![None; count];
+    distance[source] = Some(W::ZERO);
+
+    let mut relaxed = None;
+    for _ in 0..count {
+        relaxed = None;
+        for &(from, to, weight) in &edges {
+            let Some(from_distance) = distance[from] else {
+                continue;
+            };
+            let candidate = from_distance + weight;
+            if distance[to].is_none_or(|known| candidate < known) {
+                distance[to] = Some(candidate);
+                previous[to] = Some(from);
+                relaxed = Some(to);
+            }
+        }
+        if relaxed.is_none() {
+            break;
+        }
+    }
+
+    // Something still got shorter on the last round, so the predecessors
+    // lead into a negative cycle. Going back as many steps as there are
+    // nodes is sure to end up on it.
+    if let Some(mut node) = relaxed {
+        for _ in 0..count {
+            node = previous[node].expect("a relaxed node has a predecessor");
+        }
+        let mut cycle = vec![node];
+        let mut current = previous[node].expect("a node on a cycle has a predecessor");
+        while current != node {
+            cycle.push(current);
+            current = previous[current].expect("a node on a cycle has a predecessor");
+        }
+        cycle.reverse();
+        return Err(PathError::NegativeCycle(
+            cycle.into_iter().map(|index| graph.node(index).clone()).collect(),
+        ));
     }
+    Ok(ShortestPaths::new(graph, source, &distance, &previous))
 }
 
-// 无向图的实现
-pub struct UndirectedGraph {
-    adjacency_table: HashMap<String, Vec<(String, i32)>>,
+// 全源最短路径：任意两个节点之间的距离，以及最短路径上的下一个节点
+#[derive(Debug, Clone)]
+pub struct AllPairs<N, W> {
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    distance: Vec<Vec<Option<W>>>,
+    next: Vec<Vec<Option<usize>>>,
 }
-impl Graph for UndirectedGraph {
-    fn new() -> UndirectedGraph {
-        UndirectedGraph {
-            adjacency_table: HashMap::new(),
+
+impl<N, W> AllPairs<N, W>
+where
+    N: Eq + Hash + Clone,
+    W: Weight,
+{
+    pub fn distance(&self, from: &N, to: &N) -> Option<W> {
+        self.distance[*self.indices.get(from)?][*self.indices.get(to)?]
+    }
+
+    pub fn path(&self, from: &N, to: &N) -> Option<Path<N, W>> {
+        let (mut current, to) = (*self.indices.get(from)?, *self.indices.get(to)?);
+        let cost = self.distance[current][to]?;
+        let mut nodes = vec![self.nodes[current].clone()];
+        while current != to {
+            current = self.next[current][to]?;
+            nodes.push(self.nodes[current].clone());
         }
+        Some(Path { nodes, cost })
     }
-    fn adjacency_table_mutable(&mut self) -> &mut HashMap<String, Vec<(String, i32)>> {
-        &mut self.adjacency_table
+}
+
+// Floyd-Warshall 算法：依次允许路径经过第 k 个节点，更新每一对节点之间的距离。
+// O(V³)。
+pub fn floyd_warshall<N, W, E, S>(graph: &Graph<N, W, E, S>) -> Result<AllPairs<N, W>, PathError<N>>
+where
+    N: Eq + Hash + Clone,
+    W: Weight,
+    E: EdgeType,
+    S: Storage<W>,
+{
+    let count = graph.node_count();
+    let mut distance: Vec<Vec<Option<W>>> = vec![vec![None; count]; count];
+    let mut next = vec![vec![None; count]; count];
+    for i in 0..count {
+        distance[i][i] = Some(W::ZERO);
+        next[i][i] = Some(i);
     }
-    fn adjacency_table(&self) -> &HashMap<String, Vec<(String, i32)>> {
-        &self.adjacency_table
+    for (from, to, weight) in edge_indices(graph) {
+        if distance[from][to].is_none_or(|known| weight < known) {
+            distance[from][to] = Some(weight);
+            next[from][to] = Some(to);
+        }
     }
-    fn add_edge(&mut self, edge: (&str, &str, i32)) {
-        self.adjacency_table_mutable().entry(edge.0.to_string()).or_default().push((edge.1.to_string(), edge.2));
-        self.adjacency_table_mutable().entry(edge.1.to_string()).or_default().push((edge.0.to_string(), edge.2));
+    for k in 0..count {
+        for i in 0..count {
+            let Some(through_k) = distance[i][k] else {
+                continue;
+            };
+            for j in 0..count {
+                let Some(from_k) = distance[k][j] else {
+                    continue;
+                };
+                let candidate = through_k + from_k;
+                if distance[i][j].is_none_or(|known| candidate < known) {
+                    distance[i][j] = Some(candidate);
+                    next[i][j] = next[i][k];
+                }
+            }
+        }
+
+        // A node that is less than zero away from itself is on a negative
+        // cycle, which Bellman-Ford can then find from it. Stopping right
+        // away keeps the distances from growing ever more negative.
+        if let Some(start) = (0..count).find(|&i| distance[i][i] < Some(W::ZERO)) {
+            return match bellman_ford(graph, graph.node(start)) {
+                Err(cycle) => Err(cycle),
+                Ok(_) => unreachable!("Bellman-Ford has to find the negative cycle"),
+            };
+        }
     }
+    Ok(AllPairs {
+        nodes: graph.nodes().cloned().collect(),
+        indices: graph.nodes().cloned().enumerate().map(|(i, node)| (node, i)).collect(),
+        distance,
+        next,
+    })
 }
-// 图的 trait
-pub trait Graph {
-    fn new() -> Self;
-    fn adjacency_table_mutable(&mut self) -> &mut HashMap<String, Vec<(String, i32)>>;
-    fn adjacency_table(&self) -> &HashMap<String, Vec<(String, i32)>>;
-    fn add_node(&mut self, node: &str) -> bool {
-        self.adjacency_table_mutable().entry(node.to_string()).or_default();
+
+// 并查集：按秩合并并压缩路径，几乎是 O(1) 地判断两个元素是否在同一个集合里
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    sets: usize,
+}
+
+impl UnionFind {
+    pub fn new(len: usize) -> Self {
+        UnionFind {
+            parent: (0..len).collect(),
+            rank: vec![0; len],
+            sets: len,
+        }
+    }
+
+    // The representative of the set the element is in
+    pub fn find(&mut self, mut element: usize) -> usize {
+        while self.parent[element] != element {
+            // 路径减半：让每个节点指向它的祖父节点
+            self.parent[element] = self.parent[self.parent[element]];
+            element = self.parent[element];
+        }
+        element
+    }
+
+    // Merge the sets of the two elements, returning whether they were apart
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (low, high) = if self.rank[a] < self.rank[b] { (a, b) } else { (b, a) };
+        self.parent[low] = high;
+        if self.rank[low] == self.rank[high] {
+            self.rank[high] += 1;
+        }
+        self.sets -= 1;
         true
     }
-    fn add_edge(&mut self, edge: (&str, &str, i32)) {
-        // 解释一下
-        // 1. 将 edge.0 和 edge.1 转换为字符串
-        // 2. 将 edge.0 和 edge.1 添加到图的邻接表中
-        // 3. 将 edge.1 和 edge.0 添加到图的邻接表中
-        self.adjacency_table_mutable().entry(edge.0.to_string()).or_default().push((edge.1.to_string(), edge.2));
-        self.adjacency_table_mutable().entry(edge.1.to_string()).or_default().push((edge.0.to_string(), edge.2));
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    // How many separate sets there are
+    pub fn sets(&self) -> usize {
+        self.sets
+    }
+}
+
+// 最小生成树：连接所有节点的权重之和最小的边。图不连通时是每个连通分量的
+// 最小生成树合在一起的森林。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanningTree<N, W> {
+    pub edges: Vec<(N, N, W)>,
+    pub weight: W,
+}
+
+impl<N, W: Weight> SpanningTree<N, W> {
+    fn from_edges(edges: Vec<(N, N, W)>) -> Self {
+        let weight = edges.iter().fold(W::ZERO, |sum, edge| sum + edge.2);
+        SpanningTree { edges, weight }
+    }
+}
+
+// Prim 算法：从一个节点开始，每次加入连接树和树外节点的最轻的边
+pub fn prim<N, W, S>(graph: &Graph<N, W, Undirected, S>) -> SpanningTree<N, W>
+where
+    N: Eq + Hash + Clone,
+    W: Weight,
+    S: Storage<W>,
+{
+    let count = graph.node_count();
+    let mut in_tree = vec![false; count];
+    let mut edges = Vec::new();
+    for root in 0..count {
+        if in_tree[root] {
+            continue;
+        }
+        in_tree[root] = true;
+        let mut heap: BinaryHeap<_> = graph
+            .neighbor_indices(root)
+            .map(|(to, &weight)| Reverse((weight, root, to)))
+            .collect();
+        while let Some(Reverse((weight, from, to))) = heap.pop() {
+            if in_tree[to] {
+                continue;
+            }
+            in_tree[to] = true;
+            edges.push((graph.node(from).clone(), graph.node(to).clone(), weight));
+            heap.extend(
+                graph
+                    .neighbor_indices(to)
+                    .filter(|&(next, _)| !in_tree[next])
+                    .map(|(next, &weight)| Reverse((weight, to, next))),
+            );
+        }
+    }
+    SpanningTree::from_edges(edges)
+}
+
+// Kruskal 算法：按权重从小到大考虑每条边，用并查集跳过会形成环的边
+pub fn kruskal<N, W, S>(graph: &Graph<N, W, Undirected, S>) -> SpanningTree<N, W>
+where
+    N: Eq + Hash + Clone,
+    W: Weight,
+    S: Storage<W>,
+{
+    let mut candidates: Vec<(usize, usize, W)> = edge_indices(graph)
+        .into_iter()
+        .filter(|&(from, to, _)| from < to)
+        .collect();
+    candidates.sort_by_key(|&(_, _, weight)| weight);
+    let mut sets = UnionFind::new(graph.node_count());
+    let edges = candidates
+        .into_iter()
+        .filter(|&(from, to, _)| sets.union(from, to))
+        .map(|(from, to, weight)| (graph.node(from).clone(), graph.node(to).clone(), weight))
+        .collect();
+    SpanningTree::from_edges(edges)
+}
+
+// 连通分量：每个分量里的节点按广度优先的顺序排列，分量按第一个节点加入图的顺序排列
+pub fn connected_components<N, W, S>(graph: &Graph<N, W, Undirected, S>) -> Vec<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    S: Storage<W>,
+{
+    let mut seen = vec![false; graph.node_count()];
+    let mut components = Vec::new();
+    for (index, node) in graph.nodes().enumerate() {
+        if seen[index] {
+            continue;
+        }
+        let component: Vec<N> = graph
+            .bfs(node)
+            .expect("the node is in the graph")
+            .cloned()
+            .collect();
+        for member in &component {
+            seen[graph.index(member).expect("the node is in the graph")] = true;
+        }
+        components.push(component);
+    }
+    components
+}
+
+// 找一个环，按边的方向依次列出环上的节点
+pub fn find_cycle<N, W, E, S>(graph: &Graph<N, W, E, S>) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    E: EdgeType,
+    S: Storage<W>,
+{
+    depth_first(graph, true, |_| {})
+        .err()
+        .map(|cycle| cycle.into_iter().map(|index| graph.node(index).clone()).collect())
+}
+
+// 对所有节点做深度优先搜索，不用递归。一个节点的后代都访问完时调用 finished。
+// 当前路径上的节点又被走到就说明有环（无向图里走回上一个节点的那条边不算），
+// stop_at_cycle 时就返回这个环上的节点下标。
+fn depth_first<N, W, E, S>(
+    graph: &Graph<N, W, E, S>,
+    stop_at_cycle: bool,
+    mut finished: impl FnMut(usize),
+) -> Result<(), Vec<usize>>
+where
+    N: Eq + Hash + Clone,
+    E: EdgeType,
+    S: Storage<W>,
+{
+    #[derive(Clone, Copy, PartialEq)]
+    enum Color {
+        // 还没访问
+        White,
+        // 在当前路径上
+        Gray,
+        // 已经访问完
+        Black,
+    }
+
+    let neighbors = |node: usize| -> std::vec::IntoIter<usize> {
+        let neighbors: Vec<usize> = graph.neighbor_indices(node).map(|(to, _)| to).collect();
+        neighbors.into_iter()
+    };
+    let count = graph.node_count();
+    let mut color = vec![Color::White; count];
+    for root in 0..count {
+        if color[root] != Color::White {
+            continue;
+        }
+        // The current path, each node with its neighbors still to be tried
+        let mut path = vec![(root, neighbors(root))];
+        color[root] = Color::Gray;
+        while let Some((node, untried)) = path.last_mut() {
+            let node = *node;
+            let Some(neighbor) = untried.next() else {
+                color[node] = Color::Black;
+                finished(node);
+                path.pop();
+                continue;
+            };
+            match color[neighbor] {
+                Color::White => {
+                    color[neighbor] = Color::Gray;
+                    path.push((neighbor, neighbors(neighbor)));
+                }
+                Color::Gray if stop_at_cycle => {
+                    let parent = path.len().checked_sub(2).map(|i| path[i].0);
+                    if !E::DIRECTED && parent == Some(neighbor) {
+                        continue;
+                    }
+                    let start = path.iter().position(|(on_path, _)| *on_path == neighbor).unwrap();
+                    return Err(path[start..].iter().map(|(index, _)| *index).collect());
+                }
+                Color::Gray | Color::Black => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+// 有环的有向图没有拓扑排序，这是其中的一个环
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<N>(pub Vec<N>);
+
+impl<N: Debug> std::fmt::Display for Cycle<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "the graph has a cycle through {:?}", self.0)
+    }
+}
+
+impl<N: Debug> std::error::Error for Cycle<N> {}
+
+// Kahn 算法：不断取出没有入边的节点。同时有几个节点可以取时，取最早加入图的那个，
+// 所以没有边约束的节点保持加入的顺序，就像 info.toml 里练习的顺序一样。
+pub fn topological_sort<N, W, S>(graph: &Graph<N, W, Directed, S>) -> Result<Vec<N>, Cycle<N>>
+where
+    N: Eq + Hash + Clone,
+    S: Storage<W>,
+{
+    let count = graph.node_count();
+    let mut in_degree = vec![0; count];
+    for from in 0..count {
+        for (to, _) in graph.neighbor_indices(from) {
+            in_degree[to] += 1;
+        }
+    }
+    let mut ready: BinaryHeap<Reverse<usize>> =
+        (0..count).filter(|&node| in_degree[node] == 0).map(Reverse).collect();
+    let mut order = Vec::with_capacity(count);
+    while let Some(Reverse(node)) = ready.pop() {
+        order.push(graph.node(node).clone());
+        for (to, _) in graph.neighbor_indices(node) {
+            in_degree[to] -= 1;
+            if in_degree[to] == 0 {
+                ready.push(Reverse(to));
+            }
+        }
+    }
+    // The nodes that never became ready wait on each other in a cycle
+    if order.len() < count {
+        return Err(Cycle(find_cycle(graph).expect("nodes that are never ready lie on a cycle")));
+    }
+    Ok(order)
+}
+
+// 基于深度优先搜索的拓扑排序：一个节点在它能到达的所有节点之后访问完，
+// 所以把访问完的顺序倒过来就是拓扑排序
+pub fn topological_sort_dfs<N, W, S>(graph: &Graph<N, W, Directed, S>) -> Result<Vec<N>, Cycle<N>>
+where
+    N: Eq + Hash + Clone,
+    S: Storage<W>,
+{
+    let mut finished = Vec::with_capacity(graph.node_count());
+    if let Err(cycle) = depth_first(graph, true, |node| finished.push(node)) {
+        return Err(Cycle(cycle.into_iter().map(|index| graph.node(index).clone()).collect()));
+    }
+    Ok(finished.into_iter().rev().map(|index| graph.node(index).clone()).collect())
+}
+
+// Tarjan 算法求强连通分量，只做一次深度优先搜索。每个节点记录访问的次序 index
+// 和它能回到的最早的节点 low，low 等于自己的 index 的节点是一个分量的根。
+// 分量按拓扑顺序排列：一个分量排在它的边通向的分量前面。
+pub fn tarjan_scc<N, W, S>(graph: &Graph<N, W, Directed, S>) -> Vec<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    S: Storage<W>,
+{
+    to_nodes(graph, tarjan_indices(graph))
+}
+
+fn tarjan_indices<N, W, S>(graph: &Graph<N, W, Directed, S>) -> Vec<Vec<usize>>
+where
+    N: Eq + Hash + Clone,
+    S: Storage<W>,
+{
+    let count = graph.node_count();
+    let mut index: Vec<Option<usize>> = vec![None; count];
+    let mut low = vec![0; count];
+    let mut on_stack = vec![false; count];
+    let mut stack = Vec::new();
+    let mut next_index = 0;
+    let mut components = Vec::new();
+
+    for root in 0..count {
+        if index[root].is_some() {
+            continue;
+        }
+        // The nodes being visited, each with its neighbors still to be tried
+        let mut calls: Vec<(usize, std::vec::IntoIter<usize>)> = Vec::new();
+        let mut visit = Some(root);
+        loop {
+            if let Some(node) = visit.take() {
+                index[node] = Some(next_index);
+                low[node] = next_index;
+                next_index += 1;
+                stack.push(node);
+                on_stack[node] = true;
+                let neighbors: Vec<usize> = graph.neighbor_indices(node).map(|(to, _)| to).collect();
+                calls.push((node, neighbors.into_iter()));
+            }
+            let Some((node, neighbors)) = calls.last_mut() else {
+                break;
+            };
+            let node = *node;
+            if let Some(neighbor) = neighbors.next() {
+                match index[neighbor] {
+                    None => visit = Some(neighbor),
+                    Some(neighbor_index) if on_stack[neighbor] => {
+                        low[node] = low[node].min(neighbor_index);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+            calls.pop();
+            if let Some(&(parent, _)) = calls.last() {
+                low[parent] = low[parent].min(low[node]);
+            }
+            if Some(low[node]) == index[node] {
+                let mut component = Vec::new();
+                loop {
+                    let member = stack.pop().expect("the root is still on the stack");
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+    // Tarjan finds a component only after every component it leads to
+    components.reverse();
+    components
+}
+
+// Kosaraju 算法求强连通分量：先记下深度优先搜索访问完的顺序，再在反向图上按这个
+// 顺序倒着搜索，每次搜到的就是一个分量。分量同样按拓扑顺序排列。
+pub fn kosaraju_scc<N, W, S>(graph: &Graph<N, W, Directed, S>) -> Vec<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    S: Storage<W>,
+{
+    let count = graph.node_count();
+    let mut finished = Vec::with_capacity(count);
+    // Without stopping at cycles, the search always succeeds
+    let _ = depth_first(graph, false, |node| finished.push(node));
+
+    let mut reversed = vec![Vec::new(); count];
+    for from in 0..count {
+        for (to, _) in graph.neighbor_indices(from) {
+            reversed[to].push(from);
+        }
+    }
+    let mut assigned = vec![false; count];
+    let mut components = Vec::new();
+    for &root in finished.iter().rev() {
+        if assigned[root] {
+            continue;
+        }
+        assigned[root] = true;
+        let mut component = Vec::new();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            component.push(node);
+            for &previous in &reversed[node] {
+                if !assigned[previous] {
+                    assigned[previous] = true;
+                    stack.push(previous);
+                }
+            }
+        }
+        components.push(component);
     }
-    fn contains(&self, node: &str) -> bool {
-        self.adjacency_table().get(node).is_some()
+    to_nodes(graph, components)
+}
+
+fn to_nodes<N, W, E, S>(graph: &Graph<N, W, E, S>, components: Vec<Vec<usize>>) -> Vec<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    E: EdgeType,
+    S: Storage<W>,
+{
+    components
+        .into_iter()
+        .map(|component| component.into_iter().map(|index| graph.node(index).clone()).collect())
+        .collect()
+}
+
+// 缩点：把每个强连通分量缩成一个节点，得到一个有向无环图。分量的编号按拓扑顺序，
+// 所以图里的每条边都从编号小的分量通向编号大的分量。
+#[derive(Debug)]
+pub struct Condensation<N> {
+    pub components: Vec<Vec<N>>,
+    pub graph: DirectedGraph<usize>,
+    component_of: HashMap<N, usize>,
+}
+
+impl<N> Condensation<N>
+where
+    N: Eq + Hash + Clone,
+{
+    // The number of the component the node belongs to
+    pub fn component_of(&self, node: &N) -> Option<usize> {
+        self.component_of.get(node).copied()
     }
-    fn nodes(&self) -> HashSet<&String> {
-        self.adjacency_table().keys().collect()
+}
+
+pub fn condensation<N, W, S>(graph: &Graph<N, W, Directed, S>) -> Condensation<N>
+where
+    N: Eq + Hash + Clone,
+    S: Storage<W>,
+{
+    let components = tarjan_indices(graph);
+    let mut component_of = vec![0; graph.node_count()];
+    let mut dag = DirectedGraph::new();
+    for (id, component) in components.iter().enumerate() {
+        dag.add_node(id);
+        for &member in component {
+            component_of[member] = id;
+        }
     }
-    fn edges(&self) -> Vec<(&String, &String, i32)> {
-        let mut edges = Vec::new();
-        for (from_node, from_node_neighbours) in self.adjacency_table() {
-            for (to_node, weight) in from_node_neighbours {
-                edges.push((from_node, to_node, *weight));
+    for from in 0..graph.node_count() {
+        for (to, _) in graph.neighbor_indices(from) {
+            if component_of[from] != component_of[to] {
+                dag.add_edge(component_of[from], component_of[to], ());
             }
         }
-        edges
+    }
+    Condensation {
+        component_of: graph.nodes().cloned().zip(component_of).collect(),
+        components: to_nodes(graph, components),
+        graph: dag,
     }
 }
+
 #[cfg(test)]
 mod test_undirected_graph {
-    use super::Graph;
-    use super::UndirectedGraph;
+    use super::*;
+
     #[test]
     fn test_add_edge() {
-        let mut graph = UndirectedGraph::new();
-        graph.add_edge(("a", "b", 5));
-        graph.add_edge(("b", "c", 10));
-        graph.add_edge(("c", "a", 7));
+        let mut graph = UndirectedGraph::<&str, i32>::new();
+        graph.add_edge("a", "b", 5);
+        graph.add_edge("b", "c", 10);
+        graph.add_edge("c", "a", 7);
         let expected_edges = [
-            (&String::from("a"), &String::from("b"), 5),
-            (&String::from("b"), &String::from("a"), 5),
-            (&String::from("c"), &String::from("a"), 7),
-            (&String::from("a"), &String::from("c"), 7),
-            (&String::from("b"), &String::from("c"), 10),
-            (&String::from("c"), &String::from("b"), 10),
+            (&"a", &"b", &5),
+            (&"b", &"a", &5),
+            (&"c", &"a", &7),
+            (&"a", &"c", &7),
+            (&"b", &"c", &10),
+            (&"c", &"b", &10),
         ];
+        let edges: Vec<_> = graph.edges().collect();
         for edge in expected_edges.iter() {
-            assert_eq!(graph.edges().contains(edge), true);
+            assert_eq!(edges.contains(edge), true);
+        }
+        assert_eq!(edges.len(), 6);
+        assert_eq!(graph.edge_count(), 3);
+    }
+
+    #[test]
+    fn add_node_and_contains() {
+        let mut graph = UndirectedGraph::<String>::new();
+        assert!(graph.add_node("a".to_string()));
+        assert!(!graph.add_node("a".to_string()));
+        assert!(graph.contains(&"a".to_string()));
+        assert!(!graph.contains(&"b".to_string()));
+        assert_eq!(graph.node_count(), 1);
+        assert_eq!(graph.edge_count(), 0);
+    }
+
+    #[test]
+    fn adding_an_edge_again_replaces_its_weight() {
+        let mut graph = UndirectedGraph::<&str, i32>::new();
+        assert_eq!(graph.add_edge("a", "b", 1), None);
+        assert_eq!(graph.add_edge("b", "a", 2), Some(1));
+        assert_eq!(graph.edge(&"a", &"b"), Ok(Some(&2)));
+        assert_eq!(graph.edge_count(), 1);
+        assert_eq!(graph.remove_edge(&"a", &"b"), Ok(Some(2)));
+        assert_eq!(graph.edge(&"b", &"a"), Ok(None));
+        assert_eq!(graph.edge_count(), 0);
+    }
+}
+
+#[cfg(test)]
+mod test_graph {
+    use super::*;
+
+    // The same edges in every kind of graph
+    fn build<E: EdgeType, S: Storage<u32>>() -> Graph<&'static str, u32, E, S> {
+        let mut graph = Graph::new();
+        for (from, to, weight) in [("a", "b", 1), ("a", "c", 2), ("b", "d", 3), ("c", "d", 4), ("d", "e", 5)] {
+            graph.add_edge(from, to, weight);
+        }
+        graph.add_node("lonely");
+        graph
+    }
+
+    fn neighbors<E: EdgeType, S: Storage<u32>>(
+        graph: &Graph<&'static str, u32, E, S>,
+        node: &'static str,
+    ) -> Vec<(&'static str, u32)> {
+        let mut neighbors: Vec<_> = graph
+            .neighbors(&node)
+            .unwrap()
+            .map(|(node, weight)| (*node, *weight))
+            .collect();
+        neighbors.sort();
+        neighbors
+    }
+
+    #[test]
+    fn directed_edges_go_one_way() {
+        let graph = build::<Directed, AdjacencyList<u32>>();
+        assert!(graph.is_directed());
+        assert_eq!(neighbors(&graph, "a"), [("b", 1), ("c", 2)]);
+        assert_eq!(neighbors(&graph, "d"), [("e", 5)]);
+        assert_eq!(graph.edge(&"b", &"a"), Ok(None));
+        assert_eq!(graph.edges().count(), 5);
+        assert_eq!(graph.bfs(&"c").unwrap().copied().collect::<Vec<_>>(), ["c", "d", "e"]);
+    }
+
+    #[test]
+    fn undirected_edges_go_both_ways() {
+        let graph = build::<Undirected, AdjacencyList<u32>>();
+        assert!(!graph.is_directed());
+        assert_eq!(neighbors(&graph, "d"), [("b", 3), ("c", 4), ("e", 5)]);
+        assert_eq!(graph.edge(&"b", &"a"), Ok(Some(&1)));
+        assert_eq!(graph.edges().count(), 10);
+        assert_eq!(graph.edge_count(), 5);
+    }
+
+    #[test]
+    fn both_backends_hold_the_same_graph() {
+        let list = build::<Directed, AdjacencyList<u32>>();
+        let matrix = build::<Directed, AdjacencyMatrix<u32>>();
+        for node in list.nodes() {
+            assert_eq!(neighbors(&list, node), neighbors(&matrix, node));
+        }
+        let undirected_list = build::<Undirected, AdjacencyList<u32>>();
+        let undirected_matrix = build::<Undirected, AdjacencyMatrix<u32>>();
+        for node in undirected_list.nodes() {
+            assert_eq!(neighbors(&undirected_list, node), neighbors(&undirected_matrix, node));
+        }
+        assert_eq!(matrix.nodes().collect::<Vec<_>>(), list.nodes().collect::<Vec<_>>());
+        assert_eq!(undirected_matrix.edge_count(), 5);
+    }
+
+    #[test]
+    fn searches_visit_what_can_be_reached() {
+        let graph = build::<Undirected, AdjacencyMatrix<u32>>();
+        let bfs: Vec<_> = graph.bfs(&"a").unwrap().copied().collect();
+        assert_eq!(bfs, ["a", "b", "c", "d", "e"]);
+        let dfs: Vec<_> = graph.dfs(&"a").unwrap().copied().collect();
+        assert_eq!(dfs, ["a", "b", "d", "c", "e"]);
+        assert_eq!(graph.dfs(&"lonely").unwrap().count(), 1);
+    }
+
+    #[test]
+    fn missing_nodes_are_an_error() {
+        let mut graph = build::<Directed, AdjacencyList<u32>>();
+        assert_eq!(graph.bfs(&"z").err(), Some(NodeNotInGraph));
+        assert_eq!(graph.dfs(&"z").err(), Some(NodeNotInGraph));
+        assert!(graph.neighbors(&"z").is_err());
+        assert_eq!(graph.edge(&"a", &"z"), Err(NodeNotInGraph));
+        assert_eq!(graph.remove_edge(&"z", &"a"), Err(NodeNotInGraph));
+        assert_eq!(NodeNotInGraph.to_string(), "accessing a node that is not in the graph");
+    }
+
+    #[test]
+    fn searching_a_long_path_does_not_overflow_the_stack() {
+        let mut graph = DirectedGraph::<u32>::new();
+        for node in 0..100_000 {
+            graph.add_edge(node, node + 1, ());
+        }
+        assert_eq!(graph.dfs(&0).unwrap().count(), 100_001);
+        assert_eq!(graph.bfs(&0).unwrap().last(), Some(&100_000));
+    }
+}
+
+#[cfg(test)]
+mod test_paths {
+    use super::*;
+    use super::random::random_numbers;
+
+    // A graph on `nodes` nodes with `edges` random edges, without self-loops,
+    // of weights in `min..min + 20`
+    fn random_graph<E: EdgeType, S: Storage<i64>>(
+        seed: u64,
+        nodes: u64,
+        edges: usize,
+        min: i64,
+    ) -> Graph<u64, i64, E, S> {
+        let mut numbers = random_numbers(seed);
+        let mut graph = Graph::new();
+        for node in 0..nodes {
+            graph.add_node(node);
+        }
+        for _ in 0..edges {
+            let from = numbers.next().unwrap() % nodes;
+            let to = numbers.next().unwrap() % nodes;
+            let weight = min + (numbers.next().unwrap() % 20) as i64;
+            if from != to {
+                graph.add_edge(from, to, weight);
+            }
+        }
+        graph
+    }
+
+    // The weight of the path, checking that every step of it is an edge
+    fn walk<E: EdgeType, S: Storage<i64>>(graph: &Graph<u64, i64, E, S>, nodes: &[u64]) -> i64 {
+        nodes
+            .windows(2)
+            .map(|step| *graph.edge(&step[0], &step[1]).unwrap().expect("a step of the path is an edge"))
+            .sum()
+    }
+
+    fn classic() -> DirectedGraph<&'static str, u32> {
+        let mut graph = DirectedGraph::new();
+        for (from, to, weight) in [
+            ("s", "a", 7),
+            ("s", "b", 2),
+            ("b", "a", 3),
+            ("a", "c", 1),
+            ("b", "c", 8),
+            ("c", "t", 2),
+        ] {
+            graph.add_edge(from, to, weight);
+        }
+        graph.add_node("unreachable");
+        graph
+    }
+
+    #[test]
+    fn dijkstra_finds_the_shortest_path() {
+        let paths = dijkstra(&classic(), &"s").unwrap();
+        assert_eq!(paths.source(), &"s");
+        assert_eq!(paths.distance(&"t"), Some(8));
+        assert_eq!(
+            paths.path(&"t"),
+            Some(Path {
+                nodes: vec!["s", "b", "a", "c", "t"],
+                cost: 8
+            })
+        );
+        assert_eq!(paths.path(&"s").unwrap().nodes, ["s"]);
+        assert_eq!(paths.distance(&"unreachable"), None);
+        assert_eq!(paths.path(&"unreachable"), None);
+        assert_eq!(dijkstra(&classic(), &"x").err(), Some(PathError::NodeNotInGraph));
+    }
+
+    #[test]
+    fn shortest_path_algorithms_agree() {
+        for seed in 0..20 {
+            let directed = random_graph::<Directed, AdjacencyList<i64>>(seed, 12, 30, 0);
+            let undirected = random_graph::<Undirected, AdjacencyMatrix<i64>>(seed, 12, 20, 0);
+            let all_directed = floyd_warshall(&directed).unwrap();
+            let all_undirected = floyd_warshall(&undirected).unwrap();
+            for from in 0..12 {
+                let dijkstra_directed = dijkstra(&directed, &from).unwrap();
+                let bellman_ford_directed = bellman_ford(&directed, &from).unwrap();
+                let dijkstra_undirected = dijkstra(&undirected, &from).unwrap();
+                let bellman_ford_undirected = bellman_ford(&undirected, &from).unwrap();
+                for to in 0..12 {
+                    let expected = dijkstra_directed.distance(&to);
+                    assert_eq!(bellman_ford_directed.distance(&to), expected);
+                    assert_eq!(all_directed.distance(&from, &to), expected);
+                    let expected = dijkstra_undirected.distance(&to);
+                    assert_eq!(bellman_ford_undirected.distance(&to), expected);
+                    assert_eq!(all_undirected.distance(&from, &to), expected);
+
+                    for path in [
+                        dijkstra_directed.path(&to),
+                        bellman_ford_directed.path(&to),
+                        all_directed.path(&from, &to),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    {
+                        assert_eq!(path.nodes.first(), Some(&from));
+                        assert_eq!(path.nodes.last(), Some(&to));
+                        assert_eq!(walk(&directed, &path.nodes), path.cost);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn negative_weights_without_a_cycle() {
+        // Every edge goes from a smaller node to a larger one, so there can't be a cycle
+        let mut graph = DirectedGraph::<u64, i64>::new();
+        let mut numbers = random_numbers(3);
+        for _ in 0..40 {
+            let (a, b) = (numbers.next().unwrap() % 10, numbers.next().unwrap() % 10);
+            if a < b {
+                graph.add_edge(a, b, (numbers.next().unwrap() % 20) as i64 - 10);
+            }
+        }
+        assert!(matches!(dijkstra(&graph, &0), Err(PathError::NegativeWeight { .. })));
+        let all = floyd_warshall(&graph).unwrap();
+        for from in graph.nodes() {
+            let paths = bellman_ford(&graph, from).unwrap();
+            for to in graph.nodes() {
+                assert_eq!(paths.distance(to), all.distance(from, to));
+                if let Some(path) = paths.path(to) {
+                    assert_eq!(walk(&graph, &path.nodes), path.cost);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn negative_cycles_are_reported() {
+        let mut graph = DirectedGraph::<u64, i64>::new();
+        graph.add_edge(0, 1, 4);
+        graph.add_edge(1, 2, -2);
+        graph.add_edge(2, 3, -3);
+        graph.add_edge(3, 1, 6);
+        graph.add_edge(3, 4, 1);
+        graph.add_edge(5, 0, 1);
+        assert!(bellman_ford(&graph, &0).is_ok());
+
+        // Making the way back cheaper turns 1 -> 2 -> 3 -> 1 negative
+        graph.add_edge(3, 1, 4);
+        for result in [bellman_ford(&graph, &5).err(), floyd_warshall(&graph).err()] {
+            let Some(PathError::NegativeCycle(mut cycle)) = result else {
+                panic!("no negative cycle in {result:?}");
+            };
+            cycle.push(cycle[0]);
+            assert!(walk(&graph, &cycle) < 0, "{cycle:?} isn't negative");
+        }
+        // The cycle can't be reached from 4
+        assert!(bellman_ford(&graph, &4).is_ok());
+    }
+
+    #[test]
+    fn spanning_trees_agree() {
+        let mut graph = UndirectedGraph::<&str, u32>::new();
+        for (from, to, weight) in [
+            ("a", "b", 4),
+            ("a", "h", 8),
+            ("b", "c", 8),
+            ("b", "h", 11),
+            ("c", "d", 7),
+            ("c", "f", 4),
+            ("c", "i", 2),
+            ("d", "e", 9),
+            ("d", "f", 14),
+            ("e", "f", 10),
+            ("f", "g", 2),
+            ("g", "h", 1),
+            ("g", "i", 6),
+            ("h", "i", 7),
+        ] {
+            graph.add_edge(from, to, weight);
+        }
+        assert_eq!(prim(&graph).weight, 37);
+        assert_eq!(kruskal(&graph).weight, 37);
+        assert_eq!(kruskal(&graph).edges.len(), 8);
+
+        for seed in 0..20 {
+            let graph = random_graph::<Undirected, AdjacencyList<i64>>(seed, 15, 18, -5);
+            let prim = prim(&graph);
+            let kruskal = kruskal(&graph);
+            assert_eq!(prim.weight, kruskal.weight);
+            // A spanning forest has one edge fewer than nodes in every component
+            let forest_edges = graph.node_count() - connected_components(&graph).len();
+            assert_eq!(prim.edges.len(), forest_edges);
+            assert_eq!(kruskal.edges.len(), forest_edges);
+            for (from, to, weight) in prim.edges.iter().chain(&kruskal.edges) {
+                assert_eq!(graph.edge(from, to), Ok(Some(weight)));
+            }
+        }
+    }
+
+    #[test]
+    fn components_and_cycles() {
+        let mut graph = UndirectedGraph::<u32>::new();
+        graph.add_edge(1, 2, ());
+        graph.add_edge(2, 3, ());
+        graph.add_edge(4, 5, ());
+        graph.add_node(6);
+        assert_eq!(connected_components(&graph), [vec![1, 2, 3], vec![4, 5], vec![6]]);
+        // Going back along the edge just taken isn't a cycle
+        assert_eq!(find_cycle(&graph), None);
+        graph.add_edge(3, 1, ());
+        assert_eq!(find_cycle(&graph), Some(vec![1, 2, 3]));
+        graph.add_edge(6, 6, ());
+        assert_eq!(connected_components(&graph).len(), 3);
+
+        let mut dag = DirectedGraph::<u32>::new();
+        dag.add_edge(1, 2, ());
+        dag.add_edge(1, 3, ());
+        dag.add_edge(2, 3, ());
+        // Both ways from 1 to 3 isn't a cycle when the edges are directed
+        assert_eq!(find_cycle(&dag), None);
+        dag.add_edge(3, 4, ());
+        dag.add_edge(4, 2, ());
+        assert_eq!(find_cycle(&dag), Some(vec![2, 3, 4]));
+    }
+
+    #[test]
+    fn cycles_agree_with_union_find() {
+        for seed in 0..30 {
+            let graph = random_graph::<Undirected, AdjacencyList<i64>>(seed, 10, 9, 0);
+            // Without self-loops, an undirected graph has a cycle exactly
+            // when it has more edges than a spanning forest
+            let forest_edges = graph.node_count() - connected_components(&graph).len();
+            let cycle = find_cycle(&graph);
+            assert_eq!(cycle.is_some(), graph.edge_count() > forest_edges);
+            if let Some(mut cycle) = cycle {
+                assert!(cycle.len() >= 3);
+                cycle.push(cycle[0]);
+                walk(&graph, &cycle);
+            }
+
+            let mut sets = UnionFind::new(10);
+            for (from, to, _) in graph.edges() {
+                sets.union(*from as usize, *to as usize);
+            }
+            assert_eq!(sets.sets(), connected_components(&graph).len());
+        }
+    }
+
+    #[test]
+    fn union_find() {
+        let mut sets = UnionFind::new(5);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(3, 4));
+        assert!(!sets.union(1, 0));
+        assert!(sets.connected(0, 1));
+        assert!(!sets.connected(1, 3));
+        assert!(sets.union(1, 4));
+        assert!(sets.connected(0, 3));
+        assert_eq!(sets.sets(), 2);
+    }
+}
+
+#[cfg(test)]
+mod test_ordering {
+    use super::*;
+    use super::random::random_numbers;
+
+    fn random_digraph(seed: u64, nodes: u64, edges: usize, acyclic: bool) -> DirectedGraph<u64> {
+        let mut numbers = random_numbers(seed);
+        let mut graph = DirectedGraph::new();
+        // Added in a scrambled order, so that the order of the nodes says
+        // nothing about the edges
+        for node in 0..nodes {
+            graph.add_node((node * 7) % nodes);
+        }
+        for _ in 0..edges {
+            let (from, to) = (numbers.next().unwrap() % nodes, numbers.next().unwrap() % nodes);
+            if !acyclic || from < to {
+                graph.add_edge(from, to, ());
+            }
+        }
+        graph
+    }
+
+    // Every edge goes from a node to one later in the order
+    fn assert_topological(graph: &DirectedGraph<u64>, order: &[u64]) {
+        assert_eq!(order.len(), graph.node_count());
+        let position: HashMap<u64, usize> = order.iter().enumerate().map(|(i, node)| (*node, i)).collect();
+        for (from, to, _) in graph.edges() {
+            assert!(position[from] < position[to], "{from} -> {to} goes back in {order:?}");
         }
     }
-}
\ No newline at end of file
+
+    fn assert_cycle(graph: &DirectedGraph<u64>, cycle: &[u64]) {
+        assert!(!cycle.is_empty());
+        for (i, from) in cycle.iter().enumerate() {
+            let to = &cycle[(i + 1) % cycle.len()];
+            assert_eq!(graph.edge(from, to), Ok(Some(&())), "{cycle:?} isn't a cycle");
+        }
+    }
+
+    #[test]
+    fn both_topological_sorts_respect_every_edge() {
+        for seed in 0..20 {
+            let graph = random_digraph(seed, 15, 30, true);
+            assert_topological(&graph, &topological_sort(&graph).unwrap());
+            assert_topological(&graph, &topological_sort_dfs(&graph).unwrap());
+        }
+    }
+
+    #[test]
+    fn both_topological_sorts_report_a_cycle() {
+        for seed in 0..20 {
+            let mut graph = random_digraph(seed, 15, 30, true);
+            graph.add_edge(14, 0, ());
+            graph.add_edge(0, 14, ());
+            for result in [topological_sort(&graph), topological_sort_dfs(&graph)] {
+                let Err(Cycle(cycle)) = result else {
+                    panic!("no cycle in {result:?}");
+                };
+                assert_cycle(&graph, &cycle);
+            }
+        }
+        let mut graph = DirectedGraph::<&str>::new();
+        graph.add_edge("a", "a", ());
+        assert_eq!(topological_sort(&graph), Err(Cycle(vec!["a"])));
+        assert_eq!(
+            Cycle(vec!["a"]).to_string(),
+            r#"the graph has a cycle through ["a"]"#
+        );
+    }
+
+    #[test]
+    fn models_the_prerequisites_of_info_toml() {
+        // Each exercise depends on the one before it, unless it starts a
+        // chapter, and chapters can depend on other chapters
+        let mut graph = DirectedGraph::<&str>::new();
+        for exercise in ["intro1", "intro2", "vecs1", "vecs2", "iterators1", "quiz1"] {
+            graph.add_node(exercise);
+        }
+        graph.add_edge("intro1", "intro2", ());
+        graph.add_edge("vecs1", "vecs2", ());
+        graph.add_edge("intro2", "iterators1", ());
+        graph.add_edge("vecs2", "quiz1", ());
+        graph.add_edge("iterators1", "quiz1", ());
+        // What is ready first comes first, in the order of info.toml
+        assert_eq!(
+            topological_sort(&graph).unwrap(),
+            ["intro1", "intro2", "vecs1", "vecs2", "iterators1", "quiz1"]
+        );
+
+        graph.add_edge("quiz1", "intro1", ());
+        let Err(Cycle(cycle)) = topological_sort(&graph) else {
+            panic!("the prerequisites are circular");
+        };
+        assert!(cycle.contains(&"quiz1") && cycle.contains(&"intro1"));
+    }
+
+    // The components as sorted sets, to compare them without their order
+    fn normalized(components: &[Vec<u64>]) -> Vec<Vec<u64>> {
+        let mut components: Vec<Vec<u64>> = components
+            .iter()
+            .map(|component| {
+                let mut component = component.clone();
+                component.sort();
+                component
+            })
+            .collect();
+        components.sort();
+        components
+    }
+
+    #[test]
+    fn tarjan_and_kosaraju_agree_with_reachability() {
+        for seed in 0..20 {
+            let graph = random_digraph(seed, 12, 18, false);
+            let tarjan = tarjan_scc(&graph);
+            let kosaraju = kosaraju_scc(&graph);
+            assert_eq!(normalized(&tarjan), normalized(&kosaraju));
+
+            // Two nodes are in the same component exactly when each can
+            // reach the other
+            let reach: HashMap<u64, Vec<u64>> = graph
+                .nodes()
+                .map(|node| (*node, graph.bfs(node).unwrap().copied().collect()))
+                .collect();
+            for component in &tarjan {
+                for a in graph.nodes() {
+                    let together = component
+                        .iter()
+                        .all(|b| reach[a].contains(b) && reach[b].contains(a));
+                    assert_eq!(together, component.contains(a), "{a} and {component:?}");
+                }
+            }
+
+            // Both list a component before the ones its edges lead to
+            for components in [&tarjan, &kosaraju] {
+                let order: HashMap<u64, usize> = components
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, component)| component.iter().map(move |node| (*node, i)))
+                    .collect();
+                for (from, to, _) in graph.edges() {
+                    assert!(order[from] <= order[to]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn condensation_is_a_dag_in_topological_order() {
+        let mut graph = DirectedGraph::<&str>::new();
+        for (from, to) in [("a", "b"), ("b", "a"), ("b", "c"), ("c", "d"), ("d", "c"), ("d", "e"), ("a", "e")] {
+            graph.add_edge(from, to, ());
+        }
+        let condensed = condensation(&graph);
+        assert_eq!(condensed.components.len(), 3);
+        let (ab, cd, e) = (
+            condensed.component_of(&"a").unwrap(),
+            condensed.component_of(&"c").unwrap(),
+            condensed.component_of(&"e").unwrap(),
+        );
+        assert_eq!(condensed.component_of(&"b"), Some(ab));
+        assert_eq!(condensed.component_of(&"d"), Some(cd));
+        assert_eq!(condensed.component_of(&"z"), None);
+        assert_eq!((ab, cd, e), (0, 1, 2));
+        assert_eq!(condensed.graph.edge_count(), 3);
+
+        for seed in 0..20 {
+            let graph = random_digraph(seed, 15, 25, false);
+            let condensed = condensation(&graph);
+            assert_eq!(condensed.graph.node_count(), condensed.components.len());
+            assert!(find_cycle(&condensed.graph).is_none());
+            for (from, to, _) in condensed.graph.edges() {
+                assert!(from < to);
+            }
+            for (from, to, _) in graph.edges() {
+                let (from, to) = (condensed.component_of(from).unwrap(), condensed.component_of(to).unwrap());
+                assert!(from == to || condensed.graph.edge(&from, &to) == Ok(Some(&())));
+            }
+        }
+    }
+}
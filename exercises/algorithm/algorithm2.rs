@@ -4,7 +4,10 @@
     双向链表反转
 */
 
-use std::fmt::{self, Display, Formatter};
+use std::fmt::{self, Debug, Display, Formatter};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::mem;
 use std::ptr::NonNull;
 use std::vec::*;
 
@@ -24,11 +27,17 @@ impl<T> Node<T> {
         }
     }
 }
-#[derive(Debug)]
+
+// Every node is allocated with `Box::into_raw` and owned by exactly one
+// list, which frees it with `Box::from_raw` when it is removed or dropped.
+// Moving nodes between lists only relinks them, which is what keeps
+// `append`, `splice_after` and the splits of a cursor O(1).
 struct LinkedList<T> {
     length: u32,
     start: Option<NonNull<Node<T>>>,
     end: Option<NonNull<Node<T>>>,
+    // Tells the drop checker that the list owns values of `T`
+    marker: PhantomData<Box<Node<T>>>,
 }
 
 impl<T> Default for LinkedList<T> {
@@ -43,9 +52,18 @@ impl<T> LinkedList<T> {
             length: 0,
             start: None,
             end: None,
+            marker: PhantomData,
         }
     }
 
+    pub fn len(&self) -> usize {
+        self.length as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start.is_none()
+    }
+
     pub fn add(&mut self, obj: T) {
         let mut node = Box::new(Node::new(obj));
         node.next = None;
@@ -59,19 +77,177 @@ impl<T> LinkedList<T> {
         self.length += 1;
     }
 
-    pub fn get(&mut self, index: i32) -> Option<&T> {
-        self.get_ith_node(self.start, index)
+    pub fn push_back(&mut self, obj: T) {
+        self.add(obj);
+    }
+
+    pub fn push_front(&mut self, obj: T) {
+        self.link(None, self.start, obj);
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.end.map(|end_ptr| unsafe { self.unlink(end_ptr) })
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.start.map(|start_ptr| unsafe { self.unlink(start_ptr) })
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.start.map(|node| unsafe { &(*node.as_ptr()).val })
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.end.map(|node| unsafe { &(*node.as_ptr()).val })
+    }
+
+    // Put a new node with the value between two neighbouring nodes, where
+    // `None` stands for the start or the end of the list
+    fn link(
+        &mut self,
+        prev: Option<NonNull<Node<T>>>,
+        next: Option<NonNull<Node<T>>>,
+        obj: T,
+    ) {
+        let mut node = Box::new(Node::new(obj));
+        node.prev = prev;
+        node.next = next;
+        let node_ptr = Some(unsafe { NonNull::new_unchecked(Box::into_raw(node)) });
+        match prev {
+            None => self.start = node_ptr,
+            Some(prev_ptr) => unsafe { (*prev_ptr.as_ptr()).next = node_ptr },
+        }
+        match next {
+            None => self.end = node_ptr,
+            Some(next_ptr) => unsafe { (*next_ptr.as_ptr()).prev = node_ptr },
+        }
+        self.length += 1;
+    }
+
+    // Take the node out of the list and free it. The node has to belong to
+    // this list.
+    unsafe fn unlink(&mut self, node_ptr: NonNull<Node<T>>) -> T {
+        let node = Box::from_raw(node_ptr.as_ptr());
+        match node.prev {
+            None => self.start = node.next,
+            Some(prev_ptr) => (*prev_ptr.as_ptr()).next = node.next,
+        }
+        match node.next {
+            None => self.end = node.prev,
+            Some(next_ptr) => (*next_ptr.as_ptr()).prev = node.prev,
+        }
+        self.length -= 1;
+        node.val
+    }
+
+    // The node at the index, walking from whichever end is closer
+    fn ith_node(&self, index: usize) -> Option<NonNull<Node<T>>> {
+        if index >= self.len() {
+            return None;
+        }
+        if index < self.len() / 2 {
+            let mut node = self.start;
+            for _ in 0..index {
+                node = unsafe { (*node?.as_ptr()).next };
+            }
+            node
+        } else {
+            let mut node = self.end;
+            for _ in index + 1..self.len() {
+                node = unsafe { (*node?.as_ptr()).prev };
+            }
+            node
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.ith_node(index)
+            .map(|node| unsafe { &(*node.as_ptr()).val })
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.ith_node(index)
+            .map(|node| unsafe { &mut (*node.as_ptr()).val })
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            head: self.start,
+            tail: self.end,
+            len: self.len(),
+            marker: PhantomData,
+        }
     }
 
-    fn get_ith_node(&mut self, node: Option<NonNull<Node<T>>>, index: i32) -> Option<&T> {
-        match node {
-            None => None,
-            Some(next_ptr) => match index {
-                0 => Some(unsafe { &(*next_ptr.as_ptr()).val }),
-                _ => self.get_ith_node(unsafe { (*next_ptr.as_ptr()).next }, index - 1),
-            },
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            head: self.start,
+            tail: self.end,
+            len: self.len(),
+            marker: PhantomData,
         }
     }
+
+    // Move every value of `other` to the end of this list in O(1), leaving
+    // `other` empty
+    pub fn append(&mut self, other: &mut Self) {
+        let Some(end_ptr) = self.end else {
+            return mem::swap(self, other);
+        };
+        if let Some(other_start) = other.start.take() {
+            unsafe {
+                (*end_ptr.as_ptr()).next = Some(other_start);
+                (*other_start.as_ptr()).prev = Some(end_ptr);
+            }
+            self.end = other.end.take();
+            self.length += mem::replace(&mut other.length, 0);
+        }
+    }
+
+    // Split the list in two at the index, returning everything from the
+    // index on. Like `std::collections::LinkedList::split_off`, finding the
+    // index walks from the closer end, and the split itself is O(1).
+    // Panics if the index is greater than the length of the list.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let len = self.len();
+        assert!(at <= len, "cannot split off at a nonexistent index (is {at}, len is {len})");
+        if at == 0 {
+            return mem::take(self);
+        }
+        let mut cursor = if at - 1 < len - at {
+            let mut cursor = self.cursor_front_mut();
+            for _ in 0..at - 1 {
+                cursor.move_next();
+            }
+            cursor
+        } else {
+            let mut cursor = self.cursor_back_mut();
+            for _ in 0..len - at {
+                cursor.move_prev();
+            }
+            cursor
+        };
+        cursor.split_after()
+    }
+
+    // A cursor at the first value, or at the "ghost" position of an empty list
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            index: 0,
+            current: self.start,
+            list: self,
+        }
+    }
+
+    // A cursor at the last value, or at the "ghost" position of an empty list
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            index: self.len().saturating_sub(1),
+            current: self.end,
+            list: self,
+        }
+    }
+
 	pub fn reverse(&mut self){
 		// 1 、判断链表长度，如果小于等于 1 则无需反转。
         if self.length <= 1 {
@@ -99,33 +275,387 @@ impl<T> LinkedList<T> {
 	}
 }
 
-impl<T> Display for LinkedList<T>
-where
-    T: Display,
-{
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        match self.start {
-            Some(node) => write!(f, "{}", unsafe { node.as_ref() }),
-            None => Ok(()),
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+// A cursor that can walk the list in both directions and change it where it
+// stands. Past the last value and before the first one there is a "ghost"
+// position without a value, so moving off one end wraps around to the other.
+struct CursorMut<'a, T> {
+    // The index of the current value, or the length of the list at the ghost
+    index: usize,
+    current: Option<NonNull<Node<T>>>,
+    list: &'a mut LinkedList<T>,
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    // The index of the current value, or `None` at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        match self.current {
+            Some(current_ptr) => {
+                self.current = unsafe { (*current_ptr.as_ptr()).next };
+                self.index += 1;
+            }
+            None => {
+                self.current = self.list.start;
+                self.index = 0;
+            }
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        match self.current {
+            Some(current_ptr) => {
+                self.current = unsafe { (*current_ptr.as_ptr()).prev };
+                self.index = self.index.checked_sub(1).unwrap_or(self.list.len());
+            }
+            None => {
+                self.current = self.list.end;
+                self.index = self.list.len().saturating_sub(1);
+            }
+        }
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.current
+            .map(|node| unsafe { &mut (*node.as_ptr()).val })
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = match self.current {
+            Some(current_ptr) => unsafe { (*current_ptr.as_ptr()).next },
+            None => self.list.start,
+        };
+        next.map(|node| unsafe { &mut (*node.as_ptr()).val })
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = match self.current {
+            Some(current_ptr) => unsafe { (*current_ptr.as_ptr()).prev },
+            None => self.list.end,
+        };
+        prev.map(|node| unsafe { &mut (*node.as_ptr()).val })
+    }
+
+    // Insert the value after the current one, or at the front of the list
+    // at the ghost position
+    pub fn insert_after(&mut self, obj: T) {
+        match self.current {
+            Some(current_ptr) => {
+                let next = unsafe { (*current_ptr.as_ptr()).next };
+                self.list.link(Some(current_ptr), next, obj);
+            }
+            None => {
+                self.list.link(None, self.list.start, obj);
+                self.index = self.list.len();
+            }
+        }
+    }
+
+    // Insert the value before the current one, or at the back of the list
+    // at the ghost position
+    pub fn insert_before(&mut self, obj: T) {
+        let prev = match self.current {
+            Some(current_ptr) => unsafe { (*current_ptr.as_ptr()).prev },
+            None => self.list.end,
+        };
+        self.list.link(prev, self.current, obj);
+        self.index += 1;
+    }
+
+    // Remove the current value and move on to the next one
+    pub fn remove_current(&mut self) -> Option<T> {
+        let current_ptr = self.current?;
+        self.current = unsafe { (*current_ptr.as_ptr()).next };
+        Some(unsafe { self.list.unlink(current_ptr) })
+    }
+
+    // Move every value of `other` after the current one in O(1), or to the
+    // front of the list at the ghost position
+    pub fn splice_after(&mut self, mut other: LinkedList<T>) {
+        let (Some(other_start), Some(other_end)) = (other.start.take(), other.end.take()) else {
+            return;
+        };
+        let other_len = mem::replace(&mut other.length, 0);
+        let (prev, next) = match self.current {
+            Some(current_ptr) => (Some(current_ptr), unsafe { (*current_ptr.as_ptr()).next }),
+            None => (None, self.list.start),
+        };
+        unsafe {
+            (*other_start.as_ptr()).prev = prev;
+            (*other_end.as_ptr()).next = next;
+            match prev {
+                None => self.list.start = Some(other_start),
+                Some(prev_ptr) => (*prev_ptr.as_ptr()).next = Some(other_start),
+            }
+            match next {
+                None => self.list.end = Some(other_end),
+                Some(next_ptr) => (*next_ptr.as_ptr()).prev = Some(other_end),
+            }
+        }
+        self.list.length += other_len;
+        if self.current.is_none() {
+            self.index = self.list.len();
+        }
+    }
+
+    // Split the list in O(1), returning everything after the current value,
+    // or the whole list at the ghost position
+    pub fn split_after(&mut self) -> LinkedList<T> {
+        let (split_start, split_len) = match self.current {
+            Some(current_ptr) => (
+                unsafe { (*current_ptr.as_ptr()).next },
+                self.list.len() - self.index - 1,
+            ),
+            None => (self.list.start, self.list.len()),
+        };
+        let Some(split_start) = split_start else {
+            return LinkedList::new();
+        };
+        unsafe {
+            match (*split_start.as_ptr()).prev.take() {
+                None => self.list.start = None,
+                Some(prev_ptr) => (*prev_ptr.as_ptr()).next = None,
+            }
+        }
+        let split_end = mem::replace(&mut self.list.end, self.current);
+        self.list.length -= split_len as u32;
+        if self.current.is_none() {
+            self.index = 0;
+        }
+        LinkedList {
+            length: split_len as u32,
+            start: Some(split_start),
+            end: split_end,
+            marker: PhantomData,
+        }
+    }
+
+    // Split the list in O(1), returning everything before the current value,
+    // or the whole list at the ghost position
+    pub fn split_before(&mut self) -> LinkedList<T> {
+        let (split_end, split_len) = match self.current {
+            Some(current_ptr) => (unsafe { (*current_ptr.as_ptr()).prev }, self.index),
+            None => (self.list.end, self.list.len()),
+        };
+        let Some(split_end) = split_end else {
+            return LinkedList::new();
+        };
+        unsafe {
+            match (*split_end.as_ptr()).next.take() {
+                None => self.list.end = None,
+                Some(next_ptr) => (*next_ptr.as_ptr()).prev = None,
+            }
+        }
+        let split_start = mem::replace(&mut self.list.start, self.current);
+        self.list.length -= split_len as u32;
+        self.index = 0;
+        LinkedList {
+            length: split_len as u32,
+            start: split_start,
+            end: Some(split_end),
+            marker: PhantomData,
         }
     }
 }
 
-impl<T> Display for Node<T>
+struct Iter<'a, T> {
+    head: Option<NonNull<Node<T>>>,
+    tail: Option<NonNull<Node<T>>>,
+    len: usize,
+    marker: PhantomData<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.head.map(|node_ptr| {
+            let node = unsafe { &*node_ptr.as_ptr() };
+            self.head = node.next;
+            self.len -= 1;
+            &node.val
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.tail.map(|node_ptr| {
+            let node = unsafe { &*node_ptr.as_ptr() };
+            self.tail = node.prev;
+            self.len -= 1;
+            &node.val
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+struct IterMut<'a, T> {
+    head: Option<NonNull<Node<T>>>,
+    tail: Option<NonNull<Node<T>>>,
+    len: usize,
+    marker: PhantomData<&'a mut Node<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.head.map(|node_ptr| {
+            let node = unsafe { &mut *node_ptr.as_ptr() };
+            self.head = node.next;
+            self.len -= 1;
+            &mut node.val
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.tail.map(|node_ptr| {
+            let node = unsafe { &mut *node_ptr.as_ptr() };
+            self.tail = node.prev;
+            self.len -= 1;
+            &mut node.val
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+struct IntoIter<T>(LinkedList<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = LinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for obj in iter {
+            self.add(obj);
+        }
+    }
+}
+
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
+impl<T: Debug> Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T> Display for LinkedList<T>
 where
     T: Display,
 {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        match self.next {
-            Some(node) => write!(f, "{}, {}", self.val, unsafe { node.as_ref() }),
-            None => write!(f, "{}", self.val),
+        for (i, val) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", val)?;
         }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::LinkedList;
+    use std::rc::Rc;
 
     #[test]
     fn create_numeric_list() {
@@ -159,7 +689,7 @@ mod tests {
 		list.reverse();
 		println!("Reversed Linked List is {}", list);
 		for i in 0..original_vec.len(){
-			assert_eq!(reverse_vec[i],*list.get(i as i32).unwrap());
+			assert_eq!(reverse_vec[i],*list.get(i).unwrap());
 		}
 	}
 
@@ -175,7 +705,210 @@ mod tests {
 		list.reverse();
 		println!("Reversed Linked List is {}", list);
 		for i in 0..original_vec.len(){
-			assert_eq!(reverse_vec[i],*list.get(i as i32).unwrap());
+			assert_eq!(reverse_vec[i],*list.get(i).unwrap());
 		}
 	}
-}
\ No newline at end of file
+
+    // The values from the front and from the back, which checks both the
+    // `next` and the `prev` links
+    fn both_ways(list: &LinkedList<i32>) -> (Vec<i32>, Vec<i32>) {
+        let forwards = list.iter().copied().collect();
+        let mut backwards: Vec<i32> = list.iter().rev().copied().collect();
+        backwards.reverse();
+        (forwards, backwards)
+    }
+
+    #[test]
+    fn reverse_keeps_both_directions() {
+        let mut list: LinkedList<i32> = (1..=4).collect();
+        list.reverse();
+        assert_eq!(both_ways(&list), (vec![4, 3, 2, 1], vec![4, 3, 2, 1]));
+        list.push_back(0);
+        list.push_front(5);
+        assert_eq!(both_ways(&list), (vec![5, 4, 3, 2, 1, 0], vec![5, 4, 3, 2, 1, 0]));
+    }
+
+    #[test]
+    fn push_and_pop_both_ends() {
+        let mut list = LinkedList::new();
+        list.push_back(2);
+        list.push_front(1);
+        list.push_back(3);
+        assert_eq!((list.front(), list.back()), (Some(&1), Some(&3)));
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!(list.pop_back(), None);
+        assert_eq!(list.pop_front(), None);
+        assert!(list.is_empty());
+        list.push_front(4);
+        assert_eq!((list.front(), list.back()), (Some(&4), Some(&4)));
+    }
+
+    #[test]
+    fn iterate_from_both_ends() {
+        let mut list: LinkedList<i32> = (1..=5).collect();
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&5));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+        for val in list.iter_mut().rev().take(2) {
+            *val *= 10;
+        }
+        *list.get_mut(2).unwrap() = 30;
+        assert_eq!(list.to_string(), "1, 2, 30, 40, 50");
+        assert_eq!(list.get(3), Some(&40));
+        assert_eq!(list.get(5), None);
+        assert_eq!(list.into_iter().rev().collect::<Vec<_>>(), [50, 40, 30, 2, 1]);
+    }
+
+    #[test]
+    fn append_moves_every_value() {
+        let mut list: LinkedList<i32> = (1..=2).collect();
+        let mut other: LinkedList<i32> = (3..=4).collect();
+        list.append(&mut other);
+        assert!(other.is_empty());
+        assert_eq!(list.len(), 4);
+        assert_eq!(both_ways(&list), (vec![1, 2, 3, 4], vec![1, 2, 3, 4]));
+        let mut empty = LinkedList::new();
+        empty.append(&mut list);
+        assert!(list.is_empty());
+        assert_eq!(both_ways(&empty).0, [1, 2, 3, 4]);
+        empty.append(&mut list);
+        assert_eq!(empty.len(), 4);
+    }
+
+    #[test]
+    fn split_off_at_every_index() {
+        for at in 0..=5 {
+            let mut list: LinkedList<i32> = (0..5).collect();
+            let tail = list.split_off(at);
+            let expected_head: Vec<i32> = (0..at as i32).collect();
+            let expected_tail: Vec<i32> = (at as i32..5).collect();
+            assert_eq!(both_ways(&list), (expected_head.clone(), expected_head));
+            assert_eq!(both_ways(&tail), (expected_tail.clone(), expected_tail));
+            assert_eq!(list.len() + tail.len(), 5);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_off_past_the_end() {
+        let mut list: LinkedList<i32> = (0..2).collect();
+        list.split_off(3);
+    }
+
+    #[test]
+    fn cursor_walks_through_the_ghost() {
+        let mut list: LinkedList<i32> = (1..=3).collect();
+        let mut cursor = list.cursor_front_mut();
+        assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&mut 1)));
+        cursor.move_prev();
+        assert_eq!((cursor.index(), cursor.current()), (None, None));
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+        assert_eq!(cursor.peek_prev(), Some(&mut 3));
+        cursor.move_prev();
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&mut 3)));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&mut 1)));
+
+        let mut empty = LinkedList::<i32>::new();
+        let mut cursor = empty.cursor_back_mut();
+        assert_eq!(cursor.current(), None);
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+    }
+
+    #[test]
+    fn cursor_inserts_and_removes() {
+        let mut list: LinkedList<i32> = vec![1, 3].into_iter().collect();
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_after(2);
+        cursor.insert_before(0);
+        assert_eq!((cursor.index(), cursor.current()), (Some(1), Some(&mut 1)));
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(2));
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&mut 3)));
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+        // At the ghost, inserting after adds to the front and inserting before
+        // adds to the back
+        cursor.insert_after(-1);
+        cursor.insert_before(4);
+        cursor.move_prev();
+        assert_eq!((cursor.index(), cursor.current()), (Some(3), Some(&mut 4)));
+        assert_eq!(both_ways(&list), (vec![-1, 0, 1, 4], vec![-1, 0, 1, 4]));
+    }
+
+    #[test]
+    fn cursor_splits_and_splices() {
+        let mut list: LinkedList<i32> = (0..6).collect();
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        let after = cursor.split_after();
+        let before = cursor.split_before();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(both_ways(&before), (vec![0, 1], vec![0, 1]));
+        assert_eq!(both_ways(&after), (vec![3, 4, 5], vec![3, 4, 5]));
+        cursor.splice_after(after);
+        cursor.move_prev();
+        cursor.splice_after(before);
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(both_ways(&list), (vec![0, 1, 2, 3, 4, 5], vec![0, 1, 2, 3, 4, 5]));
+        assert_eq!(list.len(), 6);
+    }
+
+    #[test]
+    fn cursor_moves_a_value_to_the_front() {
+        // The building block of an LRU cache: find a key, take it out and
+        // put it back as the most recently used one
+        let mut recent: LinkedList<&str> = vec!["a", "b", "c"].into_iter().collect();
+        let mut cursor = recent.cursor_front_mut();
+        while cursor.current().is_some_and(|key| *key != "b") {
+            cursor.move_next();
+        }
+        let key = cursor.remove_current().unwrap();
+        recent.push_front(key);
+        assert_eq!(recent.to_string(), "b, a, c");
+        assert_eq!(recent.pop_back(), Some("c"));
+    }
+
+    #[test]
+    fn clone_and_compare() {
+        let list: LinkedList<String> = ["x", "y"].iter().map(|s| s.to_string()).collect();
+        let mut copy = list.clone();
+        assert_eq!(list, copy);
+        copy.push_front("w".to_string());
+        assert_ne!(list, copy);
+        assert_eq!(format!("{:?}", list), r#"["x", "y"]"#);
+    }
+
+    #[test]
+    fn drop_frees_every_value() {
+        let counter = Rc::new(());
+        let mut list: LinkedList<Rc<()>> = (0..6).map(|_| Rc::clone(&counter)).collect();
+        let mut tail = list.split_off(3);
+        tail.pop_back();
+        assert_eq!(Rc::strong_count(&counter), 6);
+        let mut into_iter = tail.clone().into_iter();
+        into_iter.next_back();
+        drop(into_iter);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.remove_current();
+        drop(cursor.split_after());
+        drop(tail);
+        drop(list);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
@@ -6,16 +6,69 @@
 */
 
 
-use std::cmp::PartialOrd;
+use std::cmp::Ordering;
+use std::mem;
+
+// 随机测试用的数在 random 模块里，其他算法题也用它
+#[cfg(test)]
+mod random;
+
+// Slices at most this long are left to insertion sort by quick sort
+const INSERTION_THRESHOLD: usize = 16;
+
+// A sorting algorithm. Only `sort_by` has to be implemented, the other ways
+// of sorting compare through it. Values are only ever swapped, so they don't
+// have to be `Copy` or `Clone`.
+trait Sorter {
+    // Whether values that compare equal keep the order they had
+    fn is_stable(&self) -> bool;
+
+    fn sort_by<T, F>(&self, array: &mut [T], compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering;
+
+    fn sort<T: Ord>(&self, array: &mut [T]) {
+        self.sort_by(array, T::cmp);
+    }
+
+    fn sort_by_key<T, K, F>(&self, array: &mut [T], mut key: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(array, |a, b| key(a).cmp(&key(b)));
+    }
+}
+
+struct BubbleSort;
+struct InsertionSort;
+struct MergeSort;
+struct HeapSort;
+struct QuickSort;
 
 // 冒泡排序
 // 通过反复比较相邻的元素，如果顺序错误就进行交换，每一轮都将最大的元素 “浮” 到数组的末尾。
-fn bubble_sort<T: PartialOrd + Copy>(array: &mut [T]) {
-    let n = array.len();
-    for i in 0..n {
-        for j in 0..n - i - 1 {
-            if array[j] > array[j + 1] {
-                array.swap(j, j + 1);
+impl Sorter for BubbleSort {
+    fn is_stable(&self) -> bool {
+        true
+    }
+
+    fn sort_by<T, F>(&self, array: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let n = array.len();
+        for i in 0..n {
+            let mut swapped = false;
+            for j in 0..n - i - 1 {
+                if compare(&array[j], &array[j + 1]) == Ordering::Greater {
+                    array.swap(j, j + 1);
+                    swapped = true;
+                }
+            }
+            // Nothing moved, so the rest is already in order
+            if !swapped {
+                break;
             }
         }
     }
@@ -23,117 +76,399 @@ fn bubble_sort<T: PartialOrd + Copy>(array: &mut [T]) {
 
 // 插入排序
 // 将待排序的元素插入到已排序的部分中的适当位置，从第二个元素开始，依次与已排序部分的元素比较并插入。
-fn insertion_sort<T: PartialOrd + Copy>(array: &mut [T]) {
-    for i in 1..array.len() {
-        let key = array[i];
-        let mut j = i - 1;
+impl Sorter for InsertionSort {
+    fn is_stable(&self) -> bool {
+        true
+    }
+
+    fn sort_by<T, F>(&self, array: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        insertion_sort_by(array, &mut compare);
+    }
+}
 
-        while j >= 0 && array[j] > key {
-            array[j + 1] = array[j];
+fn insertion_sort_by<T, F>(array: &mut [T], compare: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    for i in 1..array.len() {
+        let mut j = i;
+        while j > 0 && compare(&array[j - 1], &array[j]) == Ordering::Greater {
+            array.swap(j - 1, j);
             j -= 1;
         }
-        array[j + 1] = key;
     }
 }
 
-// 堆排序辅助函数：维护最大堆性质
-// 首先将数组构建成一个最大堆，然后依次将堆顶元素与末尾元素交换，并对新的堆顶元素进行调整，以维持最大堆性质。
-fn heapify<T: PartialOrd + Copy>(array: &mut [T], n: usize, i: usize) {
-    let mut largest = i;
-    let l = 2 * i + 1;
-    let r = 2 * i + 2;
+// 归并排序
+// 把数组分成两半分别排序，再把两个有序的部分合并起来。相等的元素总是先取左半部分的，所以排序是稳定的。
+impl Sorter for MergeSort {
+    fn is_stable(&self) -> bool {
+        true
+    }
+
+    fn sort_by<T, F>(&self, array: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // Values can't be moved out of the slice into a buffer without
+        // `Clone`, so the indices are merge sorted instead, and the values
+        // are then swapped into that order
+        let mut order: Vec<usize> = (0..array.len()).collect();
+        let mut buffer = order.clone();
+        merge_sort_indices(&mut order, &mut buffer, &mut |a, b| compare(&array[a], &array[b]));
+        apply_order(array, &mut order);
+    }
+}
 
-    if l < n && array[i] < array[l] {
-        largest = l;
+fn merge_sort_indices<F>(order: &mut [usize], buffer: &mut [usize], compare: &mut F)
+where
+    F: FnMut(usize, usize) -> Ordering,
+{
+    if order.len() <= 1 {
+        return;
     }
+    let mid = order.len() / 2;
+    merge_sort_indices(&mut order[..mid], &mut buffer[..mid], compare);
+    merge_sort_indices(&mut order[mid..], &mut buffer[mid..], compare);
 
-    if r < n && array[largest] < array[r] {
-        largest = r;
+    let (mut i, mut j) = (0, mid);
+    for slot in buffer.iter_mut() {
+        // Taking from the left half on ties is what keeps the sort stable
+        if j == order.len() || (i < mid && compare(order[i], order[j]) != Ordering::Greater) {
+            *slot = order[i];
+            i += 1;
+        } else {
+            *slot = order[j];
+            j += 1;
+        }
     }
+    order.copy_from_slice(buffer);
+}
 
-    if largest!= i {
-        array.swap(i, largest);
-        heapify(array, n, largest);
+// Rearrange the values so that position `i` holds the value that was at
+// `order[i]`, following each cycle of the permutation with swaps
+fn apply_order<T>(array: &mut [T], order: &mut [usize]) {
+    for start in 0..order.len() {
+        let mut current = start;
+        while order[current] != start {
+            let next = order[current];
+            array.swap(current, next);
+            order[current] = current;
+            current = next;
+        }
+        order[current] = current;
     }
 }
 
 // 堆排序
-fn heap_sort<T: PartialOrd + Copy>(array: &mut [T]) {
-    let n = array.len();
+// 首先将数组构建成一个最大堆，然后依次将堆顶元素与末尾元素交换，并对新的堆顶元素进行调整，以维持最大堆性质。
+impl Sorter for HeapSort {
+    fn is_stable(&self) -> bool {
+        false
+    }
 
-    for i in (n / 2 - 1)..=0 {
-        heapify(array, n, i as usize);
+    fn sort_by<T, F>(&self, array: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        heap_sort_by(array, &mut compare);
     }
+}
 
-    for i in (n - 1)..0 {
+fn heap_sort_by<T, F>(array: &mut [T], compare: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let n = array.len();
+    for i in (0..n / 2).rev() {
+        heapify(array, n, i, compare);
+    }
+    for i in (1..n).rev() {
         array.swap(0, i);
-        heapify(array, i, 0);
+        heapify(array, i, 0, compare);
+    }
+}
+
+// 堆排序辅助函数：维护最大堆性质
+fn heapify<T, F>(array: &mut [T], n: usize, mut i: usize, compare: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    loop {
+        let mut largest = i;
+        let l = 2 * i + 1;
+        let r = 2 * i + 2;
+
+        if l < n && compare(&array[largest], &array[l]) == Ordering::Less {
+            largest = l;
+        }
+
+        if r < n && compare(&array[largest], &array[r]) == Ordering::Less {
+            largest = r;
+        }
+
+        if largest == i {
+            return;
+        }
+        array.swap(i, largest);
+        i = largest;
     }
 }
 
 // 快排
-fn quick_sort<T: PartialOrd + Copy>(array: &mut [T]) {
-    quick_sort_helper(array, 0, array.len() - 1);
+// The pivot is the median of the first, middle and last values, so sorted
+// input splits evenly. Only the smaller side is sorted recursively, which
+// keeps the stack O(log n), and after too many uneven splits the rest is
+// left to heap sort, so the worst case stays O(n log n).
+impl Sorter for QuickSort {
+    fn is_stable(&self) -> bool {
+        false
+    }
+
+    fn sort_by<T, F>(&self, array: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let depth_limit = 2 * (usize::BITS - array.len().leading_zeros());
+        quick_sort_by(array, &mut compare, depth_limit);
+    }
 }
 
-fn quick_sort_helper<T: PartialOrd + Copy>(array: &mut [T], low: usize, high: usize) {
-    if low < high {
-        let pivot_index = partition(array, low, high);
-        quick_sort_helper(array, low, pivot_index - 1);
-        quick_sort_helper(array, pivot_index + 1, high);
+fn quick_sort_by<T, F>(mut array: &mut [T], compare: &mut F, mut depth_limit: u32)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    loop {
+        if array.len() <= INSERTION_THRESHOLD {
+            return insertion_sort_by(array, compare);
+        }
+        if depth_limit == 0 {
+            return heap_sort_by(array, compare);
+        }
+        depth_limit -= 1;
+
+        let pivot_index = partition(array, compare);
+        let (left, right) = mem::take(&mut array).split_at_mut(pivot_index);
+        let right = &mut right[1..];
+        if left.len() < right.len() {
+            quick_sort_by(left, compare, depth_limit);
+            array = right;
+        } else {
+            quick_sort_by(right, compare, depth_limit);
+            array = left;
+        }
     }
 }
 
-// 将数组中小于等于枢轴的元素放在左边，大于枢轴的元素放在右边，返回枢轴的最终位置。
-fn partition<T: PartialOrd + Copy>(array: &mut [T], low: usize, high: usize) -> usize {
-    let pivot = array[high];
-    let mut i = low - 1;
+// 将数组中小于等于枢轴的元素放在左边，大于等于枢轴的元素放在右边，返回枢轴的最终位置。
+// Both scans stop at values equal to the pivot, so runs of equal values are
+// split down the middle instead of all ending up on one side.
+fn partition<T, F>(array: &mut [T], compare: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let (a, b, c) = (0, array.len() / 2, array.len() - 1);
+    if compare(&array[b], &array[a]) == Ordering::Less {
+        array.swap(a, b);
+    }
+    if compare(&array[c], &array[b]) == Ordering::Less {
+        array.swap(b, c);
+    }
+    if compare(&array[b], &array[a]) == Ordering::Less {
+        array.swap(a, b);
+    }
+    array.swap(0, b);
 
-    for j in low..high {
-        if array[j] <= pivot {
+    let (pivot, rest) = array.split_at_mut(1);
+    let pivot = &pivot[0];
+    let (mut i, mut j) = (0, rest.len());
+    loop {
+        while i < j && compare(&rest[i], pivot) == Ordering::Less {
             i += 1;
-            array.swap(i, j);
         }
+        while i < j && compare(&rest[j - 1], pivot) == Ordering::Greater {
+            j -= 1;
+        }
+        if i >= j {
+            break;
+        }
+        rest.swap(i, j - 1);
+        i += 1;
+        j -= 1;
     }
 
-    array.swap(i + 1, high);
-    i + 1
+    // `rest[..i]` is now at most the pivot, so the pivot goes right after it
+    array.swap(0, i);
+    i
 }
 
-// 统一的 sort 函数，根据传入的参数选择排序算法
-fn sort<T: PartialOrd + Copy>(array: &mut [T], algorithm: &str) {
-    match algorithm {
-        "bubble" => bubble_sort(array),
-        "insertion" => insertion_sort(array),
-        "heap" => heap_sort(array),
-        "quick" => quick_sort(array),
-        _ => panic!("Invalid sorting algorithm"),
+// 可选的排序算法，用枚举代替字符串，拼错的名字在编译时就会报错
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Algorithm {
+    Bubble,
+    Insertion,
+    Merge,
+    Heap,
+    Quick,
+}
+
+impl Algorithm {
+    const ALL: [Algorithm; 5] = [
+        Algorithm::Bubble,
+        Algorithm::Insertion,
+        Algorithm::Merge,
+        Algorithm::Heap,
+        Algorithm::Quick,
+    ];
+}
+
+impl Sorter for Algorithm {
+    fn is_stable(&self) -> bool {
+        match self {
+            Algorithm::Bubble => BubbleSort.is_stable(),
+            Algorithm::Insertion => InsertionSort.is_stable(),
+            Algorithm::Merge => MergeSort.is_stable(),
+            Algorithm::Heap => HeapSort.is_stable(),
+            Algorithm::Quick => QuickSort.is_stable(),
+        }
+    }
+
+    fn sort_by<T, F>(&self, array: &mut [T], compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        match self {
+            Algorithm::Bubble => BubbleSort.sort_by(array, compare),
+            Algorithm::Insertion => InsertionSort.sort_by(array, compare),
+            Algorithm::Merge => MergeSort.sort_by(array, compare),
+            Algorithm::Heap => HeapSort.sort_by(array, compare),
+            Algorithm::Quick => QuickSort.sort_by(array, compare),
+        }
     }
 }
 
+// 统一的 sort 函数，根据传入的参数选择排序算法
+fn sort<T: Ord>(array: &mut [T], algorithm: Algorithm) {
+    algorithm.sort(array);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::random::random_numbers;
 
     #[test]
     fn test_sort_1() {
         let mut vec = vec![37, 73, 57, 75, 91, 19, 46, 64];
-        sort(&mut vec, "bubble");
-        // sort(&mut vec, "quick");
+        sort(&mut vec, Algorithm::Bubble);
+        // sort(&mut vec, Algorithm::Quick);
         assert_eq!(vec, vec![19, 37, 46, 57, 64, 73, 75, 91]);
     }
 	#[test]
     fn test_sort_2() {
         let mut vec = vec![1];
-        sort(&mut vec, "bubble");
-        // sort(&mut vec, "quick");
+        sort(&mut vec, Algorithm::Bubble);
+        // sort(&mut vec, Algorithm::Quick);
         assert_eq!(vec, vec![1]);
     }
 	#[test]
     fn test_sort_3() {
         let mut vec = vec![99, 88, 77, 66, 55, 44, 33, 22, 11];
-        sort(&mut vec, "bubble");
-        // sort(&mut vec, "quick");
+        sort(&mut vec, Algorithm::Bubble);
+        // sort(&mut vec, Algorithm::Quick);
         assert_eq!(vec, vec![11, 22, 33, 44, 55, 66, 77, 88, 99]);
     }
-}
\ No newline at end of file
+
+    // `len` numbers below 100, with plenty of duplicates to sort
+    fn random_vec(len: usize, seed: u64) -> Vec<i64> {
+        random_numbers(seed).take(len).map(|n| (n % 100) as i64).collect()
+    }
+
+    #[test]
+    fn every_algorithm_agrees_with_std() {
+        for algorithm in Algorithm::ALL {
+            for len in [0, 1, 2, 3, 17, 100, 1000] {
+                let mut vec = random_vec(len, len as u64);
+                let mut expected = vec.clone();
+                expected.sort();
+                sort(&mut vec, algorithm);
+                assert_eq!(vec, expected, "{algorithm:?} sorting {len} values");
+            }
+        }
+    }
+
+    #[test]
+    fn sorts_values_that_are_not_copy() {
+        for algorithm in Algorithm::ALL {
+            let mut words: Vec<String> = ["pear", "apple", "fig", "banana"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+            algorithm.sort(&mut words);
+            assert_eq!(words, ["apple", "banana", "fig", "pear"], "{algorithm:?}");
+        }
+    }
+
+    #[test]
+    fn sort_by_and_sort_by_key() {
+        for algorithm in Algorithm::ALL {
+            let mut vec = vec![3, 1, 2];
+            algorithm.sort_by(&mut vec, |a, b| b.cmp(a));
+            assert_eq!(vec, [3, 2, 1], "{algorithm:?}");
+
+            let mut floats = vec![2.5, -1.0, 0.5];
+            algorithm.sort_by(&mut floats, f64::total_cmp);
+            assert_eq!(floats, [-1.0, 0.5, 2.5], "{algorithm:?}");
+
+            let mut words = vec!["ccc", "a", "bb"];
+            algorithm.sort_by_key(&mut words, |word| word.len());
+            assert_eq!(words, ["a", "bb", "ccc"], "{algorithm:?}");
+        }
+    }
+
+    #[test]
+    fn stable_algorithms_keep_equal_values_in_order() {
+        let pairs: Vec<(i64, usize)> = random_vec(500, 7)
+            .into_iter()
+            .map(|key| key % 10)
+            .enumerate()
+            .map(|(i, key)| (key, i))
+            .collect();
+        for algorithm in Algorithm::ALL.into_iter().filter(|a| a.is_stable()) {
+            let mut vec = pairs.clone();
+            algorithm.sort_by_key(&mut vec, |&(key, _)| key);
+            // Sorting by the key and then the original position is the
+            // only order a stable sort can give
+            let mut expected = pairs.clone();
+            expected.sort();
+            assert_eq!(vec, expected, "{algorithm:?} isn't stable");
+        }
+    }
+
+    #[test]
+    fn quick_sort_handles_adversarial_input() {
+        let sorted: Vec<i64> = (0..100_000).collect();
+        let inputs = [
+            sorted.clone(),
+            sorted.iter().rev().copied().collect(),
+            vec![5; 100_000],
+            // The classic input that makes median-of-three quadratic
+            (0..100_000).map(|i| if i % 2 == 0 { i } else { 100_000 - i }).collect(),
+        ];
+        for mut vec in inputs {
+            let mut expected = vec.clone();
+            expected.sort();
+            let mut comparisons = 0;
+            QuickSort.sort_by(&mut vec, |a, b| {
+                comparisons += 1;
+                a.cmp(b)
+            });
+            assert_eq!(vec, expected);
+            // Far below the 5 billion of a quadratic sort
+            assert!(comparisons < 10_000_000, "{comparisons} comparisons");
+        }
+    }
+}
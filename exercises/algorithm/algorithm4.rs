@@ -4,8 +4,16 @@
     二叉搜索树
 */
 
-use std::cmp::Ordering;
-use std::fmt::Debug;
+use std::borrow::Borrow;
+use std::cmp::{self, Ordering};
+use std::fmt::{self, Debug, Formatter};
+use std::iter::FromIterator;
+use std::mem;
+use std::ops::{Bound, RangeBounds};
+
+// 随机测试用的数在 random 模块里，其他算法题也用它
+#[cfg(test)]
+mod random;
 
 
 #[derive(Debug)]
@@ -24,6 +32,7 @@ where
     T: Ord,
 {
     root: Option<Box<TreeNode<T>>>,
+    len: usize,
 }
 
 impl<T> TreeNode<T>
@@ -39,13 +48,23 @@ where
     }
 }
 
+// A tree that isn't balanced can be as deep as it is long, so nothing here
+// walks it recursively
 impl<T> BinarySearchTree<T>
 where
     T: Ord,
 {
 
     fn new() -> Self {
-        BinarySearchTree { root: None }
+        BinarySearchTree { root: None, len: 0 }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn is_empty(&self) -> bool {
+        self.root.is_none()
     }
 
     // Insert a value into the BST
@@ -53,6 +72,7 @@ where
         // 如果根节点为空，创建一个新的根节点并存储该值
         if let None = self.root {
             self.root = Some(Box::new(TreeNode::new(value)));
+            self.len += 1;
             return;
         }
 
@@ -66,6 +86,7 @@ where
                     // 如果左子节点为空，创建新的左子节点并存储该值
                     if let None = node.left {
                         node.left = Some(Box::new(TreeNode::new(value)));
+                        self.len += 1;
                         return;
                     }
                     // 否则继续在左子树中查找插入位置
@@ -76,6 +97,7 @@ where
                     // 如果右子节点为空，创建新的右子节点并存储该值
                     if let None = node.right {
                         node.right = Some(Box::new(TreeNode::new(value)));
+                        self.len += 1;
                         return;
                     }
                     // 否则继续在右子树中查找插入位置
@@ -105,6 +127,102 @@ where
         // 未找到返回 false
         false
     }
+
+    // Remove a value from the BST, returning whether it was there. A node
+    // with two children takes over the smallest value of its right subtree.
+    fn remove(&mut self, value: &T) -> bool {
+        let mut current = &mut self.root;
+        while current.as_ref().is_some_and(|node| *value != node.value) {
+            let node = current.as_mut().unwrap();
+            current = if *value < node.value {
+                &mut node.left
+            } else {
+                &mut node.right
+            };
+        }
+        let Some(mut node) = current.take() else {
+            return false;
+        };
+        *current = match (node.left.take(), node.right.take()) {
+            (None, None) => None,
+            (Some(child), None) | (None, Some(child)) => Some(child),
+            (Some(left), right) => {
+                let mut right = right;
+                node.value = take_min(&mut right);
+                node.left = Some(left);
+                node.right = right;
+                Some(node)
+            }
+        };
+        self.len -= 1;
+        true
+    }
+
+    fn min(&self) -> Option<&T> {
+        let mut node = self.root.as_deref()?;
+        while let Some(left) = node.left.as_deref() {
+            node = left;
+        }
+        Some(&node.value)
+    }
+
+    fn max(&self) -> Option<&T> {
+        let mut node = self.root.as_deref()?;
+        while let Some(right) = node.right.as_deref() {
+            node = right;
+        }
+        Some(&node.value)
+    }
+
+    // The number of nodes on the longest path from the root to a leaf,
+    // counted one level at a time
+    fn height(&self) -> usize {
+        let mut height = 0;
+        let mut level: Vec<&TreeNode<T>> = self.root.as_deref().into_iter().collect();
+        while !level.is_empty() {
+            height += 1;
+            level = level
+                .iter()
+                .flat_map(|node| node.left.as_deref().into_iter().chain(node.right.as_deref()))
+                .collect();
+        }
+        height
+    }
+
+    // The values in ascending order
+    fn iter(&self) -> InOrder<'_, TreeNode<T>> {
+        InOrder::new(self.root.as_deref())
+    }
+
+    // Every node before its subtrees, left subtree first
+    fn pre_order(&self) -> PreOrder<'_, TreeNode<T>> {
+        PreOrder::new(self.root.as_deref())
+    }
+
+    // Every node after its subtrees, left subtree first
+    fn post_order(&self) -> PostOrder<'_, TreeNode<T>> {
+        PostOrder::new(self.root.as_deref())
+    }
+
+    // The values in the range in ascending order, skipping the subtrees
+    // that lie outside of it
+    fn range<R>(&self, range: R) -> Range<'_, TreeNode<T>, T, R>
+    where
+        R: RangeBounds<T>,
+    {
+        Range::new(self.root.as_deref(), range, identity)
+    }
+}
+
+// Take the smallest value out of a subtree that isn't empty
+fn take_min<T: Ord>(link: &mut Option<Box<TreeNode<T>>>) -> T {
+    let mut current = link;
+    while current.as_ref().unwrap().left.is_some() {
+        current = &mut current.as_mut().unwrap().left;
+    }
+    let node = current.take().unwrap();
+    *current = node.right;
+    node.value
 }
 
 impl<T> TreeNode<T>
@@ -141,10 +259,684 @@ where
     }
 }
 
+// What the traversals need from a node, so that the plain and the balanced
+// trees share them
+trait BinaryNode {
+    type Value;
+
+    fn value(&self) -> &Self::Value;
+    fn left(&self) -> Option<&Self>;
+    fn right(&self) -> Option<&Self>;
+}
+
+impl<T> BinaryNode for TreeNode<T>
+where
+    T: Ord,
+{
+    type Value = T;
+
+    fn value(&self) -> &T {
+        &self.value
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+}
+
+fn identity<T>(value: &T) -> &T {
+    value
+}
+
+// 中序遍历：左子树、根节点、右子树，得到从小到大的顺序
+struct InOrder<'a, N> {
+    stack: Vec<&'a N>,
+}
+
+impl<'a, N: BinaryNode> InOrder<'a, N> {
+    fn new(root: Option<&'a N>) -> Self {
+        let mut iter = InOrder { stack: Vec::new() };
+        iter.push_left(root);
+        iter
+    }
+
+    fn push_left(&mut self, mut node: Option<&'a N>) {
+        while let Some(current) = node {
+            self.stack.push(current);
+            node = current.left();
+        }
+    }
+}
+
+impl<'a, N: BinaryNode> Iterator for InOrder<'a, N> {
+    type Item = &'a N::Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left(node.right());
+        Some(node.value())
+    }
+}
+
+// 前序遍历：根节点、左子树、右子树
+struct PreOrder<'a, N> {
+    stack: Vec<&'a N>,
+}
+
+impl<'a, N: BinaryNode> PreOrder<'a, N> {
+    fn new(root: Option<&'a N>) -> Self {
+        PreOrder {
+            stack: root.into_iter().collect(),
+        }
+    }
+}
+
+impl<'a, N: BinaryNode> Iterator for PreOrder<'a, N> {
+    type Item = &'a N::Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.stack.extend(node.right());
+        self.stack.extend(node.left());
+        Some(node.value())
+    }
+}
+
+// 后序遍历：左子树、右子树、根节点
+struct PostOrder<'a, N> {
+    // Each node with whether its children are already on the stack
+    stack: Vec<(&'a N, bool)>,
+}
+
+impl<'a, N: BinaryNode> PostOrder<'a, N> {
+    fn new(root: Option<&'a N>) -> Self {
+        PostOrder {
+            stack: root.map(|node| (node, false)).into_iter().collect(),
+        }
+    }
+}
+
+impl<'a, N: BinaryNode> Iterator for PostOrder<'a, N> {
+    type Item = &'a N::Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, expanded) = self.stack.pop()?;
+            if expanded {
+                return Some(node.value());
+            }
+            self.stack.push((node, true));
+            self.stack.extend(node.right().map(|right| (right, false)));
+            self.stack.extend(node.left().map(|left| (left, false)));
+        }
+    }
+}
+
+// An in-order walk that never goes below the start of the range and stops
+// at its end. `key` picks what of a value the range is about.
+struct Range<'a, N: BinaryNode, Q: ?Sized, R> {
+    stack: Vec<&'a N>,
+    range: R,
+    key: fn(&N::Value) -> &Q,
+}
+
+impl<'a, N, Q, R> Range<'a, N, Q, R>
+where
+    N: BinaryNode,
+    Q: Ord + ?Sized,
+    R: RangeBounds<Q>,
+{
+    fn new(root: Option<&'a N>, range: R, key: fn(&N::Value) -> &Q) -> Self {
+        let mut iter = Range {
+            stack: Vec::new(),
+            range,
+            key,
+        };
+        iter.push_left(root);
+        iter
+    }
+
+    fn push_left(&mut self, mut node: Option<&'a N>) {
+        while let Some(current) = node {
+            let key = (self.key)(current.value());
+            let below_start = match self.range.start_bound() {
+                Bound::Included(start) => key < start,
+                Bound::Excluded(start) => key <= start,
+                Bound::Unbounded => false,
+            };
+            // The left subtree of a node below the start is too
+            if below_start {
+                node = current.right();
+            } else {
+                self.stack.push(current);
+                node = current.left();
+            }
+        }
+    }
+}
+
+impl<'a, N, Q, R> Iterator for Range<'a, N, Q, R>
+where
+    N: BinaryNode,
+    Q: Ord + ?Sized,
+    R: RangeBounds<Q>,
+{
+    type Item = &'a N::Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        let key = (self.key)(node.value());
+        let past_end = match self.range.end_bound() {
+            Bound::Included(end) => key > end,
+            Bound::Excluded(end) => key >= end,
+            Bound::Unbounded => false,
+        };
+        if past_end {
+            // Everything still to come is even larger
+            self.stack.clear();
+            return None;
+        }
+        self.push_left(node.right());
+        Some(node.value())
+    }
+}
+
+type AvlLink<T> = Option<Box<AvlNode<T>>>;
+
+// AVL 树的节点，记录以它为根的子树的高度
+#[derive(Debug)]
+struct AvlNode<T>
+where
+    T: Ord,
+{
+    value: T,
+    height: usize,
+    left: AvlLink<T>,
+    right: AvlLink<T>,
+}
+
+impl<T> AvlNode<T>
+where
+    T: Ord,
+{
+    fn new(value: T) -> Self {
+        AvlNode {
+            value,
+            height: 1,
+            left: None,
+            right: None,
+        }
+    }
+
+    fn update_height(&mut self) {
+        self.height = 1 + cmp::max(avl_height(&self.left), avl_height(&self.right));
+    }
+
+    // How much taller the left subtree is than the right one
+    fn balance_factor(&self) -> isize {
+        avl_height(&self.left) as isize - avl_height(&self.right) as isize
+    }
+}
+
+impl<T> BinaryNode for AvlNode<T>
+where
+    T: Ord,
+{
+    type Value = T;
+
+    fn value(&self) -> &T {
+        &self.value
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+}
+
+fn avl_height<T: Ord>(link: &AvlLink<T>) -> usize {
+    link.as_ref().map_or(0, |node| node.height)
+}
+
+// 右旋：左子节点成为新的根
+fn rotate_right<T: Ord>(mut node: Box<AvlNode<T>>) -> Box<AvlNode<T>> {
+    let mut left = node.left.take().expect("rotating right needs a left child");
+    node.left = left.right.take();
+    node.update_height();
+    left.right = Some(node);
+    left.update_height();
+    left
+}
+
+// 左旋：右子节点成为新的根
+fn rotate_left<T: Ord>(mut node: Box<AvlNode<T>>) -> Box<AvlNode<T>> {
+    let mut right = node.right.take().expect("rotating left needs a right child");
+    node.right = right.left.take();
+    node.update_height();
+    right.left = Some(node);
+    right.update_height();
+    right
+}
+
+// Restore the balance of a node whose subtrees differ in height by at most
+// two, which is all a single insertion or removal below it can cause
+fn rebalance<T: Ord>(mut node: Box<AvlNode<T>>) -> Box<AvlNode<T>> {
+    node.update_height();
+    let balance = node.balance_factor();
+    if balance > 1 {
+        if node.left.as_ref().is_some_and(|left| left.balance_factor() < 0) {
+            node.left = node.left.take().map(rotate_left);
+        }
+        return rotate_right(node);
+    }
+    if balance < -1 {
+        if node.right.as_ref().is_some_and(|right| right.balance_factor() > 0) {
+            node.right = node.right.take().map(rotate_right);
+        }
+        return rotate_left(node);
+    }
+    node
+}
+
+// Insert the value, or replace an equal one and put that in `replaced`
+fn avl_insert<T: Ord>(link: AvlLink<T>, value: T, replaced: &mut Option<T>) -> Box<AvlNode<T>> {
+    let Some(mut node) = link else {
+        return Box::new(AvlNode::new(value));
+    };
+    match value.cmp(&node.value) {
+        Ordering::Less => node.left = Some(avl_insert(node.left.take(), value, replaced)),
+        Ordering::Greater => node.right = Some(avl_insert(node.right.take(), value, replaced)),
+        Ordering::Equal => {
+            *replaced = Some(mem::replace(&mut node.value, value));
+            return node;
+        }
+    }
+    rebalance(node)
+}
+
+// Remove the value for which `target` returns `Equal` and put it in
+// `removed`. `target` compares what is looked for with a value of the tree.
+fn avl_remove<T, F>(link: AvlLink<T>, target: &F, removed: &mut Option<T>) -> AvlLink<T>
+where
+    T: Ord,
+    F: Fn(&T) -> Ordering,
+{
+    let mut node = link?;
+    match target(&node.value) {
+        Ordering::Less => node.left = avl_remove(node.left.take(), target, removed),
+        Ordering::Greater => node.right = avl_remove(node.right.take(), target, removed),
+        Ordering::Equal => match (node.left.take(), node.right.take()) {
+            (None, None) => {
+                *removed = Some(node.value);
+                return None;
+            }
+            (Some(child), None) | (None, Some(child)) => {
+                *removed = Some(node.value);
+                return Some(child);
+            }
+            (Some(left), Some(right)) => {
+                let (min, right) = avl_remove_min(right);
+                *removed = Some(mem::replace(&mut node.value, min));
+                node.left = Some(left);
+                node.right = right;
+            }
+        },
+    }
+    Some(rebalance(node))
+}
+
+fn avl_remove_min<T: Ord>(mut node: Box<AvlNode<T>>) -> (T, AvlLink<T>) {
+    match node.left.take() {
+        None => (node.value, node.right),
+        Some(left) => {
+            let (min, left) = avl_remove_min(left);
+            node.left = left;
+            (min, Some(rebalance(node)))
+        }
+    }
+}
+
+// AVL 树：每个节点左右子树的高度最多相差 1，所以有序插入也不会退化成链表，
+// 高度始终是 O(log n)
+#[derive(Debug)]
+struct AvlTree<T>
+where
+    T: Ord,
+{
+    root: AvlLink<T>,
+    len: usize,
+}
+
+impl<T> Default for AvlTree<T>
+where
+    T: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> AvlTree<T>
+where
+    T: Ord,
+{
+    fn new() -> Self {
+        AvlTree { root: None, len: 0 }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    fn height(&self) -> usize {
+        avl_height(&self.root)
+    }
+
+    // Insert the value, returning whether it wasn't in the tree yet
+    fn insert(&mut self, value: T) -> bool {
+        self.replace(value).is_none()
+    }
+
+    // Insert the value, returning the equal value it replaced
+    fn replace(&mut self, value: T) -> Option<T> {
+        let mut replaced = None;
+        self.root = Some(avl_insert(self.root.take(), value, &mut replaced));
+        if replaced.is_none() {
+            self.len += 1;
+        }
+        replaced
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.find_by(|other| value.cmp(other)).is_some()
+    }
+
+    // Remove the value, returning whether it was in the tree
+    fn remove(&mut self, value: &T) -> bool {
+        self.remove_by(|other| value.cmp(other)).is_some()
+    }
+
+    // The value for which `target` returns `Equal`, where `target` compares
+    // what is looked for with a value of the tree
+    fn find_by<F>(&self, target: F) -> Option<&T>
+    where
+        F: Fn(&T) -> Ordering,
+    {
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            match target(&node.value) {
+                Ordering::Less => current = node.left.as_deref(),
+                Ordering::Greater => current = node.right.as_deref(),
+                Ordering::Equal => return Some(&node.value),
+            }
+        }
+        None
+    }
+
+    fn find_by_mut<F>(&mut self, target: F) -> Option<&mut T>
+    where
+        F: Fn(&T) -> Ordering,
+    {
+        let mut current = self.root.as_deref_mut();
+        while let Some(node) = current {
+            match target(&node.value) {
+                Ordering::Less => current = node.left.as_deref_mut(),
+                Ordering::Greater => current = node.right.as_deref_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+            }
+        }
+        None
+    }
+
+    fn remove_by<F>(&mut self, target: F) -> Option<T>
+    where
+        F: Fn(&T) -> Ordering,
+    {
+        let mut removed = None;
+        self.root = avl_remove(self.root.take(), &target, &mut removed);
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    fn min(&self) -> Option<&T> {
+        self.iter().next()
+    }
+
+    fn max(&self) -> Option<&T> {
+        let mut node = self.root.as_deref()?;
+        while let Some(right) = node.right.as_deref() {
+            node = right;
+        }
+        Some(&node.value)
+    }
+
+    fn iter(&self) -> InOrder<'_, AvlNode<T>> {
+        InOrder::new(self.root.as_deref())
+    }
+
+    fn pre_order(&self) -> PreOrder<'_, AvlNode<T>> {
+        PreOrder::new(self.root.as_deref())
+    }
+
+    fn post_order(&self) -> PostOrder<'_, AvlNode<T>> {
+        PostOrder::new(self.root.as_deref())
+    }
+
+    fn range<R>(&self, range: R) -> Range<'_, AvlNode<T>, T, R>
+    where
+        R: RangeBounds<T>,
+    {
+        Range::new(self.root.as_deref(), range, identity)
+    }
+}
+
+impl<T> FromIterator<T> for AvlTree<T>
+where
+    T: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = AvlTree::new();
+        for value in iter {
+            tree.insert(value);
+        }
+        tree
+    }
+}
+
+// A key and its value, ordered by the key alone
+#[derive(Debug)]
+struct MapEntry<K, V> {
+    key: K,
+    value: V,
+}
+
+impl<K: Ord, V> PartialEq for MapEntry<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<K: Ord, V> Eq for MapEntry<K, V> {}
+
+impl<K: Ord, V> PartialOrd for MapEntry<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, V> Ord for MapEntry<K, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+fn borrowed_key<K, V, Q>(entry: &MapEntry<K, V>) -> &Q
+where
+    K: Borrow<Q>,
+    Q: ?Sized,
+{
+    entry.key.borrow()
+}
+
+// 基于 AVL 树的有序映射，接口和 BTreeMap 一样
+struct AvlMap<K, V>
+where
+    K: Ord,
+{
+    tree: AvlTree<MapEntry<K, V>>,
+}
+
+impl<K, V> Default for AvlMap<K, V>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> AvlMap<K, V>
+where
+    K: Ord,
+{
+    fn new() -> Self {
+        AvlMap {
+            tree: AvlTree::new(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    fn height(&self) -> usize {
+        self.tree.height()
+    }
+
+    // Insert the value for the key, returning the value it replaced
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.tree
+            .replace(MapEntry { key, value })
+            .map(|entry| entry.value)
+    }
+
+    fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.tree
+            .find_by(|entry| key.cmp(entry.key.borrow()))
+            .map(|entry| &entry.value)
+    }
+
+    fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.tree
+            .find_by_mut(|entry| key.cmp(entry.key.borrow()))
+            .map(|entry| &mut entry.value)
+    }
+
+    fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.tree
+            .remove_by(|entry| key.cmp(entry.key.borrow()))
+            .map(|entry| entry.value)
+    }
+
+    fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.tree.min().map(|entry| (&entry.key, &entry.value))
+    }
+
+    fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.tree.max().map(|entry| (&entry.key, &entry.value))
+    }
+
+    // The keys and values in ascending order of the keys
+    fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.tree.iter().map(|entry| (&entry.key, &entry.value))
+    }
+
+    fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    // The keys in the range and their values, in ascending order of the keys
+    fn range<Q, R>(&self, range: R) -> impl Iterator<Item = (&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        Range::new(self.tree.root.as_deref(), range, borrowed_key::<K, V, Q>)
+            .map(|entry| (&entry.key, &entry.value))
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for AvlMap<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = AvlMap::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
+
+impl<K, V> Debug for AvlMap<K, V>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::random::random_numbers;
+    use std::collections::{BTreeMap, BTreeSet};
 
     #[test]
     fn test_insert_and_search() {
@@ -191,7 +983,183 @@ mod tests {
             },
             None => panic!("Root should not be None after insertion"),
         }
+        assert_eq!(bst.len(), 1);
     }
-}    
 
+    //         5
+    //       /   \
+    //      3     8
+    //     / \   / \
+    //    1   4 7   9
+    fn sample_tree() -> BinarySearchTree<i32> {
+        let mut bst = BinarySearchTree::new();
+        for value in [5, 3, 8, 1, 4, 7, 9] {
+            bst.insert(value);
+        }
+        bst
+    }
+
+    #[test]
+    fn traversal_orders() {
+        let bst = sample_tree();
+        assert_eq!(bst.iter().copied().collect::<Vec<_>>(), [1, 3, 4, 5, 7, 8, 9]);
+        assert_eq!(bst.pre_order().copied().collect::<Vec<_>>(), [5, 3, 1, 4, 8, 7, 9]);
+        assert_eq!(bst.post_order().copied().collect::<Vec<_>>(), [1, 4, 3, 7, 9, 8, 5]);
+        assert_eq!((bst.min(), bst.max()), (Some(&1), Some(&9)));
+        assert_eq!((bst.len(), bst.height()), (7, 3));
+
+        let empty = BinarySearchTree::<i32>::new();
+        assert_eq!((empty.min(), empty.max(), empty.height()), (None, None, 0));
+        assert_eq!(empty.post_order().next(), None);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn range_queries() {
+        let bst = sample_tree();
+        assert_eq!(bst.range(3..8).copied().collect::<Vec<_>>(), [3, 4, 5, 7]);
+        assert_eq!(bst.range(2..=7).copied().collect::<Vec<_>>(), [3, 4, 5, 7]);
+        assert_eq!(bst.range(..4).copied().collect::<Vec<_>>(), [1, 3]);
+        assert_eq!(bst.range(8..).copied().collect::<Vec<_>>(), [8, 9]);
+        assert_eq!(bst.range(10..).next(), None);
+        assert_eq!(bst.range(..).count(), 7);
+    }
+
+    #[test]
+    fn remove_every_kind_of_node() {
+        let mut bst = sample_tree();
+        // A leaf, a node with one child, and roots with two children
+        assert!(bst.remove(&1));
+        assert!(bst.remove(&3));
+        assert!(bst.remove(&5));
+        assert!(!bst.remove(&5));
+        assert_eq!(bst.iter().copied().collect::<Vec<_>>(), [4, 7, 8, 9]);
+        assert_eq!(bst.pre_order().next(), Some(&7));
+        assert!(bst.remove(&7));
+        assert!(bst.remove(&8));
+        assert!(bst.remove(&4));
+        assert!(bst.remove(&9));
+        assert!(bst.is_empty());
+        assert_eq!(bst.len(), 0);
+    }
+
+    #[test]
+    fn sorted_input_degenerates_the_plain_tree_but_not_the_avl_tree() {
+        let mut bst = BinarySearchTree::new();
+        let mut avl = AvlTree::new();
+        for value in 0..1000 {
+            bst.insert(value);
+            avl.insert(value);
+        }
+        assert_eq!(bst.height(), 1000);
+        assert_eq!(avl.height(), 10);
+        assert!(avl.iter().copied().eq(0..1000));
+    }
+
+    // Check every AVL invariant below the node and return the height
+    fn check_avl<T: Ord + Debug>(link: &AvlLink<T>) -> usize {
+        let Some(node) = link else {
+            return 0;
+        };
+        let left = check_avl(&node.left);
+        let right = check_avl(&node.right);
+        assert_eq!(node.height, 1 + left.max(right), "stale height at {:?}", node.value);
+        assert!(left.abs_diff(right) <= 1, "{:?} is out of balance", node.value);
+        if let Some(child) = &node.left {
+            assert!(child.value < node.value);
+        }
+        if let Some(child) = &node.right {
+            assert!(child.value > node.value);
+        }
+        node.height
+    }
 
+    #[test]
+    fn avl_tree_stays_balanced_after_random_inserts_and_removes() {
+        let mut avl = AvlTree::new();
+        let mut bst = BinarySearchTree::new();
+        let mut oracle = BTreeSet::new();
+        let mut numbers = random_numbers(44);
+        for _ in 0..5000 {
+            let value = (numbers.next().unwrap() % 300) as i32;
+            if numbers.next().unwrap() % 3 == 0 {
+                let present = oracle.remove(&value);
+                assert_eq!(avl.remove(&value), present);
+                assert_eq!(bst.remove(&value), present);
+            } else {
+                assert_eq!(avl.insert(value), oracle.insert(value));
+                bst.insert(value);
+            }
+            check_avl(&avl.root);
+            assert_eq!(avl.len(), oracle.len());
+            assert_eq!(bst.len(), oracle.len());
+        }
+        assert!(avl.iter().eq(oracle.iter()));
+        assert!(bst.iter().eq(oracle.iter()));
+        assert!(avl.range(100..200).eq(oracle.range(100..200)));
+        assert_eq!(avl.min(), oracle.first());
+        assert_eq!(avl.max(), oracle.last());
+        // 1.44 log2(n) bounds the height of any AVL tree
+        let bound = 1.45 * ((avl.len() + 2) as f64).log2();
+        assert!((avl.height() as f64) < bound, "height {}", avl.height());
+    }
+
+    #[test]
+    fn avl_traversals() {
+        let avl: AvlTree<i32> = (1..=7).collect();
+        check_avl(&avl.root);
+        assert_eq!(avl.pre_order().copied().collect::<Vec<_>>(), [4, 2, 1, 3, 6, 5, 7]);
+        assert_eq!(avl.post_order().copied().collect::<Vec<_>>(), [1, 3, 2, 5, 7, 6, 4]);
+        assert!(avl.contains(&5));
+        assert!(!avl.contains(&8));
+    }
+
+    #[test]
+    fn map_works_like_btree_map() {
+        let mut map = AvlMap::new();
+        let mut oracle = BTreeMap::new();
+        let mut numbers = random_numbers(7);
+        for _ in 0..2000 {
+            let key = (numbers.next().unwrap() % 200) as u32;
+            let value = numbers.next().unwrap();
+            match value % 4 {
+                0 => assert_eq!(map.remove(&key), oracle.remove(&key)),
+                1 => {
+                    if let Some(found) = map.get_mut(&key) {
+                        *found += 1;
+                    }
+                    if let Some(found) = oracle.get_mut(&key) {
+                        *found += 1;
+                    }
+                }
+                _ => assert_eq!(map.insert(key, value), oracle.insert(key, value)),
+            }
+            check_avl(&map.tree.root);
+        }
+        assert_eq!(map.len(), oracle.len());
+        assert!(map.iter().eq(oracle.iter()));
+        assert!(map.range(50..=150).eq(oracle.range(50..=150)));
+        assert_eq!(map.first_key_value(), oracle.first_key_value());
+        assert_eq!(map.last_key_value(), oracle.last_key_value());
+        assert!(map.keys().eq(oracle.keys()));
+        assert!(map.values().eq(oracle.values()));
+    }
+
+    #[test]
+    fn map_with_borrowed_keys() {
+        let mut map: AvlMap<String, usize> = ["b", "a", "c"]
+            .iter()
+            .enumerate()
+            .map(|(i, key)| (key.to_string(), i))
+            .collect();
+        assert_eq!(map.get("a"), Some(&1));
+        assert!(map.contains_key("c"));
+        assert_eq!(map.insert("a".to_string(), 10), Some(1));
+        assert_eq!(map.remove("b"), Some(0));
+        assert_eq!(map.get("b"), None);
+        assert_eq!(map.range::<str, _>((Bound::Included("a"), Bound::Excluded("c"))).count(), 1);
+        assert_eq!(format!("{:?}", map), r#"{"a": 10, "c": 2}"#);
+        assert_eq!(map.height(), 2);
+        assert!(!map.is_empty());
+    }
+}
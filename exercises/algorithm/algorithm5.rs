@@ -4,62 +4,38 @@
     广度优先搜索
 */
 
-use std::collections::VecDeque;
+mod graph;
+
+use graph::UndirectedGraph;
 
 // Define a graph
+// 图的表示和广度优先搜索都在 graph 模块里，这里的图是编号从 0 开始的 n 个顶点
 struct Graph {
-    adj: Vec<Vec<usize>>, 
+    graph: UndirectedGraph<usize>,
 }
 
 impl Graph {
     // Create a new graph with n vertices
     fn new(n: usize) -> Self {
-        Graph {
-            adj: vec![vec![]; n],
+        let mut graph = UndirectedGraph::new();
+        for vertex in 0..n {
+            graph.add_node(vertex);
         }
+        Graph { graph }
     }
 
     // Add an edge to the graph
     fn add_edge(&mut self, src: usize, dest: usize) {
-        self.adj[src].push(dest); 
-        self.adj[dest].push(src); 
+        self.graph.add_edge(src, dest, ());
     }
 
     // Perform a breadth-first search on the graph, return the order of visited nodes
     // 在图上执行 bfs 广度优先搜索，返回访问节点的顺序
     fn bfs_with_return(&self, start: usize) -> Vec<usize> {
-        // 首先定义了一个布尔类型的向量visited来记录每个节点是否被访问过。
-        let mut visited = vec![false; self.adj.len()];
-        // 创建一个空的向量visit_order来存储遍历的顺序。
-        let mut visit_order = vec![];
-        // 创建一个队列queue，用于存储待访问的节点。
-        let mut queue = VecDeque::new();
-
-        // Mark the start node as visited and enqueue it
-        // 将起始节点标记为已访问，并将其放入队列中。
-        visited[start] = true;
-        queue.push_back(start);
-
-        // 当队列不为空时，循环执行以下操作：
-        while!queue.is_empty() {
-            // Dequeue a vertex from queue and print it
-            // 从队列中取出一个节点（当前节点）。
-            let current = queue.pop_front().unwrap();
-            // 将当前节点加入visit_order中。
-            visit_order.push(current);
-
-            // Get all adjacent vertices of the dequeued vertex current.
-            // If an adjacent has not been visited, then mark it visited and enqueue it
-            // 遍历当前节点的所有邻接节点，如果邻接节点未被访问过，则将其标记为已访问，并放入队列中。
-            for &neighbor in &self.adj[current] {
-                if!visited[neighbor] {
-                    visited[neighbor] = true;
-                    queue.push_back(neighbor);
-                }
-            }
+        match self.graph.bfs(&start) {
+            Ok(bfs) => bfs.copied().collect(),
+            Err(e) => panic!("can't start a search at {start}: {e}"),
         }
-        // 最终返回visit_order，即遍历的顺序。
-        visit_order
     }
 }
 
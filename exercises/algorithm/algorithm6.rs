@@ -4,48 +4,34 @@
     深度优先搜索
 */
 
-use std::collections::HashSet;
+mod graph;
 
+use graph::UndirectedGraph;
+
+// 图的表示和深度优先搜索都在 graph 模块里，这里的图是编号从 0 开始的 n 个顶点
 struct Graph {
-    adj: Vec<Vec<usize>>, 
+    graph: UndirectedGraph<usize>,
 }
 
 impl Graph {
     fn new(n: usize) -> Self {
-        Graph {
-            adj: vec![vec![]; n],
+        let mut graph = UndirectedGraph::new();
+        for vertex in 0..n {
+            graph.add_node(vertex);
         }
+        Graph { graph }
     }
 
     fn add_edge(&mut self, src: usize, dest: usize) {
-        self.adj[src].push(dest);
-        self.adj[dest].push(src); 
-    }
-
-    fn dfs_util(&self, v: usize, visited: &mut HashSet<usize>, visit_order: &mut Vec<usize>) {
-        // 将当前节点标记为已访问
-        visited.insert(v);
-        // 将当前节点添加到访问顺序中
-        visit_order.push(v);
-
-        // 遍历当前节点的所有邻居节点
-        for &neighbor in &self.adj[v] {
-            // 如果邻居节点未被访问，则递归调用dfs_util
-            if !visited.contains(&neighbor) {
-                self.dfs_util(neighbor, visited, visit_order);
-            }
-        }
+        self.graph.add_edge(src, dest, ());
     }
 
     // Perform a depth-first search on the graph, return the order of visited nodes
     fn dfs(&self, start: usize) -> Vec<usize> {
-        // 创建一个HashSet来跟踪已访问的节点
-        let mut visited = HashSet::new();
-        // 创建一个Vec来存储访问顺序
-        let mut visit_order = Vec::new(); 
-        // 调用dfs_util函数进行深度优先搜索
-        self.dfs_util(start, &mut visited, &mut visit_order);
-        visit_order
+        match self.graph.dfs(&start) {
+            Ok(dfs) => dfs.copied().collect(),
+            Err(e) => panic!("can't start a search at {start}: {e}"),
+        }
     }
 }
 
@@ -2,8 +2,11 @@
 	stack
 	This question requires you to use a stack to achieve a bracket match
 	利用堆栈实现括号匹配
+	在堆栈的基础上，再用调度场算法把中缀表达式转换成后缀表达式（逆波兰表示法）并求值
 */
 
+use std::fmt;
+
 // 定义堆栈
 #[derive(Debug)]
 struct Stack<T> {
@@ -56,7 +59,7 @@ impl<T> Stack<T> {
 	fn into_iter(self) -> IntoIter<T> {
 		IntoIter(self)
 	}
-	fn iter(&self) -> Iter<T> {
+	fn iter(&self) -> Iter<'_, T> {
 		let mut iterator = Iter { 
 			stack: Vec::new() 
 		};
@@ -65,7 +68,7 @@ impl<T> Stack<T> {
 		}
 		iterator
 	}
-	fn iter_mut(&mut self) -> IterMut<T> {
+	fn iter_mut(&mut self) -> IterMut<'_, T> {
 		let mut iterator = IterMut { 
 			stack: Vec::new() 
 		};
@@ -76,15 +79,10 @@ impl<T> Stack<T> {
 	}
 }
 struct IntoIter<T>(Stack<T>);
-impl<T: Clone> Iterator for IntoIter<T> {
+impl<T> Iterator for IntoIter<T> {
 	type Item = T;
 	fn next(&mut self) -> Option<Self::Item> {
-		if !self.0.is_empty() {
-			self.0.size -= 1;self.0.data.pop()
-		} 
-		else {
-			None
-		}
+		self.0.pop()
 	}
 }
 struct Iter<'a, T: 'a> {
@@ -106,32 +104,326 @@ impl<'a, T> Iterator for IterMut<'a, T> {
 	}
 }
 
+// 括号不匹配的错误，position 是字符在字符串中的字节偏移
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BracketError {
+	// 右括号前面没有还没闭合的左括号
+	Unexpected { close: char, position: usize },
+	// 右括号和最近一个还没闭合的左括号不是一对
+	Mismatched { open: char, open_position: usize, close: char, position: usize },
+	// 字符串结束时还有左括号没有闭合，报告最后一个，也就是最先需要闭合的那个
+	Unclosed { open: char, position: usize },
+}
+
+impl fmt::Display for BracketError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			BracketError::Unexpected { close, position } => {
+				write!(f, "unexpected '{close}' at {position}")
+			}
+			BracketError::Mismatched { open, open_position, close, position } => {
+				write!(f, "'{close}' at {position} doesn't close '{open}' at {open_position}")
+			}
+			BracketError::Unclosed { open, position } => {
+				write!(f, "'{open}' at {position} is never closed")
+			}
+		}
+	}
+}
+
+impl std::error::Error for BracketError {}
+
+// 右括号对应的左括号
+fn opening(close: char) -> Option<char> {
+	match close {
+		')' => Some('('),
+		']' => Some('['),
+		'}' => Some('{'),
+		_ => None,
+	}
+}
+
+// 检查括号是否匹配，不匹配时返回第一个出错的位置和原因
+fn bracket_check(bracket: &str) -> Result<(), BracketError> {
+	// 堆栈中保存还没闭合的左括号和它们的位置
+	let mut stack = Stack::new();
+	for (position, c) in bracket.char_indices() {
+		if c == '(' || c == '[' || c == '{' {
+			stack.push((c, position));
+		} else if let Some(expected) = opening(c) {
+			match stack.pop() {
+				None => return Err(BracketError::Unexpected { close: c, position }),
+				Some((open, open_position)) if open != expected => {
+					return Err(BracketError::Mismatched { open, open_position, close: c, position });
+				}
+				Some(_) => {}
+			}
+		}
+	}
+	match stack.pop() {
+		Some((open, position)) => Err(BracketError::Unclosed { open, position }),
+		None => Ok(()),
+	}
+}
+
 fn bracket_match(bracket: &str) -> bool
 {
-	// 创建一个堆栈
+	bracket_check(bracket).is_ok()
+}
+
+// 运算符，Neg 是一元负号
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+	Add,
+	Sub,
+	Mul,
+	Div,
+	Rem,
+	Pow,
+	Neg,
+}
+
+impl Operator {
+	// 优先级越高越先计算。负号比乘方低，所以 -2^2 是 -(2^2)
+	fn precedence(self) -> u8 {
+		match self {
+			Operator::Add | Operator::Sub => 1,
+			Operator::Mul | Operator::Div | Operator::Rem => 2,
+			Operator::Neg => 3,
+			Operator::Pow => 4,
+		}
+	}
+
+	// 乘方和负号是右结合的：2^3^2 是 2^(3^2)
+	fn is_left_associative(self) -> bool {
+		!matches!(self, Operator::Pow | Operator::Neg)
+	}
+
+	fn symbol(self) -> &'static str {
+		match self {
+			Operator::Add => "+",
+			Operator::Sub => "-",
+			Operator::Mul => "*",
+			Operator::Div => "/",
+			Operator::Rem => "%",
+			Operator::Pow => "^",
+			Operator::Neg => "neg",
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+	Number(i64),
+	Op(Operator),
+	LeftParen,
+	RightParen,
+}
+
+impl fmt::Display for Token {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Token::Number(n) => write!(f, "{n}"),
+			Token::Op(op) => write!(f, "{}", op.symbol()),
+			Token::LeftParen => write!(f, "("),
+			Token::RightParen => write!(f, ")"),
+		}
+	}
+}
+
+// 表达式的错误。对字符串报告的 position 是字节偏移，eval_rpn 报告的是记号的下标
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ExprError {
+	InvalidCharacter { c: char, position: usize },
+	NumberTooLarge { position: usize },
+	// 在这里不能出现的记号，比如两个数连在一起，或者缺了操作数的运算符
+	UnexpectedToken { token: Token, position: usize },
+	// 表达式在需要操作数的地方结束了
+	UnexpectedEnd,
+	Bracket(BracketError),
+	// 后缀表达式中运算符没有足够的操作数
+	MissingOperand,
+	// 后缀表达式算完后堆栈中剩下不止一个数
+	TooManyOperands(usize),
+	DivisionByZero,
+	NegativeExponent,
+	Overflow,
+}
+
+impl fmt::Display for ExprError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			ExprError::InvalidCharacter { c, position } => {
+				write!(f, "invalid character '{c}' at {position}")
+			}
+			ExprError::NumberTooLarge { position } => write!(f, "the number at {position} is too large"),
+			ExprError::UnexpectedToken { token, position } => write!(f, "unexpected '{token}' at {position}"),
+			ExprError::UnexpectedEnd => write!(f, "the expression ends too early"),
+			ExprError::Bracket(error) => error.fmt(f),
+			ExprError::MissingOperand => write!(f, "an operator is missing an operand"),
+			ExprError::TooManyOperands(count) => write!(f, "{count} values are left without an operator"),
+			ExprError::DivisionByZero => write!(f, "division by zero"),
+			ExprError::NegativeExponent => write!(f, "negative exponent"),
+			ExprError::Overflow => write!(f, "arithmetic overflow"),
+		}
+	}
+}
+
+impl std::error::Error for ExprError {}
+
+impl From<BracketError> for ExprError {
+	fn from(error: BracketError) -> Self {
+		ExprError::Bracket(error)
+	}
+}
+
+// 把字符串切分成记号和它们的位置。'-' 出现在表达式开头、运算符或左括号后面时是负号
+fn tokenize(expr: &str) -> Result<Vec<(usize, Token)>, ExprError> {
+	let mut tokens: Vec<(usize, Token)> = Vec::new();
+	let mut chars = expr.char_indices().peekable();
+	while let Some((position, c)) = chars.next() {
+		let token = match c {
+			c if c.is_whitespace() => continue,
+			'0'..='9' => {
+				let mut end = position + 1;
+				while let Some(&(next, '0'..='9')) = chars.peek() {
+					end = next + 1;
+					chars.next();
+				}
+				let number = expr[position..end]
+					.parse()
+					.map_err(|_| ExprError::NumberTooLarge { position })?;
+				Token::Number(number)
+			}
+			'+' => Token::Op(Operator::Add),
+			'-' => match tokens.last() {
+				None | Some((_, Token::Op(_) | Token::LeftParen)) => Token::Op(Operator::Neg),
+				Some(_) => Token::Op(Operator::Sub),
+			},
+			'*' => Token::Op(Operator::Mul),
+			'/' => Token::Op(Operator::Div),
+			'%' => Token::Op(Operator::Rem),
+			'^' => Token::Op(Operator::Pow),
+			'(' => Token::LeftParen,
+			')' => Token::RightParen,
+			_ => return Err(ExprError::InvalidCharacter { c, position }),
+		};
+		tokens.push((position, token));
+	}
+	Ok(tokens)
+}
+
+// 调度场算法：把中缀表达式转换成后缀表达式
+fn to_postfix(expr: &str) -> Result<Vec<Token>, ExprError> {
+	let mut output = Vec::new();
+	// 还没输出的运算符和左括号
+	let mut operators: Stack<(usize, Token)> = Stack::new();
+	// 下一个记号应该是操作数（数、左括号或负号）还是运算符（二元运算符或右括号）
+	let mut expect_operand = true;
+	for (position, token) in tokenize(expr)? {
+		let unexpected = ExprError::UnexpectedToken { token, position };
+		match token {
+			Token::Number(_) => {
+				if !expect_operand {
+					return Err(unexpected);
+				}
+				output.push(token);
+				expect_operand = false;
+			}
+			Token::LeftParen | Token::Op(Operator::Neg) => {
+				if !expect_operand {
+					return Err(unexpected);
+				}
+				// 前缀的运算符还没有操作数，不能让前面的运算符先输出
+				operators.push((position, token));
+			}
+			Token::Op(op) => {
+				if expect_operand {
+					return Err(unexpected);
+				}
+				// 先输出栈顶优先级更高的运算符，优先级相同时左结合的也先输出
+				while let Some(&(_, Token::Op(top))) = operators.peek() {
+					let first = top.precedence() > op.precedence()
+						|| (top.precedence() == op.precedence() && op.is_left_associative());
+					if !first {
+						break;
+					}
+					output.push(Token::Op(top));
+					operators.pop();
+				}
+				operators.push((position, token));
+				expect_operand = true;
+			}
+			Token::RightParen => {
+				if expect_operand {
+					return Err(unexpected);
+				}
+				// 输出运算符直到遇到对应的左括号
+				loop {
+					match operators.pop() {
+						Some((_, Token::LeftParen)) => break,
+						Some((_, token)) => output.push(token),
+						None => return Err(BracketError::Unexpected { close: ')', position }.into()),
+					}
+				}
+			}
+		}
+	}
+	if expect_operand {
+		return Err(ExprError::UnexpectedEnd);
+	}
+	while let Some((position, token)) = operators.pop() {
+		if token == Token::LeftParen {
+			return Err(BracketError::Unclosed { open: '(', position }.into());
+		}
+		output.push(token);
+	}
+	Ok(output)
+}
+
+// 计算后缀表达式：数入栈，运算符从栈中取出操作数，再把结果入栈
+fn eval_rpn(tokens: &[Token]) -> Result<i64, ExprError> {
 	let mut stack = Stack::new();
-	// 遍历括号字符串
-	for c in bracket.chars() {
-		// 如果当前字符是左括号，将其压入堆栈
-		if c == '(' || c == '[' || c == '{' {
-			stack.push(c);
-		} else if c == ')' || c == ']' || c == '}' {
-			// 如果当前字符是右括号，检查堆栈是否为空
-			if stack.is_empty() {
-				return false;
+	for (position, &token) in tokens.iter().enumerate() {
+		let op = match token {
+			Token::Number(n) => {
+				stack.push(n);
+				continue;
 			}
-			// 如果堆栈不为空，弹出堆栈顶部的元素
-			let top = stack.pop().unwrap();
-			// 如果弹出的元素与当前字符不匹配，返回 false
-			if (c == ')' && top != '(') ||
-			   (c == ']' && top != '[') || 
-			   (c == '}' && top != '{') {
-				return false;
+			Token::Op(op) => op,
+			Token::LeftParen | Token::RightParen => {
+				return Err(ExprError::UnexpectedToken { token, position });
 			}
+		};
+		let b = stack.pop().ok_or(ExprError::MissingOperand)?;
+		if op == Operator::Neg {
+			stack.push(b.checked_neg().ok_or(ExprError::Overflow)?);
+			continue;
 		}
+		let a = stack.pop().ok_or(ExprError::MissingOperand)?;
+		let result = match op {
+			Operator::Add => a.checked_add(b),
+			Operator::Sub => a.checked_sub(b),
+			Operator::Mul => a.checked_mul(b),
+			Operator::Div | Operator::Rem if b == 0 => return Err(ExprError::DivisionByZero),
+			Operator::Div => a.checked_div(b),
+			Operator::Rem => a.checked_rem(b),
+			Operator::Pow if b < 0 => return Err(ExprError::NegativeExponent),
+			Operator::Pow => u32::try_from(b).ok().and_then(|b| a.checked_pow(b)),
+			Operator::Neg => unreachable!(),
+		};
+		stack.push(result.ok_or(ExprError::Overflow)?);
 	}
-	// 如果堆栈为空，返回 true，否则返回 false
-	stack.is_empty()
+	match stack.len() {
+		0 => Err(ExprError::MissingOperand),
+		1 => Ok(stack.pop().unwrap()),
+		count => Err(ExprError::TooManyOperands(count)),
+	}
+}
+
+// 计算中缀表达式
+fn evaluate(expr: &str) -> Result<i64, ExprError> {
+	eval_rpn(&to_postfix(expr)?)
 }
 
 #[cfg(test)]
@@ -168,4 +460,138 @@ mod tests {
 		let s = "";
 		assert_eq!(bracket_match(s),true);
 	}
+	#[test]
+	fn bracket_errors_point_at_the_mismatch(){
+		assert_eq!(bracket_check("(2+3){func}[abc]"), Ok(()));
+		assert_eq!(
+			bracket_check("(2+3)*(3-1"),
+			Err(BracketError::Unclosed { open: '(', position: 6 })
+		);
+		assert_eq!(
+			bracket_check("{{(}[)]}"),
+			Err(BracketError::Mismatched { open: '(', open_position: 2, close: '}', position: 3 })
+		);
+		assert_eq!(
+			bracket_check("[[[]]]]]]]]]"),
+			Err(BracketError::Unexpected { close: ']', position: 6 })
+		);
+		// 位置是字节偏移
+		assert_eq!(
+			bracket_check("(函数]"),
+			Err(BracketError::Mismatched { open: '(', open_position: 0, close: ']', position: 7 })
+		);
+		// 最先需要闭合的是最后一个左括号
+		assert_eq!(bracket_check("([{"), Err(BracketError::Unclosed { open: '{', position: 2 }));
+		assert_eq!(
+			BracketError::Mismatched { open: '(', open_position: 2, close: '}', position: 3 }.to_string(),
+			"'}' at 3 doesn't close '(' at 2"
+		);
+	}
+	#[test]
+	fn stack_iterators(){
+		let mut stack = Stack::new();
+		for i in 1..=3 {
+			stack.push(i);
+		}
+		assert_eq!(stack.iter().collect::<Vec<_>>(), [&3, &2, &1]);
+		for item in stack.iter_mut() {
+			*item *= 10;
+		}
+		*stack.peek_mut().unwrap() += 1;
+		assert_eq!(stack.peek(), Some(&31));
+		// 元素不需要实现 Clone
+		struct NotClone(i32);
+		let mut owned = Stack::new();
+		owned.push(NotClone(1));
+		owned.push(NotClone(2));
+		assert_eq!(owned.into_iter().map(|n| n.0).collect::<Vec<_>>(), [2, 1]);
+		assert_eq!(stack.into_iter().collect::<Vec<_>>(), [31, 20, 10]);
+		let mut stack = Stack::new();
+		stack.push('a');
+		stack.clear();
+		assert!(stack.is_empty());
+		assert_eq!(stack.pop(), None);
+	}
+	fn postfix(expr: &str) -> String {
+		let tokens = to_postfix(expr).unwrap();
+		tokens.iter().map(|token| token.to_string()).collect::<Vec<_>>().join(" ")
+	}
+	#[test]
+	fn shunting_yard_respects_precedence_and_associativity(){
+		assert_eq!(postfix("3 + 4 * 2"), "3 4 2 * +");
+		assert_eq!(postfix("(3 + 4) * 2"), "3 4 + 2 *");
+		assert_eq!(postfix("10 - 4 - 3"), "10 4 - 3 -");
+		assert_eq!(postfix("2 ^ 3 ^ 2"), "2 3 2 ^ ^");
+		assert_eq!(postfix("-2 ^ 2"), "2 2 ^ neg");
+		assert_eq!(postfix("3 - -2"), "3 2 neg -");
+		assert_eq!(postfix("3 + 4 * 2 / (1 - 5) ^ 2 ^ 3"), "3 4 2 * 1 5 - 2 3 ^ ^ / +");
+	}
+	#[test]
+	fn evaluates_infix_expressions(){
+		assert_eq!(evaluate("3 + 4 * 2"), Ok(11));
+		assert_eq!(evaluate("(3 + 4) * 2"), Ok(14));
+		assert_eq!(evaluate("10 - 4 - 3"), Ok(3));
+		assert_eq!(evaluate("2 ^ 3 ^ 2"), Ok(512));
+		assert_eq!(evaluate("-2 ^ 2"), Ok(-4));
+		assert_eq!(evaluate("(-2) ^ 2"), Ok(4));
+		assert_eq!(evaluate("--3"), Ok(3));
+		assert_eq!(evaluate("17 % 5 * -(2 - 10) / 4"), Ok(4));
+		assert_eq!(evaluate("((((7))))"), Ok(7));
+	}
+	#[test]
+	fn reports_malformed_expressions(){
+		assert_eq!(evaluate("1 + a"), Err(ExprError::InvalidCharacter { c: 'a', position: 4 }));
+		assert_eq!(evaluate("99999999999999999999"), Err(ExprError::NumberTooLarge { position: 0 }));
+		assert_eq!(
+			evaluate("1 2"),
+			Err(ExprError::UnexpectedToken { token: Token::Number(2), position: 2 })
+		);
+		assert_eq!(
+			evaluate("1 + * 2"),
+			Err(ExprError::UnexpectedToken { token: Token::Op(Operator::Mul), position: 4 })
+		);
+		assert_eq!(
+			evaluate("()"),
+			Err(ExprError::UnexpectedToken { token: Token::RightParen, position: 1 })
+		);
+		assert_eq!(
+			evaluate("2(3)"),
+			Err(ExprError::UnexpectedToken { token: Token::LeftParen, position: 1 })
+		);
+		assert_eq!(evaluate(""), Err(ExprError::UnexpectedEnd));
+		assert_eq!(evaluate("1 +"), Err(ExprError::UnexpectedEnd));
+		assert_eq!(
+			evaluate("(1 + (2)"),
+			Err(ExprError::Bracket(BracketError::Unclosed { open: '(', position: 0 }))
+		);
+		assert_eq!(
+			evaluate("1 + 2)"),
+			Err(ExprError::Bracket(BracketError::Unexpected { close: ')', position: 5 }))
+		);
+		assert_eq!(evaluate("1 + a").unwrap_err().to_string(), "invalid character 'a' at 4");
+	}
+	#[test]
+	fn reports_arithmetic_errors(){
+		assert_eq!(evaluate("1 / (2 - 2)"), Err(ExprError::DivisionByZero));
+		assert_eq!(evaluate("1 % 0"), Err(ExprError::DivisionByZero));
+		assert_eq!(evaluate("2 ^ -1"), Err(ExprError::NegativeExponent));
+		assert_eq!(evaluate("2 ^ 63"), Err(ExprError::Overflow));
+		// 负号比乘方后算，所以 2^63 先溢出了
+		assert_eq!(evaluate("-2 ^ 63"), Err(ExprError::Overflow));
+		assert_eq!(evaluate("-(2 ^ 62) * 2"), Ok(i64::MIN));
+		assert_eq!(evaluate("-(2 ^ 62) * 2 / -1"), Err(ExprError::Overflow));
+	}
+	#[test]
+	fn eval_rpn_checks_its_input(){
+		use Operator::*;
+		let n = Token::Number;
+		assert_eq!(eval_rpn(&[n(5), n(1), n(2), Token::Op(Add), n(4), Token::Op(Mul), Token::Op(Add), n(3), Token::Op(Sub)]), Ok(14));
+		assert_eq!(eval_rpn(&[]), Err(ExprError::MissingOperand));
+		assert_eq!(eval_rpn(&[n(1), Token::Op(Add)]), Err(ExprError::MissingOperand));
+		assert_eq!(eval_rpn(&[n(1), n(2), n(3), Token::Op(Neg)]), Err(ExprError::TooManyOperands(3)));
+		assert_eq!(
+			eval_rpn(&[n(1), Token::LeftParen]),
+			Err(ExprError::UnexpectedToken { token: Token::LeftParen, position: 1 })
+		);
+	}
 }
\ No newline at end of file
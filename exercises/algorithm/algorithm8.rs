@@ -4,56 +4,477 @@
     队列实现堆栈
 */
 
-#[derive(Debug)]
+use std::fmt;
+use std::iter::FusedIterator;
+
+// 随机测试用的数在 random 模块里，其他算法题也用它
+#[cfg(test)]
+mod random;
+
+// 队列的错误
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueError {
+    Empty,
+    Full,
+}
+
+impl fmt::Display for QueueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QueueError::Empty => write!(f, "the queue is empty"),
+            QueueError::Full => write!(f, "the queue is full"),
+        }
+    }
+}
+
+impl std::error::Error for QueueError {}
+
+// 有界队列满了，没能入队的元素原样还给调用者
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Full<T>(pub T);
+
+impl<T> fmt::Display for Full<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        QueueError::Full.fmt(f)
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for Full<T> {}
+
+impl<T> From<Full<T>> for QueueError {
+    fn from(_: Full<T>) -> Self {
+        QueueError::Full
+    }
+}
+
+// 环形缓冲区实现的队列：队首在 head，元素依次往后排，到了缓冲区末尾再从开头接着排。
+// 缓冲区满了就扩大一倍，所以入队和出队都是均摊 O(1)。
+#[derive(Clone)]
 pub struct Queue<T> {
-    elements: Vec<T>,
+    buffer: Vec<Option<T>>,
+    head: usize,
+    len: usize,
 }
 
 impl<T> Queue<T> {
     pub fn new() -> Queue<T> {
+        Queue::with_capacity(0)
+    }
+
+    // 放得下 capacity 个元素之前都不需要扩容
+    pub fn with_capacity(capacity: usize) -> Queue<T> {
         Queue {
-            elements: Vec::new(),
+            buffer: (0..capacity).map(|_| None).collect(),
+            head: 0,
+            len: 0,
         }
     }
 
+    // 第 i 个元素在缓冲区中的下标
+    fn slot(&self, i: usize) -> usize {
+        (self.head + i) % self.buffer.len()
+    }
+
+    // 扩容时按顺序搬到新缓冲区的开头
+    fn grow(&mut self) {
+        let capacity = (self.buffer.len() * 2).max(4);
+        let mut buffer: Vec<Option<T>> = (0..capacity).map(|_| None).collect();
+        for (i, slot) in buffer.iter_mut().take(self.len).enumerate() {
+            let from = self.slot(i);
+            *slot = self.buffer[from].take();
+        }
+        self.buffer = buffer;
+        self.head = 0;
+    }
+
     // 入队
     pub fn enqueue(&mut self, value: T) {
-        self.elements.push(value)
+        if self.len == self.buffer.len() {
+            self.grow();
+        }
+        let tail = self.slot(self.len);
+        self.buffer[tail] = Some(value);
+        self.len += 1;
     }
 
     // 出队
-    pub fn dequeue(&mut self) -> Result<T, &str> {
-        if !self.elements.is_empty() {
-            Ok(self.elements.remove(0usize))
-        } else {
-            Err("Queue is empty")
+    pub fn dequeue(&mut self) -> Result<T, QueueError> {
+        if self.len == 0 {
+            return Err(QueueError::Empty);
         }
+        let value = self.buffer[self.head].take().unwrap();
+        self.head = self.slot(1);
+        self.len -= 1;
+        Ok(value)
     }
 
     // 查看队列的第一个元素
-    pub fn peek(&self) -> Result<&T, &str> {
-        match self.elements.first() {
-            Some(value) => Ok(value),
-            None => Err("Queue is empty"),
+    pub fn peek(&self) -> Result<&T, QueueError> {
+        self.get(0).ok_or(QueueError::Empty)
+    }
+
+    // 查看最后入队的元素
+    pub fn peek_back(&self) -> Result<&T, QueueError> {
+        self.len
+            .checked_sub(1)
+            .and_then(|i| self.get(i))
+            .ok_or(QueueError::Empty)
+    }
+
+    // 从队首数起的第 i 个元素
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i >= self.len {
+            return None;
         }
+        self.buffer[self.slot(i)].as_ref()
     }
 
     // 查看队列的大小
     pub fn size(&self) -> usize {
-        self.elements.len()
+        self.len
     }
 
     // 查看队列是否为空
     pub fn is_empty(&self) -> bool {
-        self.elements.is_empty()
+        self.len == 0
+    }
+
+    // 不扩容能放下的元素个数
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn clear(&mut self) {
+        while self.dequeue().is_ok() {}
+    }
+
+    // 从队首到队尾遍历
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            queue: self,
+            front: 0,
+            back: self.len,
+        }
     }
 }
 
 impl<T> Default for Queue<T> {
     fn default() -> Queue<T> {
-        Queue {
-            elements: Vec::new(),
+        Queue::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Queue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for Queue<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for Queue<T> {}
+
+pub struct Iter<'a, T> {
+    queue: &'a Queue<T>,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.front == self.back {
+            return None;
         }
+        self.front += 1;
+        self.queue.get(self.front - 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.back - self.front, Some(self.back - self.front))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        self.queue.get(self.back)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+// 按出队的顺序拿走所有元素
+pub struct IntoIter<T>(Queue<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.dequeue().ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.size(), Some(self.0.size()))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for Queue<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Queue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T> Extend<T> for Queue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.enqueue(value);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for Queue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = Queue::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+// 有界队列：容量固定，不会扩容。满了以后入队会失败，让生产者知道要等消费者跟上
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundedQueue<T> {
+    queue: Queue<T>,
+}
+
+impl<T> BoundedQueue<T> {
+    pub fn new(capacity: usize) -> Self {
+        BoundedQueue {
+            queue: Queue::with_capacity(capacity),
+        }
+    }
+
+    // 入队，队列满了就把元素还回去
+    pub fn enqueue(&mut self, value: T) -> Result<(), Full<T>> {
+        if self.is_full() {
+            return Err(Full(value));
+        }
+        self.queue.enqueue(value);
+        Ok(())
+    }
+
+    // 入队，队列满了就挤掉队首的元素并返回它
+    pub fn force_enqueue(&mut self, value: T) -> Option<T> {
+        if self.capacity() == 0 {
+            return Some(value);
+        }
+        let evicted = if self.is_full() {
+            self.queue.dequeue().ok()
+        } else {
+            None
+        };
+        self.queue.enqueue(value);
+        evicted
+    }
+
+    pub fn dequeue(&mut self) -> Result<T, QueueError> {
+        self.queue.dequeue()
+    }
+
+    pub fn peek(&self) -> Result<&T, QueueError> {
+        self.queue.peek()
+    }
+
+    pub fn size(&self) -> usize {
+        self.queue.size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.size() == self.capacity()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.queue.capacity()
+    }
+
+    // 还能入队的元素个数
+    pub fn remaining(&self) -> usize {
+        self.capacity() - self.size()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.queue.iter()
+    }
+}
+
+impl<T> IntoIterator for BoundedQueue<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        self.queue.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a BoundedQueue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+// 两个栈实现的队列：入队压入 inbox，出队从 outbox 弹出，outbox 空了再把 inbox 整个倒过去。
+// 每个元素最多被搬一次，所以出队是均摊 O(1)。
+#[derive(Debug, Clone)]
+pub struct TwoStackQueue<T> {
+    inbox: Vec<T>,
+    // 栈顶是队首
+    outbox: Vec<T>,
+}
+
+impl<T> TwoStackQueue<T> {
+    pub fn new() -> Self {
+        TwoStackQueue {
+            inbox: Vec::new(),
+            outbox: Vec::new(),
+        }
+    }
+
+    pub fn enqueue(&mut self, value: T) {
+        self.inbox.push(value);
+    }
+
+    pub fn dequeue(&mut self) -> Result<T, QueueError> {
+        if self.outbox.is_empty() {
+            self.outbox.extend(self.inbox.drain(..).rev());
+        }
+        self.outbox.pop().ok_or(QueueError::Empty)
+    }
+
+    // outbox 空的时候队首是 inbox 的栈底，不用搬就能看到
+    pub fn peek(&self) -> Result<&T, QueueError> {
+        self.outbox
+            .last()
+            .or_else(|| self.inbox.first())
+            .ok_or(QueueError::Empty)
+    }
+
+    pub fn size(&self) -> usize {
+        self.inbox.len() + self.outbox.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inbox.is_empty() && self.outbox.is_empty()
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        // Chain isn't ExactSizeIterator, so index into the two stacks instead
+        let (outbox, inbox) = (&self.outbox, &self.inbox);
+        (0..self.size()).map(move |i| match outbox.len().checked_sub(i + 1) {
+            Some(j) => &outbox[j],
+            None => &inbox[i - outbox.len()],
+        })
+    }
+}
+
+impl<T> Default for TwoStackQueue<T> {
+    fn default() -> Self {
+        TwoStackQueue::new()
+    }
+}
+
+impl<T: PartialEq> PartialEq for TwoStackQueue<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for TwoStackQueue<T> {}
+
+impl<T> Extend<T> for TwoStackQueue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.inbox.extend(iter);
+    }
+}
+
+impl<T> FromIterator<T> for TwoStackQueue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        TwoStackQueue {
+            inbox: iter.into_iter().collect(),
+            outbox: Vec::new(),
+        }
+    }
+}
+
+impl<T> IntoIterator for TwoStackQueue<T> {
+    type Item = T;
+    type IntoIter = std::iter::Chain<std::iter::Rev<std::vec::IntoIter<T>>, std::vec::IntoIter<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.outbox.into_iter().rev().chain(self.inbox)
+    }
+}
+
+// 栈：后进先出
+pub trait Stack<T> {
+    fn push(&mut self, elem: T);
+    fn pop(&mut self) -> Result<T, QueueError>;
+    // 查看栈顶的元素
+    fn peek(&self) -> Result<&T, QueueError>;
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Stack<T> for Vec<T> {
+    fn push(&mut self, elem: T) {
+        Vec::push(self, elem);
+    }
+
+    fn pop(&mut self) -> Result<T, QueueError> {
+        Vec::pop(self).ok_or(QueueError::Empty)
+    }
+
+    fn peek(&self) -> Result<&T, QueueError> {
+        self.last().ok_or(QueueError::Empty)
+    }
+
+    fn len(&self) -> usize {
+        Vec::len(self)
     }
 }
 
@@ -71,14 +492,24 @@ impl<T> myStack<T> {
 			q2:Queue::<T>::new()
         }
     }
-    pub fn push(&mut self, elem: T) {
+}
+
+impl<T> Default for myStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// 用两个队列实现的栈，入栈 O(1)，出栈 O(n)
+impl<T> Stack<T> for myStack<T> {
+    fn push(&mut self, elem: T) {
         // 将元素入队到 q1
 		self.q1.enqueue(elem);
     }
-    pub fn pop(&mut self) -> Result<T, &str> {
+    fn pop(&mut self) -> Result<T, QueueError> {
 		// 如果 q1 为空，返回错误
 		if self.q1.is_empty() {
-			Err("Stack is empty")
+			Err(QueueError::Empty)
 		} else {
 			// 将 q1 中的元素出队到 q2，直到 q1 中只剩下一个元素
 			while self.q1.size() > 1 {
@@ -91,7 +522,14 @@ impl<T> myStack<T> {
             Ok(last_element)
 		}
     }
-    pub fn is_empty(&self) -> bool {
+    fn peek(&self) -> Result<&T, QueueError> {
+		// 所有元素都在 q1 中，最后入队的就是栈顶
+		self.q1.peek_back()
+    }
+    fn len(&self) -> usize {
+		self.q1.size() + self.q2.size()
+    }
+    fn is_empty(&self) -> bool {
 		// 如果 q1 和 q2 都为空，返回 true
 		self.q1.is_empty() && self.q2.is_empty()
     }
@@ -100,11 +538,12 @@ impl<T> myStack<T> {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use super::random::random_numbers;
 	
 	#[test]
 	fn test_queue(){
 		let mut s = myStack::<i32>::new();
-		assert_eq!(s.pop(), Err("Stack is empty"));
+		assert_eq!(s.pop(), Err(QueueError::Empty));
         s.push(1);
         s.push(2);
         s.push(3);
@@ -116,7 +555,203 @@ mod tests {
         assert_eq!(s.pop(), Ok(5));
         assert_eq!(s.pop(), Ok(4));
         assert_eq!(s.pop(), Ok(1));
-        assert_eq!(s.pop(), Err("Stack is empty"));
+        assert_eq!(s.pop(), Err(QueueError::Empty));
         assert_eq!(s.is_empty(), true);
 	}
+
+    #[test]
+    fn queues_agree_with_vec_deque() {
+        for seed in 0..10 {
+            let mut oracle = std::collections::VecDeque::new();
+            let mut ring = Queue::new();
+            let mut two_stacks = TwoStackQueue::new();
+            for (i, n) in random_numbers(seed).take(500).enumerate() {
+                // Enqueue more often than dequeue, so the ring has to grow
+                // while its elements wrap around
+                if n % 5 < 3 {
+                    oracle.push_back(i);
+                    ring.enqueue(i);
+                    two_stacks.enqueue(i);
+                } else {
+                    let expected = oracle.pop_front().ok_or(QueueError::Empty);
+                    assert_eq!(ring.dequeue(), expected);
+                    assert_eq!(two_stacks.dequeue(), expected);
+                }
+                let front = oracle.front().ok_or(QueueError::Empty);
+                assert_eq!(ring.peek(), front);
+                assert_eq!(two_stacks.peek(), front);
+                assert_eq!(ring.peek_back(), oracle.back().ok_or(QueueError::Empty));
+                assert_eq!(ring.size(), oracle.len());
+                assert_eq!(two_stacks.size(), oracle.len());
+                assert!(ring.iter().eq(oracle.iter()));
+                assert!(two_stacks.iter().eq(oracle.iter()));
+                assert!(ring.iter().rev().eq(oracle.iter().rev()));
+                assert!(two_stacks.iter().rev().eq(oracle.iter().rev()));
+            }
+        }
+    }
+
+    #[test]
+    fn ring_buffer_grows_only_when_full() {
+        let mut queue = Queue::with_capacity(4);
+        for round in 0..100 {
+            queue.enqueue(round);
+            queue.enqueue(round);
+            queue.dequeue().unwrap();
+            queue.dequeue().unwrap();
+        }
+        assert_eq!(queue.capacity(), 4);
+        queue.extend(0..5);
+        assert_eq!(queue.capacity(), 8);
+        assert_eq!(queue.iter().len(), 5);
+        assert_eq!(format!("{queue:?}"), "[0, 1, 2, 3, 4]");
+        assert_eq!(queue.get(4), Some(&4));
+        assert_eq!(queue.get(5), None);
+        queue.clear();
+        assert!(queue.is_empty());
+        assert_eq!(queue.capacity(), 8);
+        assert_eq!(queue.dequeue(), Err(QueueError::Empty));
+    }
+
+    #[test]
+    fn bounded_queue_pushes_back() {
+        let mut queue = BoundedQueue::new(3);
+        for i in 0..3 {
+            assert_eq!(queue.enqueue(i), Ok(()));
+        }
+        assert!(queue.is_full());
+        assert_eq!(queue.remaining(), 0);
+        // The rejected value comes back to the producer
+        assert_eq!(queue.enqueue(9), Err(Full(9)));
+        assert_eq!(queue.dequeue(), Ok(0));
+        assert_eq!(queue.remaining(), 1);
+        assert_eq!(queue.enqueue(3), Ok(()));
+        // Overwriting evicts the oldest element
+        assert_eq!(queue.force_enqueue(4), Some(1));
+        assert_eq!(queue.iter().copied().collect::<Vec<_>>(), [2, 3, 4]);
+        assert_eq!(queue.capacity(), 3);
+        assert_eq!(queue.peek(), Ok(&2));
+
+        fn produce(queue: &mut BoundedQueue<u8>, values: &[u8]) -> Result<(), QueueError> {
+            for &value in values {
+                queue.enqueue(value)?;
+            }
+            Ok(())
+        }
+        let mut small = BoundedQueue::new(2);
+        assert_eq!(produce(&mut small, &[1, 2, 3]), Err(QueueError::Full));
+        assert_eq!(small.into_iter().collect::<Vec<_>>(), [1, 2]);
+
+        let mut none = BoundedQueue::new(0);
+        assert_eq!(none.enqueue('a'), Err(Full('a')));
+        assert_eq!(none.force_enqueue('a'), Some('a'));
+        assert!(none.is_empty() && none.is_full());
+        assert_eq!(Full('a').to_string(), "the queue is full");
+    }
+
+    #[test]
+    fn queues_work_with_iterators() {
+        let ring: Queue<i32> = (1..=4).collect();
+        let two_stacks: TwoStackQueue<i32> = (1..=4).collect();
+        assert_eq!((&ring).into_iter().sum::<i32>(), 10);
+        assert_eq!(ring.clone().into_iter().collect::<Vec<_>>(), [1, 2, 3, 4]);
+        assert_eq!(ring, ring.iter().copied().collect());
+
+        let mut two_stacks = two_stacks;
+        assert_eq!(two_stacks.dequeue(), Ok(1));
+        two_stacks.extend([5, 6]);
+        assert_eq!(two_stacks.iter().len(), 5);
+        assert_eq!(two_stacks.clone().into_iter().collect::<Vec<_>>(), [2, 3, 4, 5, 6]);
+        assert_eq!(two_stacks, (2..=6).collect());
+
+        let mut strings = Queue::new();
+        strings.enqueue(String::from("a"));
+        strings.enqueue(String::from("b"));
+        let joined: String = strings.into_iter().collect();
+        assert_eq!(joined, "ab");
+    }
+
+    // Works the same way on every implementation of the trait
+    fn reverse<S: Stack<char> + Default>(s: &str) -> String {
+        let mut stack = S::default();
+        for c in s.chars() {
+            stack.push(c);
+        }
+        assert_eq!(stack.len(), s.chars().count());
+        assert_eq!(stack.peek().ok(), s.chars().last().as_ref());
+        let mut reversed = String::new();
+        while let Ok(c) = stack.pop() {
+            reversed.push(c);
+        }
+        assert!(stack.is_empty());
+        reversed
+    }
+
+    #[test]
+    fn stacks_share_a_trait() {
+        assert_eq!(reverse::<myStack<char>>("stack"), "kcats");
+        assert_eq!(reverse::<Vec<char>>("stack"), "kcats");
+        assert_eq!(reverse::<myStack<char>>(""), "");
+        assert_eq!(QueueError::Empty.to_string(), "the queue is empty");
+    }
+
+    // Fills each queue and drains it again. A Vec queue shifts every remaining
+    // element on each dequeue, so the others have to beat it by a wide margin.
+    // Add --nocapture to see the timings.
+    #[test]
+    fn queues_beat_a_vec() {
+        use std::hint::black_box;
+        use std::time::{Duration, Instant};
+
+        const N: usize = 40_000;
+        // The best of a few runs, which is the least disturbed by anything
+        // else running at the same time
+        fn time(name: &str, run: impl Fn()) -> Duration {
+            let elapsed = (0..3)
+                .map(|_| {
+                    let start = Instant::now();
+                    run();
+                    start.elapsed()
+                })
+                .min()
+                .unwrap();
+            println!("{name:<16} {elapsed:>12.2?}");
+            elapsed
+        }
+
+        // Start the table below the name of the test
+        println!();
+        let vec = time("Vec::remove(0)", || {
+            let mut queue: Vec<usize> = (0..N).collect();
+            while !queue.is_empty() {
+                black_box(queue.remove(0));
+            }
+        });
+        let queues = [
+            time("Queue", || {
+                let mut queue: Queue<usize> = (0..N).collect();
+                while let Ok(value) = queue.dequeue() {
+                    black_box(value);
+                }
+            }),
+            time("BoundedQueue", || {
+                let mut queue = BoundedQueue::new(N);
+                for i in 0..N {
+                    queue.enqueue(i).unwrap();
+                }
+                while let Ok(value) = queue.dequeue() {
+                    black_box(value);
+                }
+            }),
+            time("TwoStackQueue", || {
+                let mut queue: TwoStackQueue<usize> = (0..N).collect();
+                while let Ok(value) = queue.dequeue() {
+                    black_box(value);
+                }
+            }),
+        ];
+        for elapsed in queues {
+            assert!(elapsed * 4 < vec, "{elapsed:?} is not much faster than {vec:?}");
+        }
+    }
 }
\ No newline at end of file
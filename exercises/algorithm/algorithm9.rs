@@ -5,35 +5,147 @@
 */
 
 use std::cmp::Ord;
-use std::default::Default;
+use std::fmt;
 
-pub struct Heap<T>
+// 随机测试用的数在 random 模块里，其他算法题也用它
+#[cfg(test)]
+mod random;
+
+// 比较器：before(a, b) 为 true 时 a 应该比 b 先出堆
+pub trait Comparator<T> {
+    fn before(&self, a: &T, b: &T) -> bool;
+}
+
+impl<T, F> Comparator<T> for F
 where
-    T: Default + Clone,
+    F: Fn(&T, &T) -> bool,
 {
-    // 堆的大小
-    count: usize,
+    fn before(&self, a: &T, b: &T) -> bool {
+        self(a, b)
+    }
+}
+
+// 小的先出堆
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinOrder;
+
+// 大的先出堆
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaxOrder;
+
+impl<T: Ord> Comparator<T> for MinOrder {
+    fn before(&self, a: &T, b: &T) -> bool {
+        a < b
+    }
+}
+
+impl<T: Ord> Comparator<T> for MaxOrder {
+    fn before(&self, a: &T, b: &T) -> bool {
+        a > b
+    }
+}
+
+// 堆的元素从下标 0 开始存放，下标 i 的子节点是 2i+1 和 2i+2
+pub struct Heap<T, C = fn(&T, &T) -> bool> {
     // 堆的元素
     items: Vec<T>,
     // 比较器
-    comparator: fn(&T, &T) -> bool,
+    comparator: C,
 }
 
-impl<T> Heap<T>
+pub type MinHeap<T> = Heap<T, MinOrder>;
+pub type MaxHeap<T> = Heap<T, MaxOrder>;
+
+impl<T, F> Heap<T, F>
 where
-    T: Default + Clone,
+    F: Fn(&T, &T) -> bool,
 {
-    pub fn new(comparator: fn(&T, &T) -> bool) -> Self {
-        Self {
-            count: 0,
-            items: vec![T::default()],
-            comparator,
+    pub fn with_comparator(comparator: F) -> Self {
+        Self::heapify(Vec::new(), comparator)
+    }
+
+    // 用 O(n) 的自底向上建堆
+    pub fn from_vec(items: Vec<T>, comparator: F) -> Self {
+        Self::heapify(items, comparator)
+    }
+}
+
+impl<T: Ord> Heap<T> {
+    /// Create a new MinHeap
+    pub fn new_min() -> Self {
+        Self::with_comparator(|a, b| a < b)
+    }
+
+    /// Create a new MaxHeap
+    pub fn new_max() -> Self {
+        Self::with_comparator(|a, b| a > b)
+    }
+}
+
+impl<T: Ord> MinHeap<T> {
+    pub fn new() -> Self {
+        Self::heapify(Vec::new(), MinOrder)
+    }
+}
+
+impl<T: Ord> MaxHeap<T> {
+    pub fn new() -> Self {
+        Self::heapify(Vec::new(), MaxOrder)
+    }
+}
+
+impl<T: Ord> Default for MinHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> Default for MaxHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> From<Vec<T>> for MinHeap<T> {
+    fn from(items: Vec<T>) -> Self {
+        Self::heapify(items, MinOrder)
+    }
+}
+
+impl<T: Ord> From<Vec<T>> for MaxHeap<T> {
+    fn from(items: Vec<T>) -> Self {
+        Self::heapify(items, MaxOrder)
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MinHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MaxHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T, C> Heap<T, C>
+where
+    C: Comparator<T>,
+{
+    // 从最后一个有子节点的节点开始往前逐个下沉，越靠下的节点下沉的距离越短，总共是 O(n)
+    fn heapify(items: Vec<T>, comparator: C) -> Self {
+        let mut heap = Self { items, comparator };
+        for idx in (0..heap.len() / 2).rev() {
+            heap.sift_down(idx, heap.len());
         }
+        heap
     }
 
     // 返回堆的大小
     pub fn len(&self) -> usize {
-        self.count
+        self.items.len()
     }
 
     // 判断堆是否为空
@@ -43,16 +155,72 @@ where
 
     // 添加元素
     pub fn add(&mut self, value: T) {
-        self.count += 1;
         self.items.push(value);
-        self.sift_up(self.count);
+        self.sift_up(self.len() - 1);
+    }
+
+    // 查看堆顶的元素
+    pub fn peek(&self) -> Option<&T> {
+        self.items.first()
+    }
+
+    // 取出堆顶的元素：把最后一个元素换到堆顶，再让它下沉
+    pub fn pop(&mut self) -> Option<T> {
+        if self.items.is_empty() {
+            return None;
+        }
+        let result = self.items.swap_remove(0);
+        self.sift_down(0, self.len());
+        Some(result)
+    }
+
+    // 把另一个堆的元素并进来。另一个堆比较大时整个重新建堆更快
+    pub fn merge(&mut self, other: Heap<T, C>) {
+        let added = other.len();
+        let total = self.len() + added;
+        // 逐个添加要 O(added · log total)，重新建堆要 O(total)
+        let log_total = (usize::BITS - total.leading_zeros()) as usize;
+        self.items.extend(other.items);
+        if added * log_total > total {
+            for idx in (0..total / 2).rev() {
+                self.sift_down(idx, total);
+            }
+        } else {
+            for idx in total - added..total {
+                self.sift_up(idx);
+            }
+        }
+    }
+
+    // 按出堆的顺序返回所有元素。原地堆排序：每次把堆顶换到末尾，最后再反转
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        for end in (1..self.len()).rev() {
+            self.items.swap(0, end);
+            self.sift_down(0, end);
+        }
+        self.items.reverse();
+        self.items
+    }
+
+    // 按堆中存放的顺序返回所有元素
+    pub fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+
+    // 按任意顺序遍历
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.items.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
     }
 
     // 上浮操作
     fn sift_up(&mut self, mut idx: usize) {
-        while idx > 1 {
-            let parent = self.parent_idx(idx);
-            if (self.comparator)(&self.items[idx], &self.items[parent]) {
+        while idx > 0 {
+            let parent = parent_idx(idx);
+            if self.comparator.before(&self.items[idx], &self.items[parent]) {
                 self.items.swap(idx, parent);
                 idx = parent;
             } else {
@@ -61,129 +229,271 @@ where
         }
     }
 
-    // 返回父节点的索引
-    fn parent_idx(&self, idx: usize) -> usize {
-        idx / 2
+    // 下沉操作，只看前 end 个元素
+    fn sift_down(&mut self, mut idx: usize, end: usize) {
+        while let Some(child) = optimal_child_idx(idx, end, |a, b| {
+            self.comparator.before(&self.items[a], &self.items[b])
+        }) {
+            if self.comparator.before(&self.items[child], &self.items[idx]) {
+                self.items.swap(idx, child);
+                idx = child;
+            } else {
+                break;
+            }
+        }
     }
+}
+
+// 返回父节点的索引
+fn parent_idx(idx: usize) -> usize {
+    (idx - 1) / 2
+}
+
+// 返回左子节点的索引
+fn left_child_idx(idx: usize) -> usize {
+    idx * 2 + 1
+}
 
-    // 判断子节点是否存在
-    fn children_present(&self, idx: usize) -> bool {
-        self.left_child_idx(idx) <= self.count
+// 返回右子节点的索引
+fn right_child_idx(idx: usize) -> usize {
+    left_child_idx(idx) + 1
+}
+
+// 返回前 end 个元素中最优的子节点索引，没有子节点时返回 None
+fn optimal_child_idx(idx: usize, end: usize, before: impl Fn(usize, usize) -> bool) -> Option<usize> {
+    let left = left_child_idx(idx);
+    let right = right_child_idx(idx);
+    if left >= end {
+        None
+    } else if right < end && before(right, left) {
+        Some(right)
+    } else {
+        Some(left)
     }
+}
+
+// 实现迭代器 trait，按出堆的顺序取出元素
+impl<T, C> Iterator for Heap<T, C>
+where
+    C: Comparator<T>,
+{
+    type Item = T;
 
-    // 返回左子节点的索引
-    fn left_child_idx(&self, idx: usize) -> usize {
-        idx * 2
+    fn next(&mut self) -> Option<T> {
+        self.pop()
     }
 
-    // 返回右子节点的索引
-    fn right_child_idx(&self, idx: usize) -> usize {
-        self.left_child_idx(idx) + 1
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
     }
+}
 
-    // 返回最优的子节点索引
-    fn optimal_child_idx(&self, idx: usize) -> usize {
-        if self.right_child_idx(idx) > self.count {
-            return self.left_child_idx(idx);
+impl<T, C> ExactSizeIterator for Heap<T, C> where C: Comparator<T> {}
+
+impl<T, C> Extend<T> for Heap<T, C>
+where
+    C: Comparator<T>,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.add(value);
         }
-        
-        let left = self.left_child_idx(idx);
-        let right = self.right_child_idx(idx);
-        if (self.comparator)(&self.items[left], &self.items[right]) {
-            left
-        } else {
-            right
+    }
+}
+
+impl<T: fmt::Debug, C> fmt::Debug for Heap<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Heap").field("items", &self.items).finish()
+    }
+}
+
+// 索引优先队列的错误
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriorityError {
+    // 这个索引不在队列中
+    NotInQueue(usize),
+    // decrease_key 的新优先级会让元素更晚出队
+    NotDecreased(usize),
+}
+
+impl fmt::Display for PriorityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PriorityError::NotInQueue(index) => write!(f, "{index} is not in the queue"),
+            PriorityError::NotDecreased(index) => {
+                write!(f, "the new priority of {index} would move it back in the queue")
+            }
         }
     }
 }
 
-impl<T> Heap<T>
-where
-    T: Default + Ord + Clone,
-{
-    /// Create a new MinHeap
-    pub fn new_min() -> Self {
-        Self::new(|a, b| a < b)
+impl std::error::Error for PriorityError {}
+
+// 索引优先队列：元素是 0、1、2…这样的索引，每个索引带一个优先级。
+// 额外记录每个索引在堆中的位置，所以能在 O(log n) 内修改任意索引的优先级，
+// Dijkstra 算法用它来更新到节点的距离，而不用把同一个节点放进堆很多次。
+pub struct IndexedPriorityQueue<P, C = MinOrder> {
+    // 堆中的 (索引, 优先级)
+    heap: Vec<(usize, P)>,
+    // positions[index] 是索引在 heap 中的位置
+    positions: Vec<Option<usize>>,
+    comparator: C,
+}
+
+impl<P: Ord> IndexedPriorityQueue<P> {
+    // 优先级小的先出队
+    pub fn new() -> Self {
+        Self::with_comparator(MinOrder)
     }
+}
 
-    /// Create a new MaxHeap
-    pub fn new_max() -> Self {
-        Self::new(|a, b| a > b)
+impl<P: Ord> Default for IndexedPriorityQueue<P> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-// 实现迭代器 trait
-impl<T> Iterator for Heap<T>
+impl<P, C> IndexedPriorityQueue<P, C>
 where
-    T: Default + Clone,
+    C: Comparator<P>,
 {
-    type Item = T;
+    pub fn with_comparator(comparator: C) -> Self {
+        Self {
+            heap: Vec::new(),
+            positions: Vec::new(),
+            comparator,
+        }
+    }
 
-    fn next(&mut self) -> Option<T> {
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    fn position(&self, index: usize) -> Option<usize> {
+        self.positions.get(index).copied().flatten()
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        self.position(index).is_some()
+    }
+
+    // 索引当前的优先级
+    pub fn priority(&self, index: usize) -> Option<&P> {
+        self.position(index).map(|position| &self.heap[position].1)
+    }
+
+    // 添加索引。索引已经在队列中时修改它的优先级，并返回原来的优先级
+    pub fn push(&mut self, index: usize, priority: P) -> Option<P> {
+        if self.contains(index) {
+            return self.change_priority(index, priority).ok();
+        }
+        if index >= self.positions.len() {
+            self.positions.resize(index + 1, None);
+        }
+        self.heap.push((index, priority));
+        self.positions[index] = Some(self.len() - 1);
+        self.sift_up(self.len() - 1);
+        None
+    }
+
+    // 查看最先出队的索引和它的优先级
+    pub fn peek(&self) -> Option<(usize, &P)> {
+        self.heap.first().map(|(index, priority)| (*index, priority))
+    }
+
+    pub fn pop(&mut self) -> Option<(usize, P)> {
         if self.is_empty() {
             return None;
         }
+        Some(self.remove_at(0))
+    }
 
-        let result = self.items[1].clone();
-        self.items[1] = self.items[self.count].clone();
-        self.items.pop();
-        self.count -= 1;
+    // 把索引从队列中移除，返回它的优先级
+    pub fn remove(&mut self, index: usize) -> Option<P> {
+        let position = self.position(index)?;
+        Some(self.remove_at(position).1)
+    }
 
-        if !self.is_empty() {
-            self.sift_down(1);
+    // 把索引的优先级改成更靠前的值；新优先级会让它更晚出队时不做修改并返回错误
+    pub fn decrease_key(&mut self, index: usize, priority: P) -> Result<(), PriorityError> {
+        let position = self.position(index).ok_or(PriorityError::NotInQueue(index))?;
+        if self.comparator.before(&self.heap[position].1, &priority) {
+            return Err(PriorityError::NotDecreased(index));
         }
+        self.heap[position].1 = priority;
+        self.sift_up(position);
+        Ok(())
+    }
 
-        Some(result)
+    // 把索引的优先级改成任意值，返回原来的优先级
+    pub fn change_priority(&mut self, index: usize, priority: P) -> Result<P, PriorityError> {
+        let position = self.position(index).ok_or(PriorityError::NotInQueue(index))?;
+        let old = std::mem::replace(&mut self.heap[position].1, priority);
+        let position = self.sift_up(position);
+        self.sift_down(position);
+        Ok(old)
     }
-}
 
-impl<T> Heap<T>
-where
-    T: Default + Clone,
-{
-    fn sift_down(&mut self, mut idx: usize) {
-        while self.children_present(idx) {
-            let child = self.optimal_child_idx(idx);
-            if !(self.comparator)(&self.items[idx], &self.items[child]) {
-                self.items.swap(idx, child);
-                idx = child;
-            } else {
-                break;
-            }
+    // 把 position 处的元素换到末尾移除，再把换过来的元素放到正确的位置
+    fn remove_at(&mut self, position: usize) -> (usize, P) {
+        let last = self.len() - 1;
+        self.swap(position, last);
+        let (index, priority) = self.heap.pop().unwrap();
+        self.positions[index] = None;
+        if position < self.len() {
+            let position = self.sift_up(position);
+            self.sift_down(position);
         }
+        (index, priority)
     }
-}
 
-pub struct MinHeap;
+    // 交换堆中的两个元素，同时更新它们的位置
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.positions[self.heap[a].0] = Some(a);
+        self.positions[self.heap[b].0] = Some(b);
+    }
 
-impl MinHeap {
-    #[allow(clippy::new_ret_no_self)]
-    pub fn new<T>() -> Heap<T>
-    where
-        T: Default + Ord + Clone,
-    {
-        Heap::new(|a, b| a < b)
+    fn before(&self, a: usize, b: usize) -> bool {
+        self.comparator.before(&self.heap[a].1, &self.heap[b].1)
     }
-}
 
-pub struct MaxHeap;
+    // 上浮操作，返回元素最后所在的位置
+    fn sift_up(&mut self, mut position: usize) -> usize {
+        while position > 0 {
+            let parent = parent_idx(position);
+            if !self.before(position, parent) {
+                break;
+            }
+            self.swap(position, parent);
+            position = parent;
+        }
+        position
+    }
 
-impl MaxHeap {
-    #[allow(clippy::new_ret_no_self)]
-    pub fn new<T>() -> Heap<T>
-    where
-        T: Default + Ord + Clone,
-    {
-        Heap::new(|a, b| a > b)
+    // 下沉操作
+    fn sift_down(&mut self, mut position: usize) {
+        while let Some(child) = optimal_child_idx(position, self.len(), |a, b| self.before(a, b)) {
+            if !self.before(child, position) {
+                break;
+            }
+            self.swap(position, child);
+            position = child;
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::random::random_numbers;
     #[test]
     fn test_empty_heap() {
-        let mut heap = MaxHeap::new::<i32>();
+        let mut heap = MaxHeap::<i32>::new();
         assert_eq!(heap.next(), None);
     }
 
@@ -216,4 +526,183 @@ mod tests {
         heap.add(1);
         assert_eq!(heap.next(), Some(2));
     }
-}
\ No newline at end of file
+
+    // Every parent comes out before its children
+    fn assert_heap<T, C: Comparator<T>>(heap: &Heap<T, C>) {
+        for idx in 1..heap.len() {
+            assert!(!heap.comparator.before(&heap.items[idx], &heap.items[parent_idx(idx)]));
+        }
+    }
+
+    #[test]
+    fn test_from_vec_and_into_sorted_vec() {
+        for seed in 0..20 {
+            let values: Vec<u64> = random_numbers(seed).take(seed as usize * 7).map(|n| n % 50).collect();
+            let mut sorted = values.clone();
+            sorted.sort();
+
+            let min = MinHeap::from(values.clone());
+            assert_heap(&min);
+            assert_eq!(min.peek(), sorted.first());
+            assert_eq!(min.into_sorted_vec(), sorted);
+
+            let max: MaxHeap<u64> = values.iter().copied().collect();
+            assert_heap(&max);
+            assert_eq!(max.peek(), sorted.last());
+            assert_eq!(max.into_sorted_vec(), sorted.iter().rev().copied().collect::<Vec<_>>());
+
+            let heap = Heap::from_vec(values.clone(), |a, b| a % 10 < b % 10);
+            let by_last_digit: Vec<u64> = heap.map(|n| n % 10).collect();
+            assert!(by_last_digit.windows(2).all(|pair| pair[0] <= pair[1]));
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        // Small into large adds one at a time, large into small rebuilds
+        for (left, right) in [(100, 3), (3, 100), (0, 5), (5, 0)] {
+            let mut heap: MinHeap<u64> = random_numbers(left).take(left as usize).collect();
+            let other: MinHeap<u64> = random_numbers(right).take(right as usize).collect();
+            let mut expected: Vec<u64> = heap.iter().chain(other.iter()).copied().collect();
+            expected.sort();
+            heap.merge(other);
+            assert_heap(&heap);
+            assert_eq!(heap.len(), expected.len());
+            assert_eq!(heap.into_sorted_vec(), expected);
+        }
+    }
+
+    #[test]
+    fn test_closure_comparators() {
+        // The comparator can capture what it compares by
+        let ranks = std::collections::HashMap::from([("gold", 1), ("silver", 2), ("bronze", 3)]);
+        let mut heap = Heap::with_comparator(|a: &&str, b: &&str| ranks[a] < ranks[b]);
+        heap.extend(["silver", "bronze", "gold"]);
+        assert_eq!(heap.peek(), Some(&"gold"));
+        assert_eq!(heap.collect::<Vec<_>>(), ["gold", "silver", "bronze"]);
+
+        // The values needn't be Clone or Default
+        struct Task(u32, String);
+        let mut tasks = Heap::with_comparator(|a: &Task, b: &Task| a.0 > b.0);
+        tasks.add(Task(1, String::from("low")));
+        tasks.add(Task(5, String::from("high")));
+        assert_eq!(tasks.pop().map(|task| task.1), Some(String::from("high")));
+        assert_eq!(tasks.len(), 1);
+        tasks.clear();
+        assert!(tasks.is_empty());
+        assert!(tasks.pop().is_none());
+
+        // Picking the order at runtime needs a single type for both
+        let mut heaps = [Heap::new_min(), Heap::new_max()];
+        for heap in &mut heaps {
+            heap.extend([3, 1, 2]);
+        }
+        assert_eq!(heaps.map(|heap| heap.into_sorted_vec()), [[1, 2, 3], [3, 2, 1]]);
+    }
+
+    #[test]
+    fn test_indexed_priority_queue() {
+        let mut queue = IndexedPriorityQueue::new();
+        assert_eq!(queue.push(3, 30), None);
+        assert_eq!(queue.push(0, 10), None);
+        assert_eq!(queue.push(7, 70), None);
+        assert_eq!(queue.peek(), Some((0, &10)));
+        assert_eq!(queue.decrease_key(7, 5), Ok(()));
+        assert_eq!(queue.peek(), Some((7, &5)));
+        assert_eq!(queue.decrease_key(7, 6), Err(PriorityError::NotDecreased(7)));
+        assert_eq!(queue.priority(7), Some(&5));
+        assert_eq!(queue.decrease_key(1, 0), Err(PriorityError::NotInQueue(1)));
+        assert_eq!(queue.change_priority(7, 100), Ok(5));
+        assert_eq!(queue.push(0, 40), Some(10));
+        assert_eq!(queue.remove(3), Some(30));
+        assert_eq!(queue.remove(3), None);
+        assert!(!queue.contains(3) && queue.contains(0));
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.pop(), Some((0, 40)));
+        assert_eq!(queue.pop(), Some((7, 100)));
+        assert_eq!(queue.pop(), None);
+        assert_eq!(PriorityError::NotInQueue(1).to_string(), "1 is not in the queue");
+
+        let mut latest = IndexedPriorityQueue::with_comparator(|a: &u32, b: &u32| a > b);
+        latest.push(0, 1);
+        latest.push(1, 2);
+        assert_eq!(latest.decrease_key(0, 3), Ok(()));
+        assert_eq!(latest.peek(), Some((0, &3)));
+    }
+
+    #[test]
+    fn test_indexed_priority_queue_agrees_with_a_map() {
+        for seed in 0..20 {
+            let mut oracle = std::collections::BTreeMap::new();
+            let mut queue = IndexedPriorityQueue::new();
+            let mut numbers = random_numbers(seed);
+            for _ in 0..300 {
+                let index = (numbers.next().unwrap() % 20) as usize;
+                let priority = numbers.next().unwrap() % 100;
+                match numbers.next().unwrap() % 4 {
+                    0 => assert_eq!(queue.push(index, priority), oracle.insert(index, priority)),
+                    1 => assert_eq!(queue.remove(index), oracle.remove(&index)),
+                    2 => {
+                        let old = oracle.get_mut(&index).map(|old| std::mem::replace(old, priority));
+                        assert_eq!(queue.change_priority(index, priority).ok(), old);
+                    }
+                    _ => {
+                        // Ties come out in any order, but with the lowest priority
+                        let lowest = oracle.values().min().copied();
+                        let popped = queue.pop();
+                        assert_eq!(popped.map(|(_, priority)| priority), lowest);
+                        if let Some((index, priority)) = popped {
+                            assert_eq!(oracle.remove(&index), Some(priority));
+                        }
+                    }
+                }
+                assert_eq!(queue.len(), oracle.len());
+                for (index, priority) in &oracle {
+                    assert_eq!(queue.priority(*index), Some(priority));
+                }
+            }
+        }
+    }
+
+    // Dijkstra with one entry per node in the queue, lowering its distance
+    // whenever a shorter path to it turns up
+    fn dijkstra(adjacency: &[Vec<(usize, u64)>], source: usize) -> Vec<Option<u64>> {
+        let mut distances = vec![None; adjacency.len()];
+        let mut queue = IndexedPriorityQueue::new();
+        queue.push(source, 0);
+        while let Some((node, distance)) = queue.pop() {
+            distances[node] = Some(distance);
+            for &(next, weight) in &adjacency[node] {
+                if distances[next].is_some() {
+                    continue;
+                }
+                let candidate = distance + weight;
+                match queue.priority(next) {
+                    None => {
+                        queue.push(next, candidate);
+                    }
+                    Some(&current) if candidate < current => queue.decrease_key(next, candidate).unwrap(),
+                    Some(_) => {}
+                }
+            }
+        }
+        distances
+    }
+
+    #[test]
+    fn test_drives_dijkstra() {
+        let adjacency = vec![
+            vec![(1, 7), (2, 9), (5, 14)],
+            vec![(0, 7), (2, 10), (3, 15)],
+            vec![(0, 9), (1, 10), (3, 11), (5, 2)],
+            vec![(1, 15), (2, 11), (4, 6)],
+            vec![(3, 6), (5, 9)],
+            vec![(0, 14), (2, 2), (4, 9)],
+            vec![],
+        ];
+        assert_eq!(
+            dijkstra(&adjacency, 0),
+            [Some(0), Some(7), Some(9), Some(20), Some(20), Some(11), None]
+        );
+    }
+}
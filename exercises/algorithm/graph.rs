@@ -0,0 +1,385 @@
+// 通用的图模块，algorithm5、algorithm6 和 algorithm10 共用
+//
+// A graph is generic over its nodes, the weights of its edges, whether its
+// edges are directed, and how the edges are stored. Nodes are kept in the
+// order they were added, and the storage only ever deals with their indices.
+
+// Not every exercise uses every part of the module
+#![allow(dead_code)]
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::mem;
+
+// 图的节点不存在的错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeNotInGraph;
+
+impl fmt::Display for NodeNotInGraph {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "accessing a node that is not in the graph")
+    }
+}
+
+impl std::error::Error for NodeNotInGraph {}
+
+// Whether the edges of a graph have a direction
+pub trait EdgeType {
+    const DIRECTED: bool;
+}
+
+// 有向图：边 a -> b 不代表 b -> a
+#[derive(Debug)]
+pub enum Directed {}
+
+// 无向图：每条边两个方向都能走
+#[derive(Debug)]
+pub enum Undirected {}
+
+impl EdgeType for Directed {
+    const DIRECTED: bool = true;
+}
+
+impl EdgeType for Undirected {
+    const DIRECTED: bool = false;
+}
+
+// How the edges between node indices are stored. There is at most one edge
+// from a node to another, so adding it again replaces its weight.
+pub trait Storage<W> {
+    fn new() -> Self;
+    fn add_node(&mut self);
+    // Add the edge, returning the weight it had if it was already there
+    fn insert_edge(&mut self, from: usize, to: usize, weight: W) -> Option<W>;
+    fn remove_edge(&mut self, from: usize, to: usize) -> Option<W>;
+    fn edge(&self, from: usize, to: usize) -> Option<&W>;
+    // The nodes the edges from the node lead to, with their weights
+    fn neighbors(&self, from: usize) -> Box<dyn Iterator<Item = (usize, &W)> + '_>;
+}
+
+// 邻接表：每个节点保存它的出边，适合稀疏图。邻居按边加入的顺序排列。
+#[derive(Debug, Clone)]
+pub struct AdjacencyList<W> {
+    adj: Vec<Vec<(usize, W)>>,
+}
+
+impl<W> Storage<W> for AdjacencyList<W> {
+    fn new() -> Self {
+        AdjacencyList { adj: Vec::new() }
+    }
+
+    fn add_node(&mut self) {
+        self.adj.push(Vec::new());
+    }
+
+    fn insert_edge(&mut self, from: usize, to: usize, weight: W) -> Option<W> {
+        let edges = &mut self.adj[from];
+        match edges.iter_mut().find(|(node, _)| *node == to) {
+            Some((_, old)) => Some(mem::replace(old, weight)),
+            None => {
+                edges.push((to, weight));
+                None
+            }
+        }
+    }
+
+    fn remove_edge(&mut self, from: usize, to: usize) -> Option<W> {
+        let edges = &mut self.adj[from];
+        let position = edges.iter().position(|(node, _)| *node == to)?;
+        Some(edges.remove(position).1)
+    }
+
+    fn edge(&self, from: usize, to: usize) -> Option<&W> {
+        self.adj[from]
+            .iter()
+            .find(|(node, _)| *node == to)
+            .map(|(_, weight)| weight)
+    }
+
+    fn neighbors(&self, from: usize) -> Box<dyn Iterator<Item = (usize, &W)> + '_> {
+        Box::new(self.adj[from].iter().map(|(node, weight)| (*node, weight)))
+    }
+}
+
+// 邻接矩阵：matrix[from][to] 是边的权重，适合稠密图，查找一条边是 O(1)。
+// 邻居按节点加入的顺序排列。
+#[derive(Debug, Clone)]
+pub struct AdjacencyMatrix<W> {
+    matrix: Vec<Vec<Option<W>>>,
+}
+
+impl<W> Storage<W> for AdjacencyMatrix<W> {
+    fn new() -> Self {
+        AdjacencyMatrix { matrix: Vec::new() }
+    }
+
+    fn add_node(&mut self) {
+        for row in &mut self.matrix {
+            row.push(None);
+        }
+        let len = self.matrix.len() + 1;
+        self.matrix.push((0..len).map(|_| None).collect());
+    }
+
+    fn insert_edge(&mut self, from: usize, to: usize, weight: W) -> Option<W> {
+        self.matrix[from][to].replace(weight)
+    }
+
+    fn remove_edge(&mut self, from: usize, to: usize) -> Option<W> {
+        self.matrix[from][to].take()
+    }
+
+    fn edge(&self, from: usize, to: usize) -> Option<&W> {
+        self.matrix[from][to].as_ref()
+    }
+
+    fn neighbors(&self, from: usize) -> Box<dyn Iterator<Item = (usize, &W)> + '_> {
+        Box::new(
+            self.matrix[from]
+                .iter()
+                .enumerate()
+                .filter_map(|(to, weight)| Some((to, weight.as_ref()?))),
+        )
+    }
+}
+
+// 图：节点类型 N，边的权重类型 W，有向或无向 E，存储方式 S
+#[derive(Debug)]
+pub struct Graph<N, W = (), E = Undirected, S = AdjacencyList<W>> {
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    storage: S,
+    edge_count: usize,
+    marker: PhantomData<(W, E)>,
+}
+
+pub type DirectedGraph<N, W = (), S = AdjacencyList<W>> = Graph<N, W, Directed, S>;
+pub type UndirectedGraph<N, W = (), S = AdjacencyList<W>> = Graph<N, W, Undirected, S>;
+
+impl<N, W, E, S> Default for Graph<N, W, E, S>
+where
+    N: Eq + Hash + Clone,
+    E: EdgeType,
+    S: Storage<W>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N, W, E, S> Graph<N, W, E, S>
+where
+    N: Eq + Hash + Clone,
+    E: EdgeType,
+    S: Storage<W>,
+{
+    pub fn new() -> Self {
+        Graph {
+            nodes: Vec::new(),
+            indices: HashMap::new(),
+            storage: S::new(),
+            edge_count: 0,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        E::DIRECTED
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    // The number of edges, counting an undirected edge once
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.indices.contains_key(node)
+    }
+
+    // Add the node, returning whether it wasn't in the graph yet
+    pub fn add_node(&mut self, node: N) -> bool {
+        let count = self.node_count();
+        self.insert_node(node) == count
+    }
+
+    fn insert_node(&mut self, node: N) -> usize {
+        if let Some(&index) = self.indices.get(&node) {
+            return index;
+        }
+        let index = self.nodes.len();
+        self.nodes.push(node.clone());
+        self.indices.insert(node, index);
+        self.storage.add_node();
+        index
+    }
+
+    pub(crate) fn index(&self, node: &N) -> Result<usize, NodeNotInGraph> {
+        self.indices.get(node).copied().ok_or(NodeNotInGraph)
+    }
+
+    pub(crate) fn node(&self, index: usize) -> &N {
+        &self.nodes[index]
+    }
+
+    pub(crate) fn neighbor_indices(&self, index: usize) -> impl Iterator<Item = (usize, &W)> {
+        self.storage.neighbors(index)
+    }
+
+    // Add an edge, and the nodes at its ends if they aren't in the graph
+    // yet, returning the weight the edge had if it was already there
+    pub fn add_edge(&mut self, from: N, to: N, weight: W) -> Option<W>
+    where
+        W: Clone,
+    {
+        let from = self.insert_node(from);
+        let to = self.insert_node(to);
+        if !E::DIRECTED && from != to {
+            self.storage.insert_edge(to, from, weight.clone());
+        }
+        let old = self.storage.insert_edge(from, to, weight);
+        if old.is_none() {
+            self.edge_count += 1;
+        }
+        old
+    }
+
+    // Remove the edge, returning its weight if it was there
+    pub fn remove_edge(&mut self, from: &N, to: &N) -> Result<Option<W>, NodeNotInGraph> {
+        let from = self.index(from)?;
+        let to = self.index(to)?;
+        if !E::DIRECTED && from != to {
+            self.storage.remove_edge(to, from);
+        }
+        let removed = self.storage.remove_edge(from, to);
+        if removed.is_some() {
+            self.edge_count -= 1;
+        }
+        Ok(removed)
+    }
+
+    // The weight of the edge, if there is one
+    pub fn edge(&self, from: &N, to: &N) -> Result<Option<&W>, NodeNotInGraph> {
+        Ok(self.storage.edge(self.index(from)?, self.index(to)?))
+    }
+
+    // The nodes in the order they were added
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.nodes.iter()
+    }
+
+    // Every edge that can be followed, with its weight. An undirected edge
+    // can be followed both ways, so it comes up in both directions.
+    pub fn edges(&self) -> impl Iterator<Item = (&N, &N, &W)> {
+        (0..self.nodes.len()).flat_map(move |from| {
+            self.storage
+                .neighbors(from)
+                .map(move |(to, weight)| (&self.nodes[from], &self.nodes[to], weight))
+        })
+    }
+
+    // The nodes an edge from the node leads to, with the weights of those edges
+    pub fn neighbors(&self, node: &N) -> Result<impl Iterator<Item = (&N, &W)>, NodeNotInGraph> {
+        let index = self.index(node)?;
+        Ok(self
+            .storage
+            .neighbors(index)
+            .map(move |(to, weight)| (&self.nodes[to], weight)))
+    }
+
+    // The nodes that can be reached from the start, nearest first
+    pub fn bfs(&self, start: &N) -> Result<Bfs<'_, N, W, E, S>, NodeNotInGraph> {
+        let start = self.index(start)?;
+        let mut visited = vec![false; self.nodes.len()];
+        visited[start] = true;
+        Ok(Bfs {
+            graph: self,
+            visited,
+            queue: VecDeque::from([start]),
+        })
+    }
+
+    // The nodes that can be reached from the start, following each path as
+    // far as it goes before backing up
+    pub fn dfs(&self, start: &N) -> Result<Dfs<'_, N, W, E, S>, NodeNotInGraph> {
+        let start = self.index(start)?;
+        Ok(Dfs {
+            graph: self,
+            visited: vec![false; self.nodes.len()],
+            stack: vec![start],
+        })
+    }
+}
+
+// 广度优先搜索
+pub struct Bfs<'a, N, W, E, S> {
+    graph: &'a Graph<N, W, E, S>,
+    // 记录每个节点是否已经放入过队列
+    visited: Vec<bool>,
+    // 待访问的节点
+    queue: VecDeque<usize>,
+}
+
+impl<'a, N, W, E, S> Iterator for Bfs<'a, N, W, E, S>
+where
+    N: Eq + Hash + Clone,
+    E: EdgeType,
+    S: Storage<W>,
+{
+    type Item = &'a N;
+
+    fn next(&mut self) -> Option<&'a N> {
+        // 从队列中取出一个节点（当前节点）
+        let current = self.queue.pop_front()?;
+        // 遍历当前节点的所有邻接节点，如果邻接节点未被访问过，则将其标记为已访问，并放入队列中
+        for (neighbor, _) in self.graph.storage.neighbors(current) {
+            if !self.visited[neighbor] {
+                self.visited[neighbor] = true;
+                self.queue.push_back(neighbor);
+            }
+        }
+        Some(&self.graph.nodes[current])
+    }
+}
+
+// 深度优先搜索，用栈代替递归，访问顺序和递归的写法一样
+pub struct Dfs<'a, N, W, E, S> {
+    graph: &'a Graph<N, W, E, S>,
+    visited: Vec<bool>,
+    stack: Vec<usize>,
+}
+
+impl<'a, N, W, E, S> Iterator for Dfs<'a, N, W, E, S>
+where
+    N: Eq + Hash + Clone,
+    E: EdgeType,
+    S: Storage<W>,
+{
+    type Item = &'a N;
+
+    fn next(&mut self) -> Option<&'a N> {
+        while let Some(current) = self.stack.pop() {
+            // 一个节点可能从多条边被放入栈中，只访问第一次
+            if self.visited[current] {
+                continue;
+            }
+            self.visited[current] = true;
+            // 邻居倒序入栈，这样第一个邻居最先被访问
+            let neighbors: Vec<usize> = self
+                .graph
+                .storage
+                .neighbors(current)
+                .map(|(neighbor, _)| neighbor)
+                .filter(|&neighbor| !self.visited[neighbor])
+                .collect();
+            self.stack.extend(neighbors.into_iter().rev());
+            return Some(&self.graph.nodes[current]);
+        }
+        None
+    }
+}
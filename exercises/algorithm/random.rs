@@ -0,0 +1,14 @@
+// 随机测试共用的伪随机数生成器
+//
+// The randomized tests of the algorithm exercises check them against the
+// standard library on inputs drawn from here. The numbers only depend on the
+// seed, so a failing test fails the same way on every run.
+
+// Pseudo-random numbers from a linear congruential generator
+pub fn random_numbers(seed: u64) -> impl Iterator<Item = u64> {
+    let mut state = seed;
+    std::iter::repeat_with(move || {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        state >> 33
+    })
+}
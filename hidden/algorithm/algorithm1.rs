@@ -0,0 +1,79 @@
+// Property for exercises/algorithm/algorithm1.rs
+//
+// Merging two sorted lists has to give the same result as sorting both of
+// them together, and any sequence of insertions and removals has to leave
+// the list holding the same values as a `Vec` it is compared with.
+
+fn property(g: &mut rustlings_property::Gen) -> Result<(), String> {
+    let mut vec_a = g.vec_i32(30, -100..100);
+    let mut vec_b = g.vec_i32(30, -100..100);
+    vec_a.sort();
+    vec_b.sort();
+
+    let mut list_a = LinkedList::<i32>::new();
+    let mut list_b = LinkedList::<i32>::new();
+    for &value in &vec_a {
+        list_a.add(value);
+    }
+    for &value in &vec_b {
+        list_b.add(value);
+    }
+    let merged = LinkedList::<i32>::merge(list_a, list_b);
+
+    let mut expected: Vec<i32> = vec_a.iter().chain(&vec_b).copied().collect();
+    expected.sort();
+    let actual: Vec<i32> = (0..expected.len())
+        .map_while(|i| merged.get(i).copied())
+        .collect();
+    if actual != expected || merged.length as usize != expected.len() {
+        return Err(format!(
+            "merging {vec_a:?} and {vec_b:?} gave {actual:?} of length {}",
+            merged.length
+        ));
+    }
+
+    let mut list = LinkedList::<i32>::new();
+    let mut model: Vec<i32> = Vec::new();
+    let mut operations = Vec::new();
+    for _ in 0..g.usize_in(0..40) {
+        let value = g.i32_in(-100..100);
+        match g.usize_in(0..5) {
+            0 => {
+                operations.push(format!("add({value})"));
+                list.add(value);
+                model.push(value);
+            }
+            1 => {
+                operations.push(format!("push_front({value})"));
+                list.push_front(value);
+                model.insert(0, value);
+            }
+            2 => {
+                operations.push("pop_front()".to_string());
+                let expected = (!model.is_empty()).then(|| model.remove(0));
+                if list.pop_front() != expected {
+                    return Err(format!("after {operations:?}, pop_front() didn't return {expected:?}"));
+                }
+            }
+            3 => {
+                let index = g.usize_in(0..model.len() + 1);
+                operations.push(format!("insert({index}, {value})"));
+                list.insert(index, value);
+                model.insert(index, value);
+            }
+            _ => {
+                let index = g.usize_in(0..model.len() + 1);
+                operations.push(format!("remove({index})"));
+                let expected = (index < model.len()).then(|| model.remove(index));
+                if list.remove(index) != expected {
+                    return Err(format!("after {operations:?}, remove({index}) didn't return {expected:?}"));
+                }
+            }
+        }
+        let values: Vec<i32> = list.iter().copied().collect();
+        if values != model || list.len() != model.len() {
+            return Err(format!("after {operations:?}, the list holds {values:?} instead of {model:?}"));
+        }
+    }
+    Ok(())
+}
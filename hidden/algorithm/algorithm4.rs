@@ -0,0 +1,65 @@
+// Property for exercises/algorithm/algorithm4.rs
+//
+// After any sequence of insertions and removals, the tree has to contain
+// exactly the values a `BTreeSet` does, in the same order, and the AVL
+// tree has to stay within the height an AVL tree can have.
+
+fn property(g: &mut rustlings_property::Gen) -> Result<(), String> {
+    let values = g.vec_i32(40, -50..50);
+    let mut bst = BinarySearchTree::new();
+    let mut oracle = std::collections::BTreeSet::new();
+    for &value in &values {
+        bst.insert(value);
+        oracle.insert(value);
+    }
+    for probe in -51..51 {
+        if bst.search(probe) != oracle.contains(&probe) {
+            return Err(format!(
+                "after inserting {values:?}, searching {probe} returned {}",
+                bst.search(probe)
+            ));
+        }
+    }
+
+    let mut avl: AvlTree<i32> = values.iter().copied().collect();
+    let mut operations = Vec::new();
+    for _ in 0..g.usize_in(0..60) {
+        let value = g.i32_in(-50..50);
+        let (actual, avl_actual, expected) = if g.bool() {
+            operations.push(format!("insert({value})"));
+            let expected = oracle.insert(value);
+            bst.insert(value);
+            (expected, avl.insert(value), expected)
+        } else {
+            operations.push(format!("remove({value})"));
+            (bst.remove(&value), avl.remove(&value), oracle.remove(&value))
+        };
+        if actual != expected || avl_actual != expected {
+            return Err(format!(
+                "after inserting {values:?} and {operations:?}, the last one returned {actual} \
+                 for the tree and {avl_actual} for the AVL tree instead of {expected}"
+            ));
+        }
+        if !bst.iter().eq(oracle.iter()) || bst.len() != oracle.len() {
+            let actual: Vec<_> = bst.iter().collect();
+            return Err(format!(
+                "after inserting {values:?} and {operations:?}, the tree holds {actual:?} instead of {oracle:?}"
+            ));
+        }
+        if !avl.iter().eq(oracle.iter()) || avl.len() != oracle.len() {
+            let actual: Vec<_> = avl.iter().collect();
+            return Err(format!(
+                "after inserting {values:?} and {operations:?}, the AVL tree holds {actual:?} instead of {oracle:?}"
+            ));
+        }
+        let bound = 1.45 * ((avl.len() + 2) as f64).log2();
+        if avl.height() as f64 >= bound {
+            return Err(format!(
+                "after inserting {values:?} and {operations:?}, the AVL tree of {} values is {} high",
+                avl.len(),
+                avl.height()
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,88 @@
+// Property for exercises/algorithm/algorithm7.rs
+//
+// The bracket matcher has to agree with a straightforward reference matcher
+// on random strings of brackets and other characters, down to where and why
+// the first mismatch happens. The evaluator has to agree with a sum of
+// products on random expressions of `+`, `-`, `*` and negated numbers.
+
+fn property(g: &mut rustlings_property::Gen) -> Result<(), String> {
+    let chars = ['(', ')', '[', ']', '{', '}', 'a', '+'];
+    let input: String = g.vec(30, |g| *g.choose(&chars)).into_iter().collect();
+
+    let mut open = Vec::new();
+    let mut expected = Ok(());
+    for (position, c) in input.char_indices() {
+        let opening = match c {
+            ')' => '(',
+            ']' => '[',
+            '}' => '{',
+            '(' | '[' | '{' => {
+                open.push((c, position));
+                continue;
+            }
+            _ => continue,
+        };
+        match open.pop() {
+            Some((o, _)) if o == opening => {}
+            Some((o, open_position)) => {
+                expected = Err(BracketError::Mismatched { open: o, open_position, close: c, position });
+                break;
+            }
+            None => {
+                expected = Err(BracketError::Unexpected { close: c, position });
+                break;
+            }
+        }
+    }
+    if expected.is_ok() {
+        if let Some((o, position)) = open.pop() {
+            expected = Err(BracketError::Unclosed { open: o, position });
+        }
+    }
+
+    let actual = bracket_check(&input);
+    if actual != expected {
+        return Err(format!("bracket_check({input:?}) returned {actual:?} instead of {expected:?}"));
+    }
+    if bracket_match(&input) != expected.is_ok() {
+        return Err(format!("bracket_match({input:?}) returned {}", !expected.is_ok()));
+    }
+
+    // The value is the sum of the terms, each of them a product of numbers
+    let mut expr = String::new();
+    let mut sum = 0i64;
+    let mut term = 1i64;
+    let mut sign = 1i64;
+    for i in 0..g.usize_in(1..8) {
+        if i > 0 {
+            match *g.choose(&['+', '-', '*']) {
+                '*' => expr.push_str(" * "),
+                op => {
+                    sum += sign * term;
+                    sign = if op == '+' { 1 } else { -1 };
+                    term = 1;
+                    expr.push_str(if op == '+' { " + " } else { " - " });
+                }
+            }
+        }
+        let mut number = g.i64_in(0..10);
+        expr.push_str(&number.to_string());
+        if g.bool() {
+            expr.insert(expr.len() - 1, '-');
+            number = -number;
+        }
+        term *= number;
+    }
+    sum += sign * term;
+
+    let actual = evaluate(&expr);
+    if actual != Ok(sum) {
+        return Err(format!("evaluate({expr:?}) returned {actual:?} instead of Ok({sum})"));
+    }
+    let parenthesized = format!("({expr})");
+    let actual = evaluate(&parenthesized);
+    if actual != Ok(sum) {
+        return Err(format!("evaluate({parenthesized:?}) returned {actual:?} instead of Ok({sum})"));
+    }
+    Ok(())
+}
@@ -0,0 +1,92 @@
+// Property for exercises/algorithm/algorithm9.rs
+//
+// Any mix of additions and removals has to behave like the standard library's
+// `BinaryHeap`, for both min and max heaps. Heaps built from a vector or
+// merged from two heaps have to come out sorted, and the indexed priority
+// queue has to give out the lowest priority it holds.
+
+fn property(g: &mut rustlings_property::Gen) -> Result<(), String> {
+    let min = g.bool();
+    // Some(value) adds a value, None takes the next one out of the heap
+    let ops = g.vec(60, |g| if g.below(3) == 0 { None } else { Some(g.i32_in(-20..20)) });
+
+    let mut heap = if min { Heap::new_min() } else { Heap::new_max() };
+    let mut oracle = std::collections::BinaryHeap::new();
+    for (step, op) in ops.iter().enumerate() {
+        match op {
+            Some(value) => {
+                heap.add(*value);
+                oracle.push(if min { -value } else { *value });
+            }
+            None => {
+                let expected = oracle.pop().map(|v| if min { -v } else { v });
+                let actual = heap.next();
+                if actual != expected {
+                    let kind = if min { "min" } else { "max" };
+                    return Err(format!(
+                        "the {kind} heap returned {actual:?} instead of {expected:?} \
+                         at step {step} of {ops:?}"
+                    ));
+                }
+            }
+        }
+        if heap.len() != oracle.len() {
+            return Err(format!("the heap has the wrong length after {:?}", &ops[..=step]));
+        }
+    }
+
+    let left = g.vec_i32(30, -20..20);
+    let right = g.vec_i32(30, -20..20);
+    let mut expected: Vec<i32> = left.iter().chain(&right).copied().collect();
+    expected.sort();
+    let mut merged = MinHeap::from(left.clone());
+    merged.merge(MinHeap::from(right.clone()));
+    let actual = merged.into_sorted_vec();
+    if actual != expected {
+        return Err(format!(
+            "merging the heaps of {left:?} and {right:?} gave {actual:?} instead of {expected:?}"
+        ));
+    }
+    expected.reverse();
+    let actual = MaxHeap::from(left.iter().chain(&right).copied().collect::<Vec<_>>()).into_sorted_vec();
+    if actual != expected {
+        return Err(format!(
+            "the max heap of {left:?} and {right:?} gave {actual:?} instead of {expected:?}"
+        ));
+    }
+
+    // (index, Some(priority)) sets the priority of an index, (index, None)
+    // takes the lowest priority out of the queue
+    let ops = g.vec(60, |g| (g.usize_in(0..10), if g.below(3) == 0 { None } else { Some(g.i32_in(-20..20)) }));
+    let mut queue = IndexedPriorityQueue::new();
+    let mut priorities = std::collections::HashMap::new();
+    for (step, &(index, op)) in ops.iter().enumerate() {
+        match op {
+            Some(priority) => {
+                if queue.push(index, priority) != priorities.insert(index, priority) {
+                    return Err(format!("push returned the wrong priority at step {step} of {ops:?}"));
+                }
+            }
+            None => {
+                let lowest = priorities.values().min().copied();
+                let popped = queue.pop();
+                let valid = match popped {
+                    Some((index, priority)) => {
+                        Some(priority) == lowest && priorities.remove(&index) == Some(priority)
+                    }
+                    None => lowest.is_none(),
+                };
+                if !valid {
+                    return Err(format!(
+                        "the queue gave out {popped:?} instead of the lowest priority {lowest:?} \
+                         at step {step} of {ops:?}"
+                    ));
+                }
+            }
+        }
+        if queue.len() != priorities.len() {
+            return Err(format!("the queue has the wrong length after {:?}", &ops[..=step]));
+        }
+    }
+    Ok(())
+}
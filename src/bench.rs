@@ -0,0 +1,209 @@
+use crate::exercise::{temp_file, Exercise};
+use crate::harness::{build, GeneratedFiles};
+use serde::Deserialize;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::process::Command;
+use std::time::Duration;
+
+const DEFAULT_ITERATIONS: usize = 15;
+const DEFAULT_MAX_SLOWDOWN: f64 = 3.0;
+const OPT_ARGS: &[&str] = &["-C", "opt-level=3"];
+
+// The harness appended to the exercise and its hidden workload. It runs the
+// `workload` function once to warm up, then prints how long each of the
+// requested iterations took in nanoseconds, one per line.
+const HARNESS: &str = r#"
+fn main() {
+    let iterations: usize = std::env::args()
+        .nth(1)
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(1);
+    std::hint::black_box(workload());
+    for _ in 0..iterations {
+        let start = std::time::Instant::now();
+        std::hint::black_box(workload());
+        println!("{}", start.elapsed().as_nanos());
+    }
+}
+"#;
+
+// The settings of a Bench exercise, given in info.toml as
+// `bench = { iterations = 15, max_slowdown = 3.0 }`
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct BenchSettings {
+    // How many times the workload is measured
+    #[serde(default = "default_iterations")]
+    pub iterations: usize,
+    // How many times slower than the reference solution the exercise may be
+    #[serde(default = "default_max_slowdown")]
+    pub max_slowdown: f64,
+}
+
+fn default_iterations() -> usize {
+    DEFAULT_ITERATIONS
+}
+
+fn default_max_slowdown() -> f64 {
+    DEFAULT_MAX_SLOWDOWN
+}
+
+impl Default for BenchSettings {
+    fn default() -> Self {
+        BenchSettings {
+            iterations: DEFAULT_ITERATIONS,
+            max_slowdown: DEFAULT_MAX_SLOWDOWN,
+        }
+    }
+}
+
+// The measured timings of one implementation
+pub struct Timings {
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl Timings {
+    fn from_nanos(mut nanos: Vec<u64>) -> Option<Timings> {
+        nanos.sort_unstable();
+        let median = *nanos.get(nanos.len() / 2)?;
+        Some(Timings {
+            median: Duration::from_nanos(median),
+            min: Duration::from_nanos(nanos[0]),
+            max: Duration::from_nanos(nanos[nanos.len() - 1]),
+        })
+    }
+}
+
+// The result of benchmarking an exercise against its reference solution
+pub struct BenchReport {
+    pub iterations: usize,
+    pub exercise: Timings,
+    pub reference: Timings,
+    pub max_slowdown: f64,
+}
+
+impl BenchReport {
+    // The median time the exercise may take at most
+    pub fn budget(&self) -> Duration {
+        self.reference.median.mul_f64(self.max_slowdown)
+    }
+
+    pub fn passed(&self) -> bool {
+        self.exercise.median <= self.budget()
+    }
+}
+
+impl Display for BenchReport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "{}", tr!("bench.results", iterations = self.iterations))?;
+        writeln!(
+            f,
+            "  {:<20} {:>12} {:>12} {:>12}",
+            "",
+            tr!("bench.median"),
+            tr!("bench.min"),
+            tr!("bench.max")
+        )?;
+        for (label, timings) in [
+            (tr!("bench.yours"), &self.exercise),
+            (tr!("bench.reference"), &self.reference),
+        ] {
+            writeln!(
+                f,
+                "  {label:<20} {:>12} {:>12} {:>12}",
+                format!("{:.2?}", timings.median),
+                format!("{:.2?}", timings.min),
+                format!("{:.2?}", timings.max),
+            )?;
+        }
+        write!(
+            f,
+            "  {}",
+            tr!(
+                "bench.budget",
+                budget = format!("{:.2?}", self.budget()),
+                max_slowdown = self.max_slowdown
+            )
+        )
+    }
+}
+
+// Time the hidden workload of the exercise against the exercise itself and
+// against its reference solution, both compiled with optimizations.
+pub fn bench(exercise: &Exercise) -> Result<BenchReport, String> {
+    let settings = exercise.bench.clone().unwrap_or_default();
+    let workload_path = exercise.hidden_path();
+    let workload = fs::read_to_string(&workload_path).map_err(|e| {
+        tr!(
+            "bench.no_workload",
+            path = workload_path.display(),
+            error = e
+        )
+    })?;
+    let solution_path = exercise.solution_path();
+    if !solution_path.exists() {
+        return Err(tr!("bench.no_solution", path = solution_path.display()));
+    }
+
+    let reference = format!("{}_bench_reference", temp_file());
+    let own = format!("{}_bench_exercise", temp_file());
+    let mut files = GeneratedFiles::default();
+    build(&solution_path, &workload, HARNESS, &reference, OPT_ARGS, &mut files)?;
+    build(&exercise.path, &workload, HARNESS, &own, OPT_ARGS, &mut files)?;
+
+    let reference = measure(&reference, settings.iterations)?;
+    let own = measure(&own, settings.iterations)?;
+
+    Ok(BenchReport {
+        iterations: settings.iterations,
+        exercise: own,
+        reference,
+        max_slowdown: settings.max_slowdown,
+    })
+}
+
+fn measure(binary: &str, iterations: usize) -> Result<Timings, String> {
+    let output = Command::new(binary)
+        .arg(iterations.to_string())
+        .output()
+        .map_err(|e| tr!("bench.run_failed", error = e))?;
+    if !output.status.success() {
+        return Err(tr!(
+            "bench.failed",
+            output = String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    let nanos = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.trim().parse().ok())
+        .collect();
+    Timings::from_nanos(nanos).ok_or_else(|| tr!("bench.no_timings").into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_timings() {
+        let timings = Timings::from_nanos(vec![40, 10, 30, 20, 50]).unwrap();
+        assert_eq!(timings.median, Duration::from_nanos(30));
+        assert_eq!(timings.min, Duration::from_nanos(10));
+        assert_eq!(timings.max, Duration::from_nanos(50));
+        assert!(Timings::from_nanos(vec![]).is_none());
+    }
+
+    #[test]
+    fn test_budget() {
+        let report = BenchReport {
+            iterations: 3,
+            exercise: Timings::from_nanos(vec![250]).unwrap(),
+            reference: Timings::from_nanos(vec![100]).unwrap(),
+            max_slowdown: 2.0,
+        };
+        assert_eq!(report.budget(), Duration::from_nanos(200));
+        assert!(!report.passed());
+    }
+}
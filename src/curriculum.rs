@@ -0,0 +1,426 @@
+use crate::exercise::Exercise;
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::str::FromStr;
+
+// A chapter listed in info.toml, to give it prerequisites of its own:
+//
+//   [[chapters]]
+//   name = "iterators"
+//   depends_on = ["generics"]
+//
+// The first exercise of a listed chapter no longer waits for the exercise
+// before it in info.toml, but only for the prerequisites of the chapter.
+#[derive(Deserialize, Clone, Debug)]
+pub struct Chapter {
+    pub name: String,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+// Something an exercise waits for before it is unlocked
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Prerequisite {
+    // The exercise at this index of info.toml
+    Exercise(usize),
+    // Every exercise of the chapter
+    Chapter(String),
+}
+
+// The formats the curriculum can be drawn in
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+}
+
+impl FromStr for GraphFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dot" => Ok(GraphFormat::Dot),
+            "mermaid" => Ok(GraphFormat::Mermaid),
+            _ => Err(tr!("main.unknown_format", format = s, expected = "dot, mermaid")),
+        }
+    }
+}
+
+// The exercises of info.toml as a graph of prerequisites. Without any
+// `depends_on`, every exercise waits for the one before it, which makes
+// the graph the plain list of info.toml.
+pub struct Curriculum<'a> {
+    exercises: &'a [Exercise],
+    // The prerequisites of each exercise, from its own `depends_on` or else
+    // the exercise before it
+    prerequisites: Vec<Vec<Prerequisite>>,
+    // The chapters listed in info.toml with their prerequisites
+    chapters: Vec<(String, Vec<Prerequisite>)>,
+    // The indices of the exercises with every exercise after its
+    // prerequisites, and otherwise in the order of info.toml
+    order: Vec<usize>,
+}
+
+impl<'a> Curriculum<'a> {
+    pub fn new(exercises: &'a [Exercise], chapters: &[Chapter]) -> Result<Self, String> {
+        let resolve = |name: &str, of: &str| {
+            if let Some(index) = exercises.iter().position(|e| e.name == name) {
+                Ok(Prerequisite::Exercise(index))
+            } else if exercises.iter().any(|e| e.chapter() == name) {
+                Ok(Prerequisite::Chapter(name.to_string()))
+            } else {
+                Err(tr!("curriculum.unknown_prerequisite", of = of, name = name))
+            }
+        };
+
+        let chapters = chapters
+            .iter()
+            .map(|chapter| {
+                if !exercises.iter().any(|e| e.chapter() == chapter.name) {
+                    return Err(tr!("curriculum.empty_chapter", chapter = chapter.name));
+                }
+                let depends_on = chapter
+                    .depends_on
+                    .iter()
+                    .map(|name| resolve(name, &chapter.name))
+                    .collect::<Result<_, _>>()?;
+                Ok((chapter.name.clone(), depends_on))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let prerequisites = exercises
+            .iter()
+            .enumerate()
+            .map(|(i, exercise)| match &exercise.depends_on {
+                Some(names) => names.iter().map(|name| resolve(name, &exercise.name)).collect(),
+                None => {
+                    let starts_listed_chapter = (i == 0
+                        || exercises[i - 1].chapter() != exercise.chapter())
+                        && chapters.iter().any(|(name, _)| *name == exercise.chapter());
+                    if i == 0 || starts_listed_chapter {
+                        Ok(vec![])
+                    } else {
+                        Ok(vec![Prerequisite::Exercise(i - 1)])
+                    }
+                }
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let mut curriculum = Curriculum {
+            exercises,
+            prerequisites,
+            chapters,
+            order: vec![],
+        };
+        curriculum.order = curriculum.topological_order()?;
+        Ok(curriculum)
+    }
+
+    pub fn exercises(&self) -> &'a [Exercise] {
+        self.exercises
+    }
+
+    // The indices of the exercises a prerequisite stands for
+    fn indices(&self, prerequisite: &Prerequisite) -> Vec<usize> {
+        match prerequisite {
+            Prerequisite::Exercise(index) => vec![*index],
+            Prerequisite::Chapter(chapter) => (0..self.exercises.len())
+                .filter(|i| self.exercises[*i].chapter() == *chapter)
+                .collect(),
+        }
+    }
+
+    // Every exercise the exercise at `index` waits for directly, including
+    // the prerequisites of its chapter
+    fn all_prerequisites(&self, index: usize) -> BTreeSet<usize> {
+        let chapter = self.exercises[index].chapter();
+        let of_chapter = self
+            .chapters
+            .iter()
+            .filter(|(name, _)| *name == chapter)
+            .flat_map(|(_, depends_on)| depends_on);
+        self.prerequisites[index]
+            .iter()
+            .chain(of_chapter)
+            .flat_map(|prerequisite| self.indices(prerequisite))
+            .collect()
+    }
+
+    // Kahn's algorithm, always taking the first exercise of info.toml that
+    // is ready, so that the order only differs from info.toml where it has to
+    fn topological_order(&self) -> Result<Vec<usize>, String> {
+        let prerequisites: Vec<BTreeSet<usize>> =
+            (0..self.exercises.len()).map(|i| self.all_prerequisites(i)).collect();
+        let mut waiting: Vec<usize> = prerequisites.iter().map(BTreeSet::len).collect();
+        let mut ready: BTreeSet<usize> = (0..waiting.len()).filter(|i| waiting[*i] == 0).collect();
+        let mut order = Vec::with_capacity(waiting.len());
+        while let Some(index) = ready.pop_first() {
+            order.push(index);
+            for (other, of_other) in prerequisites.iter().enumerate() {
+                if of_other.contains(&index) {
+                    waiting[other] -= 1;
+                    if waiting[other] == 0 {
+                        ready.insert(other);
+                    }
+                }
+            }
+        }
+        if order.len() < self.exercises.len() {
+            let cycle: Vec<&str> = (0..self.exercises.len())
+                .filter(|i| waiting[*i] > 0)
+                .map(|i| self.exercises[i].name.as_str())
+                .collect();
+            return Err(tr!("curriculum.cycle", exercises = cycle.join(", ")));
+        }
+        Ok(order)
+    }
+
+    // The exercises with every exercise after its prerequisites
+    pub fn in_order(&self) -> impl Iterator<Item = &'a Exercise> + '_ {
+        self.order.iter().map(|i| &self.exercises[*i])
+    }
+
+    fn done(&self) -> Vec<bool> {
+        self.exercises.iter().map(Exercise::looks_done).collect()
+    }
+
+    // The names of the prerequisites of the exercise that aren't done yet
+    pub fn missing_prerequisites(&self, exercise: &Exercise) -> Vec<&'a str> {
+        let Some(index) = self.exercises.iter().position(|e| e.name == exercise.name) else {
+            return vec![];
+        };
+        self.all_prerequisites(index)
+            .into_iter()
+            .filter(|i| !self.exercises[*i].looks_done())
+            .map(|i| self.exercises[i].name.as_str())
+            .collect()
+    }
+
+    // The exercises that aren't done yet, but whose prerequisites are
+    pub fn unlocked(&self) -> Vec<&'a Exercise> {
+        let done = self.done();
+        self.order
+            .iter()
+            .filter(|i| !done[**i] && self.all_prerequisites(**i).iter().all(|p| done[*p]))
+            .map(|i| &self.exercises[*i])
+            .collect()
+    }
+
+    // The chapters in the order of their first exercise, with the indices
+    // of their exercises
+    fn chapter_groups(&self) -> Vec<(String, Vec<usize>)> {
+        let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+        for (i, exercise) in self.exercises.iter().enumerate() {
+            let chapter = exercise.chapter();
+            match groups.iter_mut().find(|(name, _)| *name == chapter) {
+                Some((_, indices)) => indices.push(i),
+                None => groups.push((chapter, vec![i])),
+            }
+        }
+        groups
+    }
+
+    pub fn render(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => self.dot(),
+            GraphFormat::Mermaid => self.mermaid(),
+        }
+    }
+
+    // A Graphviz graph with a cluster per chapter. Edges from and to a
+    // chapter are drawn between its clusters.
+    fn dot(&self) -> String {
+        let done = self.done();
+        let unlocked: Vec<&str> = self.unlocked().iter().map(|e| e.name.as_str()).collect();
+        let groups = self.chapter_groups();
+        let last_of = |chapter: &str| {
+            let (_, indices) = groups.iter().find(|(name, _)| name == chapter).unwrap();
+            self.exercises[*indices.last().unwrap()].name.as_str()
+        };
+
+        let mut dot = String::from("digraph curriculum {\n    compound=true;\n    node [shape=box];\n");
+        for (chapter, indices) in &groups {
+            let _ = writeln!(dot, "    subgraph \"cluster_{chapter}\" {{\n        label=\"{chapter}\";");
+            for i in indices {
+                let name = &self.exercises[*i].name;
+                let style = if done[*i] {
+                    " [style=filled, fillcolor=palegreen]"
+                } else if unlocked.contains(&name.as_str()) {
+                    " [style=filled, fillcolor=lightyellow]"
+                } else {
+                    ""
+                };
+                let _ = writeln!(dot, "        \"{name}\"{style};");
+            }
+            dot.push_str("    }\n");
+        }
+        for (i, prerequisites) in self.prerequisites.iter().enumerate() {
+            for prerequisite in prerequisites {
+                let _ = match prerequisite {
+                    Prerequisite::Exercise(j) => writeln!(
+                        dot,
+                        "    \"{}\" -> \"{}\";",
+                        self.exercises[*j].name, self.exercises[i].name
+                    ),
+                    Prerequisite::Chapter(chapter) => writeln!(
+                        dot,
+                        "    \"{}\" -> \"{}\" [ltail=\"cluster_{chapter}\"];",
+                        last_of(chapter),
+                        self.exercises[i].name
+                    ),
+                };
+            }
+        }
+        for (chapter, depends_on) in &self.chapters {
+            let (_, indices) = groups.iter().find(|(name, _)| name == chapter).unwrap();
+            let first = &self.exercises[indices[0]].name;
+            for prerequisite in depends_on {
+                let _ = match prerequisite {
+                    Prerequisite::Exercise(j) => writeln!(
+                        dot,
+                        "    \"{}\" -> \"{first}\" [lhead=\"cluster_{chapter}\"];",
+                        self.exercises[*j].name
+                    ),
+                    Prerequisite::Chapter(other) => writeln!(
+                        dot,
+                        "    \"{}\" -> \"{first}\" [ltail=\"cluster_{other}\", lhead=\"cluster_{chapter}\"];",
+                        last_of(other)
+                    ),
+                };
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    // A Mermaid flowchart with a subgraph per chapter
+    fn mermaid(&self) -> String {
+        let done = self.done();
+        let unlocked = self.unlocked();
+        let node = |prerequisite: &Prerequisite| match prerequisite {
+            Prerequisite::Exercise(j) => self.exercises[*j].name.clone(),
+            Prerequisite::Chapter(chapter) => format!("chapter_{chapter}"),
+        };
+
+        let mut mermaid = String::from("flowchart TD\n");
+        for (chapter, indices) in self.chapter_groups() {
+            let _ = writeln!(mermaid, "    subgraph chapter_{chapter} [{chapter}]");
+            for i in indices {
+                let _ = writeln!(mermaid, "        {}", self.exercises[i].name);
+            }
+            mermaid.push_str("    end\n");
+        }
+        for (i, prerequisites) in self.prerequisites.iter().enumerate() {
+            for prerequisite in prerequisites {
+                let _ = writeln!(mermaid, "    {} --> {}", node(prerequisite), self.exercises[i].name);
+            }
+        }
+        for (chapter, depends_on) in &self.chapters {
+            for prerequisite in depends_on {
+                let _ = writeln!(mermaid, "    {} --> chapter_{chapter}", node(prerequisite));
+            }
+        }
+        let done: Vec<&str> = (0..done.len())
+            .filter(|i| done[*i])
+            .map(|i| self.exercises[i].name.as_str())
+            .collect();
+        let unlocked: Vec<&str> = unlocked.iter().map(|e| e.name.as_str()).collect();
+        for (class, fill, names) in [("done", "#cfc", done), ("unlocked", "#ffc", unlocked)] {
+            if !names.is_empty() {
+                let _ = writeln!(mermaid, "    classDef {class} fill:{fill}");
+                let _ = writeln!(mermaid, "    class {} {class}", names.join(","));
+            }
+        }
+        mermaid
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::Mode;
+    use std::path::PathBuf;
+
+    fn exercise(path: &str, depends_on: Option<&[&str]>) -> Exercise {
+        let path = PathBuf::from(path);
+        Exercise {
+            name: path.file_stem().unwrap().to_string_lossy().to_string(),
+            path,
+            mode: Mode::Test,
+            hint: String::new(),
+            expected_stdout: None,
+            stdin: None,
+            bench: None,
+            property: None,
+            check_ub: false,
+            depends_on: depends_on.map(|names| names.iter().map(|n| n.to_string()).collect()),
+        }
+    }
+
+    fn chapter(name: &str, depends_on: &[&str]) -> Chapter {
+        Chapter {
+            name: name.to_string(),
+            depends_on: depends_on.iter().map(|n| n.to_string()).collect(),
+        }
+    }
+
+    fn names<'a>(exercises: impl IntoIterator<Item = &'a Exercise>) -> Vec<&'a str> {
+        exercises.into_iter().map(|e| e.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_plain_list() {
+        let exercises = [
+            exercise("exercises/intro/intro1.rs", None),
+            exercise("exercises/intro/intro2.rs", None),
+            exercise("exercises/vecs/vecs1.rs", None),
+        ];
+        let curriculum = Curriculum::new(&exercises, &[]).unwrap();
+        assert_eq!(names(curriculum.in_order()), ["intro1", "intro2", "vecs1"]);
+        assert_eq!(curriculum.all_prerequisites(2), BTreeSet::from([1]));
+    }
+
+    #[test]
+    fn test_chapter_prerequisites() {
+        let exercises = [
+            exercise("exercises/intro/intro1.rs", None),
+            exercise("exercises/lifetimes/lifetimes1.rs", None),
+            exercise("exercises/lifetimes/lifetimes2.rs", None),
+            exercise("exercises/iterators/iterators1.rs", None),
+            exercise("exercises/quiz.rs", Some(&["lifetimes", "iterators1"])),
+        ];
+        let curriculum =
+            Curriculum::new(&exercises, &[chapter("iterators", &["intro1"])]).unwrap();
+        assert_eq!(curriculum.all_prerequisites(3), BTreeSet::from([0]));
+        assert_eq!(curriculum.all_prerequisites(4), BTreeSet::from([1, 2, 3]));
+        assert_eq!(
+            names(curriculum.in_order()),
+            ["intro1", "lifetimes1", "lifetimes2", "iterators1", "quiz"]
+        );
+    }
+
+    #[test]
+    fn test_order_follows_prerequisites() {
+        let exercises = [
+            exercise("exercises/a/a1.rs", Some(&["b1"])),
+            exercise("exercises/b/b1.rs", Some(&[])),
+        ];
+        let curriculum = Curriculum::new(&exercises, &[]).unwrap();
+        assert_eq!(names(curriculum.in_order()), ["b1", "a1"]);
+    }
+
+    #[test]
+    fn test_invalid_prerequisites() {
+        let exercises = [
+            exercise("exercises/a/a1.rs", Some(&["a2"])),
+            exercise("exercises/a/a2.rs", None),
+        ];
+        let error = Curriculum::new(&exercises, &[]).err().unwrap();
+        assert!(error.contains("cycle: a1, a2"), "{error}");
+
+        let exercises = [exercise("exercises/a/a1.rs", Some(&["nothing"]))];
+        assert!(Curriculum::new(&exercises, &[]).is_err());
+        assert!(Curriculum::new(&exercises[..0], &[chapter("a", &[])]).is_err());
+    }
+}
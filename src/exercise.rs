@@ -1,22 +1,32 @@
+use crate::bench::{bench, BenchSettings};
+use crate::curriculum::Chapter;
+use crate::i18n::localized;
+use crate::output::{color_args, output};
+use crate::property::{check_property, PropertySettings};
+use crate::ub::{check_ub, UbCheck};
+use glob::glob;
 use regex::Regex;
-use serde::Deserialize;
-use std::env;
+use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
 use std::fs::{self, remove_file, File};
-use std::io::Read;
-use std::path::PathBuf;
-use std::process::{self, Command};
-
-const RUSTC_COLOR_ARGS: &[&str] = &["--color", "always"];
-const RUSTC_EDITION_ARGS: &[&str] = &["--edition", "2021"];
-const I_AM_DONE_REGEX: &str = r"(?m)^\s*///?\s*I\s+AM\s+NOT\s+DONE";
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Output, Stdio};
+use std::str::FromStr;
+
+pub const RUSTC_EDITION_ARGS: &[&str] = &["--edition", "2021"];
+pub const I_AM_DONE_REGEX: &str = r"(?m)^\s*///?\s*I\s+AM\s+NOT\s+DONE";
+const MOD_REGEX: &str = r"(?m)^\s*(?:pub(?:\([^)]*\))?\s+)?mod\s+(\w+)\s*;";
 const CONTEXT: usize = 2;
 const CLIPPY_CARGO_TOML_PATH: &str = "./exercises/clippy/Cargo.toml";
 const BUILD_SCRIPT_CARGO_TOML_PATH: &str = "./exercises/tests/Cargo.toml";
+pub const EXERCISES_DIR: &str = "exercises";
+pub const SOLUTIONS_DIR: &str = "solutions";
+pub const HIDDEN_DIR: &str = "hidden";
 
 // Get a temporary file name that is hopefully unique
 #[inline]
-fn temp_file() -> String {
+pub fn temp_file() -> String {
     let thread_id: String = format!("{:?}", std::thread::current().id())
         .chars()
         .filter(|c| c.is_alphanumeric())
@@ -26,7 +36,7 @@ fn temp_file() -> String {
 }
 
 // The mode of the exercise.
-#[derive(Deserialize, Copy, Clone, Debug)]
+#[derive(Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum Mode {
     // Indicates that the exercise should be compiled as a binary
@@ -37,25 +47,138 @@ pub enum Mode {
     Clippy,
     // Indicates that the exercise should be run using cargo with build script
     BuildScript,
+    // Indicates that the exercise is a crate of its own, tested with cargo
+    Cargo,
+    // Indicates that the exercise should be tested, and then benchmarked
+    // against its reference solution
+    Bench,
+    // Indicates that the exercise should be tested, and then checked
+    // against a hidden property with random inputs
+    Property,
+}
+
+impl Mode {
+    // The name of the mode as it is written in info.toml
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Mode::Compile => "compile",
+            Mode::Test => "test",
+            Mode::Clippy => "clippy",
+            Mode::BuildScript => "buildscript",
+            Mode::Cargo => "cargo",
+            Mode::Bench => "bench",
+            Mode::Property => "property",
+        }
+    }
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "compile" => Ok(Mode::Compile),
+            "test" => Ok(Mode::Test),
+            "clippy" => Ok(Mode::Clippy),
+            "buildscript" => Ok(Mode::BuildScript),
+            "cargo" => Ok(Mode::Cargo),
+            "bench" => Ok(Mode::Bench),
+            "property" => Ok(Mode::Property),
+            _ => Err(format!(
+                "unknown mode '{s}', expected one of: \
+                 compile, test, clippy, buildscript, cargo, bench, property"
+            )),
+        }
+    }
 }
 
 #[derive(Deserialize)]
 pub struct ExerciseList {
     pub exercises: Vec<Exercise>,
+    #[serde(default)]
+    pub chapters: Vec<Chapter>,
 }
 
 // A representation of a rustlings exercise.
 // This is deserialized from the accompanying info.toml file
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Clone, Debug)]
 pub struct Exercise {
     // Name of the exercise
     pub name: String,
-    // The path to the file containing the exercise's source code
+    // The path to the file containing the exercise's source code,
+    // or to the crate directory of a Cargo exercise
     pub path: PathBuf,
-    // The mode of the exercise (Test, Compile, or Clippy)
+    // The mode of the exercise (Test, Compile, Clippy, BuildScript, Cargo, Bench or Property)
     pub mode: Mode,
-    // The hint text associated with the exercise
+    // The hint text associated with the exercise, in the selected language
+    #[serde(deserialize_with = "localized")]
     pub hint: String,
+    // What a Compile or Clippy exercise has to print when it is run
+    #[serde(default)]
+    pub expected_stdout: Option<ExpectedOutput>,
+    // The text fed to the standard input of a Compile or Clippy exercise
+    #[serde(default)]
+    pub stdin: Option<String>,
+    // How a Bench exercise is measured, if not with the default settings
+    #[serde(default)]
+    pub bench: Option<BenchSettings>,
+    // How the property of a Property exercise is checked, if not with the default settings
+    #[serde(default)]
+    pub property: Option<PropertySettings>,
+    // Whether the tests of the exercise are also run under Miri, or under
+    // AddressSanitizer without Miri, to catch undefined behavior in unsafe code
+    #[serde(default)]
+    pub check_ub: bool,
+    // The exercises and chapters that have to be done before this exercise.
+    // Without it, the exercise waits for the one before it in info.toml.
+    #[serde(default)]
+    pub depends_on: Option<Vec<String>>,
+}
+
+// The expected standard output of an exercise.
+// In info.toml this is either a plain string, `{ regex = "..." }`
+// or `{ file = "path/to/expected_output.txt" }`.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum ExpectedOutput {
+    // The output has to be exactly this text, ignoring trailing whitespace
+    Exact(String),
+    // The output has to match this regular expression
+    Regex { regex: String },
+    // The output has to be exactly the contents of this file, ignoring trailing whitespace
+    File { file: PathBuf },
+}
+
+impl ExpectedOutput {
+    // Check the output of an exercise, describing the mismatch if there is one
+    pub fn check(&self, stdout: &str) -> Result<(), String> {
+        let stdout = stdout.replace("\r\n", "\n");
+        let matches = match self {
+            ExpectedOutput::Exact(expected) => stdout.trim_end() == expected.trim_end(),
+            ExpectedOutput::Regex { regex } => Regex::new(regex)
+                .map_err(|e| tr!("exercise.invalid_regex", error = e))?
+                .is_match(&stdout),
+            ExpectedOutput::File { file } => {
+                let expected = fs::read_to_string(file).map_err(|e| {
+                    tr!("exercise.unreadable_expected", file = file.display(), error = e)
+                })?;
+                stdout.trim_end() == expected.replace("\r\n", "\n").trim_end()
+            }
+        };
+        if matches {
+            return Ok(());
+        }
+        let expected = match self {
+            ExpectedOutput::Exact(expected) => expected.trim_end().to_string(),
+            ExpectedOutput::Regex { regex } => tr!("exercise.expected_regex", regex = regex),
+            ExpectedOutput::File { file } => tr!("exercise.expected_file", file = file.display()),
+        };
+        Err(tr!(
+            "exercise.unexpected_output",
+            expected = expected,
+            actual = stdout.trim_end()
+        ))
+    }
 }
 
 // An enum to track of the state of an Exercise.
@@ -69,7 +192,7 @@ pub enum State {
 }
 
 // The context information of a pending exercise
-#[derive(PartialEq, Debug)]
+#[derive(Serialize, PartialEq, Debug)]
 pub struct ContextLine {
     // The source code that is still pending completion
     pub line: String,
@@ -109,17 +232,32 @@ impl Drop for FileHandle {
     }
 }
 
+// The files of the modules a single file exercise declares with `mod name;`,
+// like the graph module shared by several algorithm exercises. They live next
+// to the exercise, which is where rustc looks for them.
+pub fn module_files(path: &Path) -> Vec<PathBuf> {
+    let (Ok(source), Some(dir)) = (fs::read_to_string(path), path.parent()) else {
+        return Vec::new();
+    };
+    Regex::new(MOD_REGEX)
+        .unwrap()
+        .captures_iter(&source)
+        .map(|module| dir.join(format!("{}.rs", &module[1])))
+        .filter(|file| file.is_file())
+        .collect()
+}
+
 impl Exercise {
-    pub fn compile(&self) -> Result<CompiledExercise, ExerciseOutput> {
+    pub fn compile(&self) -> Result<CompiledExercise<'_>, ExerciseOutput> {
         let cmd = match self.mode {
             Mode::Compile => Command::new("rustc")
-                .args(&[self.path.to_str().unwrap(), "-o", &temp_file()])
-                .args(RUSTC_COLOR_ARGS)
+                .args([self.path.to_str().unwrap(), "-o", &temp_file()])
+                .args(color_args())
                 .args(RUSTC_EDITION_ARGS)
                 .output(),
-            Mode::Test => Command::new("rustc")
-                .args(&["--test", self.path.to_str().unwrap(), "-o", &temp_file()])
-                .args(RUSTC_COLOR_ARGS)
+            Mode::Test | Mode::Bench | Mode::Property => Command::new("rustc")
+                .args(["--test", self.path.to_str().unwrap(), "-o", &temp_file()])
+                .args(color_args())
                 .args(RUSTC_EDITION_ARGS)
                 .output(),
             Mode::Clippy => {
@@ -130,13 +268,15 @@ version = "0.0.1"
 edition = "2021"
 [[bin]]
 name = "{}"
-path = "{}.rs""#,
-                    self.name, self.name, self.name
+path = "{}""#,
+                    self.name,
+                    self.name,
+                    self.cargo_bin_path()
                 );
-                let cargo_toml_error_msg = if env::var("NO_EMOJI").is_ok() {
-                    "Failed to write Clippy Cargo.toml file."
-                } else {
+                let cargo_toml_error_msg = if output().emoji {
                     "Failed to write 📎 Clippy 📎 Cargo.toml file."
+                } else {
+                    "Failed to write Clippy Cargo.toml file."
                 };
                 fs::write(CLIPPY_CARGO_TOML_PATH, cargo_toml).expect(cargo_toml_error_msg);
                 // To support the ability to run the clippy exercises, build
@@ -144,8 +284,8 @@ path = "{}.rs""#,
                 // compilation failure, this would silently fail. But we expect
                 // clippy to reflect the same failure while compiling later.
                 Command::new("rustc")
-                    .args(&[self.path.to_str().unwrap(), "-o", &temp_file()])
-                    .args(RUSTC_COLOR_ARGS)
+                    .args([self.path.to_str().unwrap(), "-o", &temp_file()])
+                    .args(color_args())
                     .args(RUSTC_EDITION_ARGS)
                     .output()
                     .expect("Failed to compile!");
@@ -154,14 +294,14 @@ path = "{}.rs""#,
                 // This is already fixed on Clippy's master branch. See this issue to track merging into Cargo:
                 // https://github.com/rust-lang/rust-clippy/issues/3837
                 Command::new("cargo")
-                    .args(&["clean", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
-                    .args(RUSTC_COLOR_ARGS)
+                    .args(["clean", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
+                    .args(color_args())
                     .output()
                     .expect("Failed to run 'cargo clean'");
                 Command::new("cargo")
-                    .args(&["clippy", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
-                    .args(RUSTC_COLOR_ARGS)
-                    .args(&["--", "-D", "warnings", "-D", "clippy::float_cmp"])
+                    .args(["clippy", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
+                    .args(color_args())
+                    .args(["--", "-D", "warnings", "-D", "clippy::float_cmp"])
                     .output()
             },
             Mode::BuildScript => {
@@ -172,20 +312,29 @@ version = "0.0.1"
 edition = "2021"
 [[bin]]
 name = "{}"
-path = "{}.rs""#,
-                    self.name, self.name, self.name
+path = "{}""#,
+                    self.name,
+                    self.name,
+                    self.cargo_bin_path()
                 );
-                let cargo_toml_error_msg = if env::var("NO_EMOJI").is_ok() {
-                    "Failed to write Clippy Cargo.toml file."
-                } else {
+                let cargo_toml_error_msg = if output().emoji {
                     "Failed to write 📎 Clippy 📎 Cargo.toml file."
+                } else {
+                    "Failed to write Clippy Cargo.toml file."
                 };
                 fs::write(BUILD_SCRIPT_CARGO_TOML_PATH, cargo_toml).expect(cargo_toml_error_msg);
 
                 Command::new("cargo")
-                    .args(&["test", "--manifest-path", BUILD_SCRIPT_CARGO_TOML_PATH])
+                    .args(["test", "--manifest-path", BUILD_SCRIPT_CARGO_TOML_PATH])
                     .output()
             }
+            // Cargo exercises are built from within their own directory, so that
+            // a `.cargo/config.toml` there can point to vendored dependencies.
+            Mode::Cargo => Command::new("cargo")
+                .args(["test", "--offline", "--no-run"])
+                .args(color_args())
+                .current_dir(&self.path)
+                .output(),
         }
         .expect("Failed to run 'compile' command.");
 
@@ -203,9 +352,41 @@ path = "{}.rs""#,
         }
     }
 
+    // The path of the exercise relative to the shared Cargo.toml files, which
+    // live two directories below the rustlings root. This lets the manifest
+    // point at sources outside of its own directory, like reference solutions.
+    fn cargo_bin_path(&self) -> String {
+        Path::new("../..")
+            .join(&self.path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+
+    // The chapter of an exercise is the directory it lives in
+    pub fn chapter(&self) -> String {
+        self.path
+            .parent()
+            .and_then(Path::file_name)
+            .map_or_else(|| EXERCISES_DIR.to_string(), |name| name.to_string_lossy().to_string())
+    }
+
+    // The reference solution of the exercise, which mirrors the exercise's
+    // path inside of the `solutions/` directory.
+    pub fn solution_path(&self) -> PathBuf {
+        let relative = self.path.strip_prefix(EXERCISES_DIR).unwrap_or(&self.path);
+        Path::new(SOLUTIONS_DIR).join(relative)
+    }
+
+    // The hidden file of the exercise, like the workload of a Bench exercise,
+    // which mirrors the exercise's path inside of the `hidden/` directory.
+    pub fn hidden_path(&self) -> PathBuf {
+        let relative = self.path.strip_prefix(EXERCISES_DIR).unwrap_or(&self.path);
+        Path::new(HIDDEN_DIR).join(relative)
+    }
+
     fn run(&self) -> Result<ExerciseOutput, ExerciseOutput> {
         let arg = match self.mode {
-            Mode::Test => "--show-output",
+            Mode::Test | Mode::Bench | Mode::Property => "--show-output",
             Mode::BuildScript => return Ok(ExerciseOutput {
                 stdout: "".to_string(),
                 stderr: "".to_string(),
@@ -213,40 +394,160 @@ path = "{}.rs""#,
             _ => "",
         };
         println!("pa={}", temp_file());
-        let cmd = Command::new(&temp_file())
-            .arg(arg)
-            .output()
-            .expect("Failed to run 'run' command");
+        let cmd = match self.mode {
+            Mode::Cargo => Command::new("cargo")
+                .args(["test", "--offline"])
+                .args(color_args())
+                .args(["--", "--show-output"])
+                .current_dir(&self.path)
+                .output(),
+            _ => self.run_binary(arg),
+        }
+        .expect("Failed to run 'run' command");
 
-        let output = ExerciseOutput {
+        let mut output = ExerciseOutput {
             stdout: String::from_utf8_lossy(&cmd.stdout).to_string(),
             stderr: String::from_utf8_lossy(&cmd.stderr).to_string(),
         };
 
-        if cmd.status.success() {
-            Ok(output)
+        if !cmd.status.success() {
+            return Err(output);
+        }
+        let output = match (&self.expected_stdout, self.mode) {
+            (Some(expected), Mode::Compile | Mode::Clippy) => match expected.check(&output.stdout) {
+                Ok(()) => Ok(output),
+                Err(mismatch) => {
+                    output.stderr.push_str(&mismatch);
+                    Err(output)
+                }
+            },
+            // Only an implementation passing its tests is worth measuring
+            (_, Mode::Bench) => match bench(self) {
+                Ok(report) => {
+                    output.stdout.push_str(&format!("\n{report}\n"));
+                    if report.passed() {
+                        Ok(output)
+                    } else {
+                        output
+                            .stdout
+                            .push_str(&format!("{}\n", tr!("exercise.too_slow")));
+                        Err(output)
+                    }
+                }
+                Err(e) => {
+                    output.stdout.push_str(&format!("\n{e}\n"));
+                    Err(output)
+                }
+            },
+            (_, Mode::Property) => match check_property(self) {
+                Ok(summary) => {
+                    output.stdout.push_str(&format!("\n{summary}\n"));
+                    Ok(output)
+                }
+                Err(failure) => {
+                    output.stdout.push_str(&format!("\n{failure}\n"));
+                    Err(output)
+                }
+            },
+            _ => Ok(output),
+        }?;
+        if self.check_ub {
+            self.run_ub_check(output)
         } else {
-            Err(output)
+            Ok(output)
+        }
+    }
+
+    // Only tests that pass are worth running again to look for undefined behavior
+    fn run_ub_check(&self, mut output: ExerciseOutput) -> Result<ExerciseOutput, ExerciseOutput> {
+        match check_ub(self) {
+            UbCheck::Passed(tool) => {
+                output
+                    .stdout
+                    .push_str(&format!("\n{}\n", tr!("exercise.no_ub", tool = tool)));
+                Ok(output)
+            }
+            UbCheck::Failed(tool, report) => {
+                output.stdout.push_str(&format!(
+                    "\n{}\n",
+                    tr!("exercise.ub_found", tool = tool, report = report)
+                ));
+                Err(output)
+            }
+            UbCheck::Skipped(reason) => {
+                output
+                    .stdout
+                    .push_str(&format!("\n{}\n", tr!("exercise.ub_skipped", reason = reason)));
+                Ok(output)
+            }
         }
     }
 
+    // Run the compiled binary, feeding it the exercise's standard input if it has one
+    fn run_binary(&self, arg: &str) -> io::Result<Output> {
+        let mut child = Command::new(temp_file())
+            .arg(arg)
+            .stdin(if self.stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let (Some(input), Some(mut child_stdin)) = (&self.stdin, child.stdin.take()) {
+            // The program may exit without reading all of its input
+            let _ = child_stdin.write_all(input.as_bytes());
+        }
+        child.wait_with_output()
+    }
+
+    // The Rust source files making up the exercise. That's the exercise file
+    // and the modules it declares, or every source file of the crate for
+    // Cargo exercises.
+    pub fn source_files(&self) -> Vec<PathBuf> {
+        if self.mode != Mode::Cargo {
+            let mut files = vec![self.path.clone()];
+            files.extend(module_files(&self.path));
+            return files;
+        }
+        let pattern = self.path.join("**").join("*.rs");
+        glob(&pattern.to_string_lossy())
+            .expect("We were unable to list the files of the exercise!")
+            .filter_map(Result::ok)
+            .filter(|path| {
+                !path
+                    .strip_prefix(&self.path)
+                    .unwrap_or(path)
+                    .components()
+                    .any(|c| c.as_os_str() == "target" || c.as_os_str() == "vendor")
+            })
+            .collect()
+    }
+
+    // Whether the given file belongs to this exercise
+    pub fn owns_file(&self, file: &Path) -> bool {
+        file.ancestors().any(|path| path.ends_with(&self.path))
+            || module_files(&self.path).iter().any(|module| file.ends_with(module))
+    }
+
     pub fn state(&self) -> State {
-        let mut source_file =
-            File::open(&self.path).expect("We were unable to open the exercise file!");
+        let re = Regex::new(I_AM_DONE_REGEX).unwrap();
 
-        let source = {
+        let pending_source = self.source_files().into_iter().find_map(|path| {
+            let mut source_file =
+                File::open(path).expect("We were unable to open the exercise file!");
             let mut s = String::new();
             source_file
                 .read_to_string(&mut s)
                 .expect("We were unable to read the exercise file!");
-            s
-        };
-
-        let re = Regex::new(I_AM_DONE_REGEX).unwrap();
+            re.is_match(&s).then_some(s)
+        });
 
-        if !re.is_match(&source) {
-            return State::Done;
-        }
+        let source = match pending_source {
+            Some(source) => source,
+            None => return State::Done,
+        };
 
         let matched_line_index = source
             .lines()
@@ -290,22 +591,27 @@ impl Display for Exercise {
 
 #[inline]
 fn clean() {
-    let _ignored = remove_file(&temp_file());
+    let _ignored = remove_file(temp_file());
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use std::path::Path;
 
     #[test]
     fn test_clean() {
-        File::create(&temp_file()).unwrap();
+        File::create(temp_file()).unwrap();
         let exercise = Exercise {
             name: String::from("example"),
             path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
             mode: Mode::Compile,
             hint: String::from(""),
+            expected_stdout: None,
+            stdin: None,
+            bench: None,
+            property: None,
+            check_ub: false,
+            depends_on: None,
         };
         let compiled = exercise.compile().unwrap();
         drop(compiled);
@@ -319,6 +625,12 @@ mod test {
             path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
             mode: Mode::Compile,
             hint: String::new(),
+            expected_stdout: None,
+            stdin: None,
+            bench: None,
+            property: None,
+            check_ub: false,
+            depends_on: None,
         };
 
         let state = exercise.state();
@@ -360,11 +672,82 @@ mod test {
             path: PathBuf::from("tests/fixture/state/finished_exercise.rs"),
             mode: Mode::Compile,
             hint: String::new(),
+            expected_stdout: None,
+            stdin: None,
+            bench: None,
+            property: None,
+            check_ub: false,
+            depends_on: None,
         };
 
         assert_eq!(exercise.state(), State::Done);
     }
 
+    #[test]
+    fn test_exercise_with_module() {
+        let exercise = Exercise {
+            name: "module_exercise".into(),
+            path: PathBuf::from("tests/fixture/state/module_exercise.rs"),
+            mode: Mode::Compile,
+            hint: String::new(),
+            expected_stdout: None,
+            stdin: None,
+            bench: None,
+            property: None,
+            check_ub: false,
+            depends_on: None,
+        };
+
+        let module = PathBuf::from("tests/fixture/state/shared_module.rs");
+        assert_eq!(exercise.source_files(), vec![exercise.path.clone(), module]);
+        assert!(matches!(exercise.state(), State::Pending(_)));
+        assert!(exercise.owns_file(Path::new(
+            "/home/rustlings/tests/fixture/state/shared_module.rs"
+        )));
+        assert!(!exercise.owns_file(Path::new(
+            "/home/rustlings/tests/fixture/state/pending_exercise.rs"
+        )));
+    }
+
+    #[test]
+    fn test_pending_cargo_exercise() {
+        let exercise = Exercise {
+            name: "crateSuccess".into(),
+            path: PathBuf::from("tests/fixture/cargo/crateSuccess"),
+            mode: Mode::Cargo,
+            hint: String::new(),
+            expected_stdout: None,
+            stdin: None,
+            bench: None,
+            property: None,
+            check_ub: false,
+            depends_on: None,
+        };
+
+        match exercise.state() {
+            State::Pending(context) => assert!(context
+                .iter()
+                .any(|line| line.important && line.line == "// I AM NOT DONE")),
+            State::Done => panic!("the marker in src/shapes.rs was not found"),
+        }
+        assert!(exercise.owns_file(Path::new(
+            "/home/rustlings/tests/fixture/cargo/crateSuccess/src/shapes.rs"
+        )));
+    }
+
+    #[test]
+    fn test_expected_output() {
+        let exact = ExpectedOutput::Exact("Hello World!".into());
+        assert_eq!(exact.check("Hello World!\r\n"), Ok(()));
+        assert!(exact.check("Hello!\n").is_err());
+
+        let regex = ExpectedOutput::Regex {
+            regex: r"^Hello \w+!$".into(),
+        };
+        assert_eq!(regex.check("Hello Ferris!"), Ok(()));
+        assert!(regex.check("Goodbye Ferris!").is_err());
+    }
+
     #[test]
     fn test_exercise_with_output() {
         let exercise = Exercise {
@@ -372,6 +755,12 @@ mod test {
             path: PathBuf::from("tests/fixture/success/testSuccess.rs"),
             mode: Mode::Test,
             hint: String::new(),
+            expected_stdout: None,
+            stdin: None,
+            bench: None,
+            property: None,
+            check_ub: false,
+            depends_on: None,
         };
         let out = exercise.compile().unwrap().run().unwrap();
         assert!(out.stdout.contains("THIS TEST TOO SHALL PASS"));
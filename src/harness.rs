@@ -0,0 +1,61 @@
+use crate::exercise::RUSTC_EDITION_ARGS;
+use std::fs::{self, remove_file};
+use std::path::Path;
+use std::process::Command;
+
+// Generated sources and binaries, which are removed once they're dropped
+#[derive(Default)]
+pub struct GeneratedFiles(Vec<String>);
+
+impl GeneratedFiles {
+    // Remove the given file once these files are dropped
+    pub fn push(&mut self, file: String) {
+        self.0.push(file);
+    }
+}
+
+impl Drop for GeneratedFiles {
+    fn drop(&mut self) {
+        for file in &self.0 {
+            let _ignored = remove_file(file);
+        }
+    }
+}
+
+// Append a hidden file and a harness providing `main` to the given exercise
+// source, and compile the result into `binary` with the given rustc flags.
+// Exercises built like this have their private items visible to the hidden
+// code, because it all ends up in the same crate root.
+pub fn build(
+    source_path: &Path,
+    hidden: &str,
+    harness: &str,
+    binary: &str,
+    rustc_args: &[&str],
+    files: &mut GeneratedFiles,
+) -> Result<(), String> {
+    let source = fs::read_to_string(source_path)
+        .map_err(|e| tr!("harness.unreadable", path = source_path.display(), error = e))?;
+    let source_file = format!("{binary}.rs");
+    files.push(source_file.clone());
+    files.push(binary.to_string());
+    fs::write(&source_file, format!("{source}\n{hidden}\n{harness}"))
+        .map_err(|e| tr!("harness.unwritable", error = e))?;
+
+    let output = Command::new("rustc")
+        .args([&source_file, "-o", binary])
+        .args(RUSTC_EDITION_ARGS)
+        .args(["--cap-lints", "allow"])
+        .args(rustc_args)
+        .output()
+        .map_err(|e| tr!("harness.rustc_failed", error = e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(tr!(
+            "harness.compile_failed",
+            path = source_path.display(),
+            output = String::from_utf8_lossy(&output.stderr)
+        ))
+    }
+}
@@ -0,0 +1,881 @@
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::{self, Write};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+// The languages rustlings speaks
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Lang {
+    En,
+    Zh,
+}
+
+impl Lang {
+    // The code of the language, as used for the hints in info.toml
+    pub fn code(&self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::Zh => "zh",
+        }
+    }
+
+    // The language of a POSIX locale like `zh_CN.UTF-8`
+    fn from_locale(locale: &str) -> Option<Lang> {
+        let language = locale.split(['_', '-', '.', '@']).next()?;
+        match language.to_lowercase().as_str() {
+            "en" => Some(Lang::En),
+            "zh" => Some(Lang::Zh),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Lang {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Lang::from_locale(s).ok_or_else(|| format!("unknown language '{s}', expected one of: en, zh"))
+    }
+}
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+// Pick the language of the output: the one given with `--lang`, or the one
+// of the locale, or English. This has to happen before info.toml is read,
+// because hints are read in the selected language.
+pub fn init(lang: Option<Lang>) {
+    let lang = lang
+        .or_else(|| {
+            ["LC_ALL", "LC_MESSAGES", "LANG"]
+                .iter()
+                .filter_map(|var| env::var(var).ok())
+                .find(|locale| !locale.is_empty())
+                .and_then(|locale| Lang::from_locale(&locale))
+        })
+        .unwrap_or(Lang::En);
+    let _ = LANG.set(lang);
+}
+
+pub fn lang() -> Lang {
+    LANG.get().copied().unwrap_or(Lang::En)
+}
+
+// Look up a message of the catalog in the selected language
+pub fn message(key: &str) -> &'static str {
+    match MESSAGES.iter().find(|(k, _, _)| *k == key) {
+        Some((_, en, zh)) => match lang() {
+            Lang::En => en,
+            Lang::Zh => zh,
+        },
+        None => {
+            debug_assert!(false, "the message {key} is missing from the catalog");
+            ""
+        }
+    }
+}
+
+// A message of the catalog in the selected language, with its `{name}`
+// placeholders filled in, like `tr!("run.not_found", name = name)`
+macro_rules! tr {
+    ($key:literal) => {
+        $crate::i18n::message($key)
+    };
+    ($key:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::fill(
+            $crate::i18n::message($key),
+            &[$((stringify!($name), &$value as &dyn std::fmt::Display)),+],
+        )
+    };
+}
+
+// Fill in the `{name}` placeholders of a message in a single pass, so that
+// values which contain something like `{path}` themselves are kept as they are
+pub fn fill(message: &str, values: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut filled = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let value = rest.find('}').and_then(|end| {
+            let (_, value) = values.iter().find(|(name, _)| *name == &rest[1..end])?;
+            Some((end, value))
+        });
+        match value {
+            Some((end, value)) => {
+                let _ = write!(filled, "{value}");
+                rest = &rest[end + 1..];
+            }
+            None => {
+                filled.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
+
+// A text from info.toml, which is either a plain string or given per
+// language like `hint.en = "..."` and `hint.zh = "..."`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Localized {
+    Text(String),
+    PerLang(BTreeMap<String, String>),
+}
+
+// Deserialize a localized text in the selected language, falling back to
+// English and then to any language there is
+pub fn localized<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    Ok(match Localized::deserialize(deserializer)? {
+        Localized::Text(text) => text,
+        Localized::PerLang(texts) => texts
+            .get(lang().code())
+            .or_else(|| texts.get(Lang::En.code()))
+            .or_else(|| texts.values().next())
+            .cloned()
+            .unwrap_or_default(),
+    })
+}
+
+// Every message in English and in Chinese
+const MESSAGES: &[(&str, &str, &str)] = &[
+    (
+        "welcome",
+        "       welcome to...",
+        "       欢迎来到...",
+    ),
+    (
+        "main.wrong_dir",
+        "{exe} must be run from the rustlings directory\nTry `cd rustlings/`!",
+        "{exe} 必须在 rustlings 目录下运行\n试试 `cd rustlings/`！",
+    ),
+    (
+        "main.no_rustc",
+        "We cannot find `rustc`.\nTry running `rustc --version` to diagnose your problem.\nFor instructions on how to install Rust, check the README.",
+        "找不到 `rustc`。\n试试运行 `rustc --version` 来诊断问题。\n安装 Rust 的方法请参阅 README。",
+    ),
+    (
+        "main.curriculum_error",
+        "The prerequisites in info.toml are invalid: {error}",
+        "info.toml 中的前置条件无效：{error}",
+    ),
+    ("list.name", "Name", "名称"),
+    ("list.path", "Path", "路径"),
+    ("list.status", "Status", "状态"),
+    ("list.done", "Done", "已完成"),
+    ("list.pending", "Pending", "未完成"),
+    (
+        "list.progress",
+        "Progress: You completed {done} / {total} exercises ({percentage} %).",
+        "进度：你完成了 {done} / {total} 道练习（{percentage} %）。",
+    ),
+    (
+        "find.all_done",
+        "{party} Congratulations! You have done all the exercises!\n{end} There are no more exercises to do next!",
+        "{party} 恭喜！你已经完成了所有练习！\n{end} 没有下一道练习了！",
+    ),
+    (
+        "find.also_unlocked",
+        "Other exercises you can do next: {exercises}",
+        "接下来也可以做这些练习：{exercises}",
+    ),
+    (
+        "find.not_found",
+        "No exercise found for '{name}'!",
+        "找不到练习 '{name}'！",
+    ),
+    (
+        "new.error",
+        "Error: Could not create the exercise: {error}",
+        "错误：无法创建练习：{error}",
+    ),
+    (
+        "stats.export_error",
+        "Error: Could not export the statistics: {error}",
+        "错误：无法导出统计数据：{error}",
+    ),
+    (
+        "stats.exported",
+        "Exported the statistics to {path}",
+        "统计数据已导出到 {path}",
+    ),
+    (
+        "stats.empty",
+        "No attempts recorded yet. Attempts are recorded while you use `rustlings watch` or `rustlings run`.",
+        "还没有记录任何尝试。使用 `rustlings watch` 或 `rustlings run` 时会记录你的尝试。",
+    ),
+    ("stats.attempts", "Attempts", "尝试次数"),
+    ("stats.compile_errors", "Compile errors", "编译错误"),
+    ("stats.hints", "Hints", "提示"),
+    ("stats.time_spent", "Time spent", "耗时"),
+    ("stats.solved", "Solved", "已解决"),
+    ("stats.hardest_chapters", "Hardest chapters:", "最难的章节："),
+    (
+        "stats.chapter",
+        "  {chapter}: {attempts} attempts per exercise, {time} spent",
+        "  {chapter}：每道练习平均尝试 {attempts} 次，耗时 {time}",
+    ),
+    (
+        "stats.common_errors",
+        "Most common compiler errors:",
+        "最常见的编译错误：",
+    ),
+    (
+        "stats.error_code",
+        "  {code}: {count} times, see `rustc --explain {code}`",
+        "  {code}：{count} 次，参见 `rustc --explain {code}`",
+    ),
+    ("stats.total_time", "Total time spent: {time}", "总耗时：{time}"),
+    (
+        "report.read_error",
+        "Error: Could not read the results: {error}",
+        "错误：无法读取结果：{error}",
+    ),
+    (
+        "report.write_error",
+        "Error: Could not write the report: {error}",
+        "错误：无法生成报告：{error}",
+    ),
+    (
+        "report.write_file_error",
+        "Error: Could not write the report to {path}: {error}",
+        "错误：无法将报告写入 {path}：{error}",
+    ),
+    (
+        "report.read_file_error",
+        "Error: Could not read {file}: {error}",
+        "错误：无法读取 {file}：{error}",
+    ),
+    (
+        "report.not_authentic",
+        "Error: {file} is not authentic: {error}",
+        "错误：{file} 不可信：{error}",
+    ),
+    (
+        "report.signed",
+        "The results of {student} were signed by rustlings {version}.",
+        "{student} 的结果由 rustlings {version} 签名。",
+    ),
+    ("report.unknown_student", "an unknown student", "未知学生"),
+    ("report.unknown_version", "of an unknown version", "（未知版本）"),
+    (
+        "report.changed_files",
+        "These files changed since the exercises were graded:",
+        "以下文件在批改之后被修改过：",
+    ),
+    (
+        "report.unchanged_files",
+        "The exercises on disk are the ones that were graded.",
+        "磁盘上的练习就是被批改的那些。",
+    ),
+    (
+        "report.missing_files",
+        "These files could not be read when the exercises were graded:",
+        "以下文件在批改时无法读取：",
+    ),
+    (
+        "cicv.missing_file",
+        "Could not read {path}, it is recorded as missing",
+        "无法读取 {path}，已将其记录为缺失",
+    ),
+    ("cicv.passed", "{name} passed", "{name}执行成功"),
+    ("cicv.failed", "{name} failed", "{name}执行失败"),
+    ("cicv.total", "Total exercises: {total}", "总的题目数: {total}"),
+    (
+        "cicv.passed_so_far",
+        "Exercises passed so far: {passed}",
+        "当前做正确的题目数: {passed}",
+    ),
+    (
+        "cicv.exercise_time",
+        "Time spent grading the exercise: {seconds} s",
+        "当前修改试卷耗时: {seconds} s",
+    ),
+    (
+        "cicv.finished",
+        "=============================== Grading finished in {seconds} s ==================================",
+        "===============================试卷批改完成,总耗时: {seconds} s; ==================================",
+    ),
+    (
+        "cicv.unsigned",
+        "{var} is not set, so the results are not signed",
+        "未设置 {var}，因此结果没有签名",
+    ),
+    (
+        "cicv.sign_error",
+        "Error: Could not sign the results: {error}",
+        "错误：无法为结果签名：{error}",
+    ),
+    (
+        "lsp.no_sysroot",
+        "Couldn't find toolchain path, do you have `rustc` installed?",
+        "找不到工具链路径，你安装 `rustc` 了吗？",
+    ),
+    (
+        "lsp.parse_error",
+        "Couldn't parse rustlings exercises files",
+        "无法解析 rustlings 的练习文件",
+    ),
+    (
+        "lsp.no_exercises",
+        "Failed find any exercises, make sure you're in the `rustlings` folder",
+        "找不到任何练习，请确认你在 `rustlings` 文件夹中",
+    ),
+    (
+        "lsp.write_error",
+        "Failed to write rust-project.json to disk for rust-analyzer",
+        "无法为 rust-analyzer 写入 rust-project.json",
+    ),
+    (
+        "lsp.done",
+        "Successfully generated rust-project.json\nrust-analyzer will now parse exercises, restart your language server or editor",
+        "已成功生成 rust-project.json\nrust-analyzer 现在会解析练习，请重启你的语言服务器或编辑器",
+    ),
+    (
+        "lsp_server.marker",
+        "Remove this line once {exercise} works, to move on to the next exercise",
+        "{exercise} 完成后删除这一行，就可以进入下一道练习",
+    ),
+    ("lsp_server.run_title", "Run {exercise}", "运行 {exercise}"),
+    ("lsp_server.reset_title", "Reset {exercise}", "重置 {exercise}"),
+    ("lsp_server.hint_title", "Hint for {exercise}", "{exercise} 的提示"),
+    ("lsp_server.passed", "{exercise} passed", "{exercise} 通过了"),
+    ("lsp_server.failed", "{exercise} failed, see the output for details", "{exercise} 没有通过，详情见输出"),
+    ("lsp_server.reset", "{exercise} was reset", "{exercise} 已重置"),
+    ("lsp_server.reset_failed", "Could not reset {exercise}", "无法重置 {exercise}"),
+    ("lsp_server.error", "The language server stopped: {error}", "语言服务器已停止：{error}"),
+    (
+        "serve.listening",
+        "Serving your progress on http://{address}\n  /exercises         every exercise and whether it is done\n  /exercises/<name>  the state of one exercise\n  /result            the latest result of watch or run\n  /events            a stream of watch events\nPress Ctrl+C to stop.",
+        "正在 http://{address} 上提供你的进度\n  /exercises         所有练习及其完成情况\n  /exercises/<name>  单道练习的状态\n  /result            watch 或 run 的最新结果\n  /events            监视模式事件流\n按 Ctrl+C 停止。",
+    ),
+    (
+        "serve.error",
+        "Could not serve on {address}: {error}",
+        "无法在 {address} 上提供服务：{error}",
+    ),
+    (
+        "watch.error",
+        "Error: Could not watch your progress. Error message was {error}.\nMost likely you've run out of disk space or your 'inotify limit' has been reached.",
+        "错误：无法监视你的进度。错误信息：{error}。\n很可能是磁盘空间不足，或者达到了 'inotify limit'。",
+    ),
+    (
+        "watch.all_done",
+        "{emoji} All exercises completed! {emoji}",
+        "{emoji} 所有练习都完成了！{emoji}",
+    ),
+    (
+        "watch.unfinished",
+        "We hope you're enjoying learning about Rust!\nIf you want to continue working on the exercises at a later point, you can simply run `rustlings watch` again",
+        "希望你享受学习 Rust 的过程！\n如果想稍后继续做练习，再次运行 `rustlings watch` 即可",
+    ),
+    (
+        "watch.unlocked",
+        "You can also work on: {exercises}. Type `pick <name>` to switch to one of them.",
+        "你也可以做这些练习：{exercises}。输入 `pick <名称>` 切换过去。",
+    ),
+    (
+        "watch.pick_from",
+        "Exercises you can work on: {exercises}",
+        "你可以做的练习：{exercises}",
+    ),
+    (
+        "watch.locked",
+        "{exercise} is locked until you are done with: {missing}",
+        "{exercise} 尚未解锁，需要先完成：{missing}",
+    ),
+    ("watch.event_error", "watch error: {error}", "监视出错：{error}"),
+    (
+        "shell.welcome",
+        "Welcome to watch mode! You can type 'help' to get an overview of the commands you can use here.",
+        "欢迎进入监视模式！输入 'help' 查看可以使用的命令。",
+    ),
+    ("shell.bye", "Bye!", "再见！"),
+    (
+        "shell.help",
+        "Commands available to you in watch mode:\n  hint   - prints the current exercise's hint\n  pick   - switches to another unlocked exercise, like `pick iterators1`\n  clear  - clears the screen\n  quit   - quits watch mode\n  !<cmd> - executes a command, like `!rustc --explain E0381`\n  help   - displays this help message\n\nWatch mode automatically re-evaluates the current exercise\nwhen you edit a file's contents.",
+        "监视模式下可用的命令：\n  hint   - 显示当前练习的提示\n  pick   - 切换到另一道已解锁的练习，比如 `pick iterators1`\n  clear  - 清屏\n  quit   - 退出监视模式\n  !<cmd> - 执行一条命令，比如 `!rustc --explain E0381`\n  help   - 显示这条帮助信息\n\n当你修改文件内容时，监视模式会自动重新检查当前练习。",
+    ),
+    ("shell.no_command", "no command provided", "没有给出命令"),
+    (
+        "shell.command_failed",
+        "failed to execute command `{command}`: {error}",
+        "无法执行命令 `{command}`：{error}",
+    ),
+    ("shell.unknown", "unknown command: {input}", "未知命令：{input}"),
+    ("shell.read_error", "error reading command: {error}", "读取命令出错：{error}"),
+    (
+        "verify.progress",
+        "Progress:",
+        "进度：",
+    ),
+    ("verify.compiling", "Compiling {exercise}...", "正在编译 {exercise}..."),
+    ("verify.running", "Running {exercise}...", "正在运行 {exercise}..."),
+    ("verify.testing", "Testing {exercise}...", "正在测试 {exercise}..."),
+    (
+        "verify.ran_with_errors",
+        "Ran {exercise} with errors",
+        "运行 {exercise} 时出错",
+    ),
+    (
+        "verify.testing_failed",
+        "Testing of {exercise} failed! Please try again. Here's the output:",
+        "{exercise} 的测试失败了！请再试一次。输出如下：",
+    ),
+    (
+        "verify.compiling_failed",
+        "Compiling of {exercise} failed! Please try again. Here's the output:",
+        "{exercise} 编译失败了！请再试一次。输出如下：",
+    ),
+    ("verify.ran", "Successfully ran {exercise}!", "成功运行了 {exercise}！"),
+    ("verify.tested", "Successfully tested {exercise}!", "成功测试了 {exercise}！"),
+    (
+        "verify.compiled",
+        "Successfully compiled {exercise}!",
+        "成功编译了 {exercise}！",
+    ),
+    (
+        "verify.benchmarked",
+        "Successfully benchmarked {exercise}!",
+        "成功对 {exercise} 进行了性能测试！",
+    ),
+    ("verify.msg.compile", "The code is compiling!", "代码编译通过了！"),
+    (
+        "verify.msg.test",
+        "The code is compiling, and the tests pass!",
+        "代码编译通过，测试也通过了！",
+    ),
+    (
+        "verify.msg.clippy",
+        "The code is compiling, and 📎 Clippy 📎 is happy!",
+        "代码编译通过，📎 Clippy 📎 也很满意！",
+    ),
+    (
+        "verify.msg.clippy_no_emoji",
+        "The code is compiling, and Clippy is happy!",
+        "代码编译通过，Clippy 也很满意！",
+    ),
+    ("verify.msg.buildscript", "Build script works!", "构建脚本可以工作了！"),
+    (
+        "verify.msg.cargo",
+        "The crate is compiling, and the tests pass!",
+        "crate 编译通过，测试也通过了！",
+    ),
+    (
+        "verify.msg.bench",
+        "The code is compiling, the tests pass, and it is fast enough!",
+        "代码编译通过，测试通过，而且足够快！",
+    ),
+    (
+        "verify.msg.property",
+        "The code is compiling, the tests pass, and so does the property!",
+        "代码编译通过，测试通过，性质检查也通过了！",
+    ),
+    ("verify.output", "Output:", "输出："),
+    ("verify.hints", "Hints:", "提示："),
+    (
+        "verify.keep_working",
+        "You can keep working on this exercise,\nor jump into the next one by removing the {marker} comment:",
+        "你可以继续完善这道练习，\n或者删除 {marker} 注释进入下一道练习：",
+    ),
+    (
+        "run.compilation_failed",
+        "Compilation of {exercise} failed!, Compiler error message:\n",
+        "{exercise} 编译失败！编译器错误信息：\n",
+    ),
+    ("run.ran", "Successfully ran {exercise}", "成功运行了 {exercise}"),
+    (
+        "exercise.unexpected_output",
+        "The program did not print what was expected.\nExpected:\n{expected}\nActual:\n{actual}",
+        "程序的输出与预期不符。\n预期：\n{expected}\n实际：\n{actual}",
+    ),
+    (
+        "exercise.expected_regex",
+        "output matching the regex {regex}",
+        "匹配正则表达式 {regex} 的输出",
+    ),
+    (
+        "exercise.expected_file",
+        "the contents of {file}",
+        "{file} 的内容",
+    ),
+    (
+        "exercise.too_slow",
+        "Your solution is too slow compared to the reference solution.",
+        "与参考答案相比，你的解法太慢了。",
+    ),
+    (
+        "exercise.no_ub",
+        "No undefined behavior found by {tool}.",
+        "{tool} 没有发现未定义行为。",
+    ),
+    (
+        "exercise.ub_found",
+        "{tool} found undefined behavior in your solution:\n{report}",
+        "{tool} 在你的解法中发现了未定义行为：\n{report}",
+    ),
+    (
+        "exercise.ub_skipped",
+        "Skipped the undefined behavior check: {reason}.",
+        "跳过了未定义行为检查：{reason}。",
+    ),
+    (
+        "exercise.invalid_regex",
+        "The expected output regex is invalid: {error}",
+        "预期输出的正则表达式无效：{error}",
+    ),
+    (
+        "exercise.unreadable_expected",
+        "Could not read the expected output {file}: {error}",
+        "无法读取预期输出 {file}：{error}",
+    ),
+    (
+        "main.unknown_format",
+        "unknown format '{format}', expected one of: {expected}",
+        "未知的格式 '{format}'，可选的格式有：{expected}",
+    ),
+    (
+        "curriculum.unknown_prerequisite",
+        "{of} depends on '{name}', which is neither an exercise nor a chapter",
+        "{of} 依赖于 '{name}'，但它既不是练习也不是章节",
+    ),
+    (
+        "curriculum.cycle",
+        "The prerequisites of these exercises form a cycle: {exercises}",
+        "这些练习的前置条件构成了环：{exercises}",
+    ),
+    (
+        "curriculum.empty_chapter",
+        "The chapter '{chapter}' has no exercises",
+        "章节 '{chapter}' 中没有练习",
+    ),
+    (
+        "harness.unreadable",
+        "Could not read {path}: {error}",
+        "无法读取 {path}：{error}",
+    ),
+    (
+        "harness.unwritable",
+        "Could not write the generated harness: {error}",
+        "无法写入生成的测试框架：{error}",
+    ),
+    ("harness.rustc_failed", "Failed to run rustc: {error}", "运行 rustc 失败：{error}"),
+    (
+        "harness.compile_failed",
+        "Compiling {path} together with its hidden code failed:\n{output}",
+        "将 {path} 与其隐藏代码一起编译失败：\n{output}",
+    ),
+    (
+        "bench.results",
+        "Benchmark results over {iterations} iterations:",
+        "{iterations} 次迭代的性能测试结果：",
+    ),
+    ("bench.median", "median", "中位数"),
+    ("bench.min", "min", "最小值"),
+    ("bench.max", "max", "最大值"),
+    ("bench.yours", "your solution", "你的解法"),
+    ("bench.reference", "reference", "参考答案"),
+    (
+        "bench.budget",
+        "budget: {budget} ({max_slowdown}x the reference median)",
+        "时间上限：{budget}（参考答案中位数的 {max_slowdown} 倍）",
+    ),
+    (
+        "bench.no_workload",
+        "Could not read the benchmark workload {path}: {error}",
+        "无法读取性能测试的工作负载 {path}：{error}",
+    ),
+    (
+        "bench.no_solution",
+        "There is no reference solution at {path} to benchmark against",
+        "{path} 处没有可用于对比性能的参考答案",
+    ),
+    ("bench.run_failed", "Failed to run the benchmark: {error}", "运行性能测试失败：{error}"),
+    ("bench.failed", "The benchmark failed:\n{output}", "性能测试失败：\n{output}"),
+    (
+        "bench.no_timings",
+        "The benchmark did not report any timings",
+        "性能测试没有报告任何耗时",
+    ),
+    (
+        "property.unreadable",
+        "Could not read the property {path}: {error}",
+        "无法读取性质 {path}：{error}",
+    ),
+    (
+        "property.run_failed",
+        "Failed to run the property check: {error}",
+        "运行性质检查失败：{error}",
+    ),
+    (
+        "property.held",
+        "Property held for {cases} random inputs with seed {seed}.",
+        "性质在种子 {seed} 生成的 {cases} 个随机输入上都成立。",
+    ),
+    (
+        "property.failed",
+        "Property failed on case {case} of {cases} with seed {seed}.",
+        "性质在种子 {seed} 生成的 {cases} 个输入中的第 {case} 个上不成立。",
+    ),
+    ("property.minimized", "Minimized failing input: {input}", "最小化后的失败输入：{input}"),
+    (
+        "property.not_reproduced",
+        "the failure could not be reproduced",
+        "无法重现这次失败",
+    ),
+    ("ub.no_miri", "Miri is not installed, run `rustup +nightly component add miri` to enable it", "没有安装 Miri，运行 `rustup +nightly component add miri` 来启用它"),
+    (
+        "ub.no_tool",
+        "neither Miri nor a nightly toolchain for AddressSanitizer is installed, run `rustup +nightly component add miri` to enable it",
+        "既没有安装 Miri，也没有可以使用 AddressSanitizer 的 nightly 工具链，运行 `rustup +nightly component add miri` 来启用它",
+    ),
+    (
+        "ub.no_asan",
+        "AddressSanitizer is not supported by the installed nightly toolchain",
+        "安装的 nightly 工具链不支持 AddressSanitizer",
+    ),
+    (
+        "ub.exercise_not_found",
+        "Could not find the exercise: {error}",
+        "找不到练习：{error}",
+    ),
+    (
+        "ub.crate_not_written",
+        "Could not write the Miri crate: {error}",
+        "无法写入 Miri 使用的 crate：{error}",
+    ),
+    ("ub.run_failed", "Failed to run {tool}: {error}", "运行 {tool} 失败：{error}"),
+    (
+        "signature.no_key",
+        "Set {var} to the key the results were signed with",
+        "请把 {var} 设置为签名结果时使用的密钥",
+    ),
+    ("signature.unsigned", "The results are not signed", "结果没有签名"),
+    ("signature.malformed", "The signature is malformed", "签名格式错误"),
+    (
+        "signature.mismatch",
+        "The signature does not match, the results were changed after they were signed",
+        "签名不匹配，结果在签名之后被修改过",
+    ),
+    (
+        "report.not_a_result",
+        "Skipping {path}, which isn't a result file: {error}",
+        "跳过 {path}，它不是结果文件：{error}",
+    ),
+    (
+        "report.duplicate_result",
+        "Found more than one result for {student}, using the one in {path}",
+        "{student} 有不止一个结果，使用 {path} 中的结果",
+    ),
+    (
+        "report.no_results",
+        "There are no result files in {dir}",
+        "{dir} 中没有结果文件",
+    ),
+    (
+        "new.invalid_chapter",
+        "the chapter '{chapter}' may only contain ASCII letters, digits and underscores",
+        "章节 '{chapter}' 只能包含 ASCII 字母、数字和下划线",
+    ),
+    (
+        "new.invalid_name",
+        "the name '{name}' may only contain ASCII letters, digits and underscores",
+        "名称 '{name}' 只能包含 ASCII 字母、数字和下划线",
+    ),
+    (
+        "new.exercise_exists",
+        "an exercise named '{name}' already exists",
+        "名为 '{name}' 的练习已经存在",
+    ),
+    (
+        "new.wrong_chapter",
+        "{mode} exercises must live in the '{chapter}' chapter",
+        "{mode} 练习必须放在 '{chapter}' 章节中",
+    ),
+    ("new.file_exists", "{path} already exists", "{path} 已经存在"),
+    ("new.created", "Created {path}", "创建了 {path}"),
+    ("new.registered", "Registered {name} in {file}", "已在 {file} 中登记了 {name}"),
+    ("selftest.checking", "Checking {exercise}...", "正在检查 {exercise}..."),
+    (
+        "selftest.ok",
+        "{exercise} fails as shipped and its solution passes",
+        "{exercise} 的原始版本不通过，参考答案通过",
+    ),
+    (
+        "selftest.no_solution",
+        "{exercise} fails as shipped, but there is no solution at {path}",
+        "{exercise} 的原始版本不通过，但 {path} 处没有参考答案",
+    ),
+    (
+        "selftest.already_solved",
+        "{exercise} already passes as shipped",
+        "{exercise} 的原始版本已经通过了",
+    ),
+    (
+        "selftest.solution_fails",
+        "the solution of {exercise} at {path} does not pass",
+        "{exercise} 在 {path} 处的参考答案没有通过",
+    ),
+    (
+        "selftest.summary",
+        "Self-tested {total} exercises: {ok} ok, {failed} failed.",
+        "自检了 {total} 道练习：{ok} 道正常，{failed} 道失败。",
+    ),
+    ("serve.only_get", "Only GET requests are served", "只处理 GET 请求"),
+    ("serve.malformed", "The request is malformed", "请求格式错误"),
+    ("serve.not_found", "There is nothing at {path}", "{path} 处什么也没有"),
+    ("lsp.toolchain", "Determined toolchain: {toolchain}", "确定的工具链：{toolchain}"),
+    (
+        "default_out",
+        r#"Thanks for installing Rustlings!
+
+Is this your first time? Don't worry, Rustlings was made for beginners! We are
+going to teach you a lot of things about Rust, but before we can get
+started, here's a couple of notes about how Rustlings operates:
+
+1. The central concept behind Rustlings is that you solve exercises. These
+   exercises usually have some sort of syntax error in them, which will cause
+   them to fail compilation or testing. Sometimes there's a logic error instead
+   of a syntax error. No matter what error, it's your job to find it and fix it!
+   You'll know when you fixed it because then, the exercise will compile and
+   Rustlings will be able to move on to the next exercise.
+2. If you run Rustlings in watch mode (which we recommend), it'll automatically
+   start with the first exercise. Don't get confused by an error message popping
+   up as soon as you run Rustlings! This is part of the exercise that you're
+   supposed to solve, so open the exercise file in an editor and start your
+   detective work!
+3. If you're stuck on an exercise, there is a helpful hint you can view by typing
+   'hint' (in watch mode), or running `rustlings hint exercise_name`.
+4. If an exercise doesn't make sense to you, feel free to open an issue on GitHub!
+   (https://github.com/rust-lang/rustlings/issues/new). We look at every issue,
+   and sometimes, other learners do too so you can help each other out!
+5. If you want to use `rust-analyzer` with exercises, which provides features like
+   autocompletion, run the command `rustlings lsp`.
+
+Got all that? Great! To get started, run `rustlings watch` in order to get the first
+exercise. Make sure to have your editor open!"#,
+        r#"感谢安装 Rustlings！
+
+第一次使用吗？别担心，Rustlings 就是为初学者准备的！我们会教你很多
+关于 Rust 的知识，不过在开始之前，先了解一下 Rustlings 是如何运作的：
+
+1. Rustlings 的核心就是做练习。这些练习里通常有某种语法错误，导致它们
+   无法通过编译或测试。有时候是逻辑错误而不是语法错误。不管是什么错误，
+   你的任务就是找到并修复它！修好之后练习就能编译通过，Rustlings 也会
+   进入下一道练习。
+2. 如果你以监视模式运行 Rustlings（推荐这样做），它会自动从第一道练习
+   开始。运行 Rustlings 后马上出现错误信息不要慌！这正是你要解决的练习，
+   用编辑器打开练习文件，开始你的侦探工作吧！
+3. 如果卡在某道练习上，可以查看提示：在监视模式下输入 'hint'，或者运行
+   `rustlings hint 练习名称`。
+4. 如果某道练习让你摸不着头脑，欢迎在 GitHub 上提 issue！
+   (https://github.com/rust-lang/rustlings/issues/new)。我们会查看每一个
+   issue，有时其他学习者也会看，大家可以互相帮助！
+5. 如果想在练习中使用提供自动补全等功能的 `rust-analyzer`，请运行
+   `rustlings lsp` 命令。
+
+都明白了吗？太好了！运行 `rustlings watch` 开始第一道练习吧。记得打开你的编辑器！"#,
+    ),
+    (
+        "finish",
+        r#"We hope you enjoyed learning about the various aspects of Rust!
+If you noticed any issues, please don't hesitate to report them to our repo.
+You can also contribute your own exercises to help the greater community!
+
+Before reporting an issue or contributing, please read our guidelines:
+https://github.com/rust-lang/rustlings/blob/main/CONTRIBUTING.md"#,
+        r#"希望你享受了学习 Rust 各个方面的过程！
+如果发现任何问题，请随时在我们的仓库中报告。
+你也可以贡献自己的练习来帮助更多的人！
+
+在报告问题或做出贡献之前，请阅读我们的指南：
+https://github.com/rust-lang/rustlings/blob/main/CONTRIBUTING.md"#,
+    ),
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use regex::Regex;
+
+    #[test]
+    fn test_lang_from_locale() {
+        assert_eq!(Lang::from_locale("zh_CN.UTF-8"), Some(Lang::Zh));
+        assert_eq!(Lang::from_locale("en_US.UTF-8"), Some(Lang::En));
+        assert_eq!(Lang::from_locale("C"), None);
+        assert_eq!("zh".parse(), Ok(Lang::Zh));
+        assert_eq!("zh-CN".parse(), Ok(Lang::Zh));
+        assert!("fr".parse::<Lang>().is_err());
+    }
+
+    #[test]
+    fn test_catalog_is_consistent() {
+        let placeholder = Regex::new(r"\{[a-z_]+\}").unwrap();
+        let placeholders = |message: &str| {
+            let mut found: Vec<String> = placeholder
+                .find_iter(message)
+                .map(|m| m.as_str().to_string())
+                .collect();
+            found.sort();
+            found.dedup();
+            found
+        };
+        for (i, (key, en, zh)) in MESSAGES.iter().enumerate() {
+            assert!(
+                MESSAGES[i + 1..].iter().all(|(other, _, _)| other != key),
+                "{key} is in the catalog twice"
+            );
+            assert!(!en.is_empty() && !zh.is_empty(), "{key} is not translated");
+            assert_eq!(placeholders(en), placeholders(zh), "{key} has different placeholders");
+        }
+    }
+
+    #[test]
+    fn test_used_keys_are_in_catalog() {
+        let used = Regex::new(r#"tr!\(\s*"([a-z_.]+)""#).unwrap();
+        let src = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        for entry in std::fs::read_dir(src).unwrap() {
+            let path = entry.unwrap().path();
+            // The macro's own docs use a made up key
+            if path.extension().is_none_or(|e| e != "rs") || path.ends_with("i18n.rs") {
+                continue;
+            }
+            let source = std::fs::read_to_string(&path).unwrap();
+            for key in used.captures_iter(&source) {
+                assert!(
+                    MESSAGES.iter().any(|(k, _, _)| *k == &key[1]),
+                    "{} uses {}, which is not in the catalog",
+                    path.display(),
+                    &key[1]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_fill_placeholders() {
+        let message = tr!("report.write_file_error", path = "{error}.csv", error = "denied");
+        assert_eq!(message, "Error: Could not write the report to {error}.csv: denied");
+        assert_eq!(fill("{a} {b} {a}", &[("a", &1)]), "1 {b} 1");
+    }
+
+    #[test]
+    fn test_localized_hint() {
+        #[derive(Deserialize)]
+        struct Exercise {
+            #[serde(deserialize_with = "localized")]
+            hint: String,
+        }
+        let plain: Exercise = toml::from_str(r#"hint = "Read the book""#).unwrap();
+        assert_eq!(plain.hint, "Read the book");
+        // Tests don't pick a language, so this is the English one
+        let per_lang: Exercise =
+            toml::from_str("hint.zh = \"读读书\"\nhint.en = \"Read the book\"").unwrap();
+        assert_eq!(per_lang.hint, "Read the book");
+        let zh_only: Exercise = toml::from_str("hint.zh = \"读读书\"").unwrap();
+        assert_eq!(zh_only.hint, "读读书");
+    }
+}
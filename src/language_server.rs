@@ -0,0 +1,448 @@
+use crate::exercise::{Exercise, I_AM_DONE_REGEX};
+use crate::stats::{record, start_recording, Outcome};
+use crate::VERSION;
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::env;
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::process::Command;
+
+// The commands offered as code lenses and code actions
+const RUN_COMMAND: &str = "rustlings.run";
+const RESET_COMMAND: &str = "rustlings.reset";
+const HINT_COMMAND: &str = "rustlings.hint";
+
+// JSON-RPC error codes
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+// LSP enumerations
+const FULL_SYNC: u8 = 1;
+const SEVERITY_INFORMATION: u8 = 3;
+const MESSAGE_ERROR: u8 = 1;
+const MESSAGE_INFO: u8 = 3;
+const MESSAGE_LOG: u8 = 4;
+
+type RequestError = (i64, String);
+
+// A language server for the exercises, speaking LSP over stdin and stdout.
+// Running and resetting exercises is left to `rustlings run` and
+// `rustlings reset` in a child process, so that nothing but LSP messages
+// ever ends up on stdout.
+struct Server<'a, W: Write> {
+    exercises: &'a [Exercise],
+    // The text of the open documents, by URI
+    documents: HashMap<String, String>,
+    marker: Regex,
+    out: W,
+    shutdown: bool,
+}
+
+// Serve LSP over stdio until the client exits, returning the exit code
+pub fn serve_stdio(exercises: &[Exercise]) -> Result<i32, Box<dyn Error>> {
+    let mut server = Server::new(exercises, io::stdout());
+    let mut stdin = io::stdin().lock();
+    while let Some(message) = read_message(&mut stdin)? {
+        if let Some(code) = server.handle(&message)? {
+            return Ok(code);
+        }
+    }
+    Ok(1)
+}
+
+// Read a message framed by a `Content-Length` header, or `None` at the end of the input
+fn read_message(input: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let length = length
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing Content-Length"))?;
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn write_message(out: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+    write!(out, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    out.flush()
+}
+
+// The path of a `file://` URI, undoing its percent-encoding
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    // `file:///C:/...` on Windows
+    let path = match path.as_bytes() {
+        [b'/', _, b':', ..] => &path[1..],
+        _ => path,
+    };
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut rest = path.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let escaped = tail
+            .get(..2)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (byte, escaped) {
+            (b'%', Some(escaped)) => {
+                bytes.push(escaped);
+                rest = &tail[2..];
+            }
+            _ => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8(bytes).ok().map(PathBuf::from)
+}
+
+// LSP counts columns in UTF-16 code units
+fn utf16_len(text: &str) -> usize {
+    text.encode_utf16().count()
+}
+
+impl<'a, W: Write> Server<'a, W> {
+    fn new(exercises: &'a [Exercise], out: W) -> Self {
+        Server {
+            exercises,
+            documents: HashMap::new(),
+            marker: Regex::new(I_AM_DONE_REGEX).unwrap(),
+            out,
+            shutdown: false,
+        }
+    }
+
+    fn exercise_of(&self, uri: &str) -> Option<&'a Exercise> {
+        let path = uri_to_path(uri)?;
+        self.exercises.iter().find(|e| e.owns_file(&path))
+    }
+
+    fn exercise_named(&self, params: &Value) -> Result<&'a Exercise, RequestError> {
+        let name = params["arguments"][0].as_str().unwrap_or_default();
+        self.exercises
+            .iter()
+            .find(|e| e.name == name)
+            .ok_or_else(|| (INVALID_PARAMS, tr!("find.not_found", name = name)))
+    }
+
+    fn notify(&mut self, method: &str, params: Value) -> io::Result<()> {
+        write_message(
+            &mut self.out,
+            &json!({ "jsonrpc": "2.0", "method": method, "params": params }),
+        )
+    }
+
+    fn show_message(&mut self, kind: u8, message: &str) -> io::Result<()> {
+        self.notify("window/showMessage", json!({ "type": kind, "message": message }))
+    }
+
+    // Handle a message, returning the exit code once the client asks to exit
+    fn handle(&mut self, message: &Value) -> io::Result<Option<i32>> {
+        let Some(method) = message["method"].as_str() else {
+            // A response, but the server never sends requests
+            return Ok(None);
+        };
+        let params = &message["params"];
+        let Some(id) = message.get("id") else {
+            return self.notification(method, params);
+        };
+        let response = match self.request(method, params)? {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, error)) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": error },
+            }),
+        };
+        write_message(&mut self.out, &response)?;
+        Ok(None)
+    }
+
+    fn request(&mut self, method: &str, params: &Value) -> io::Result<Result<Value, RequestError>> {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        Ok(Ok(match method {
+            "initialize" => json!({
+                "capabilities": {
+                    "textDocumentSync": { "openClose": true, "change": FULL_SYNC },
+                    "hoverProvider": true,
+                    "codeLensProvider": {},
+                    "codeActionProvider": true,
+                    "executeCommandProvider": {
+                        "commands": [RUN_COMMAND, RESET_COMMAND, HINT_COMMAND],
+                    },
+                },
+                "serverInfo": { "name": "rustlings", "version": VERSION },
+            }),
+            "shutdown" => {
+                self.shutdown = true;
+                Value::Null
+            }
+            "textDocument/hover" => self.hover(uri, params),
+            "textDocument/codeLens" => match self.exercise_of(uri) {
+                Some(exercise) => {
+                    let range = json!({
+                        "start": { "line": 0, "character": 0 },
+                        "end": { "line": 0, "character": 0 },
+                    });
+                    let lenses: Vec<Value> = commands(exercise)
+                        .into_iter()
+                        .map(|command| json!({ "range": range, "command": command }))
+                        .collect();
+                    json!(lenses)
+                }
+                None => json!([]),
+            },
+            "textDocument/codeAction" => match self.exercise_of(uri) {
+                Some(exercise) => json!(commands(exercise)),
+                None => json!([]),
+            },
+            "workspace/executeCommand" => {
+                let exercise = match self.exercise_named(params) {
+                    Ok(exercise) => exercise,
+                    Err(error) => return Ok(Err(error)),
+                };
+                match params["command"].as_str() {
+                    Some(RUN_COMMAND) => self.run(exercise)?,
+                    Some(RESET_COMMAND) => self.reset(exercise)?,
+                    Some(HINT_COMMAND) => {
+                        start_recording();
+                        record(&exercise.name, Outcome::Hint);
+                        self.show_message(MESSAGE_INFO, &exercise.hint)?;
+                        Value::Null
+                    }
+                    command => {
+                        let command = command.unwrap_or_default();
+                        return Ok(Err((INVALID_PARAMS, format!("unknown command '{command}'"))));
+                    }
+                }
+            }
+            _ => return Ok(Err((METHOD_NOT_FOUND, format!("unknown method '{method}'")))),
+        }))
+    }
+
+    fn notification(&mut self, method: &str, params: &Value) -> io::Result<Option<i32>> {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default().to_string();
+        match method {
+            "exit" => return Ok(Some(if self.shutdown { 0 } else { 1 })),
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.documents.insert(uri.clone(), text.to_string());
+                self.publish_diagnostics(&uri)?;
+            }
+            "textDocument/didChange" => {
+                // With full sync, the last change is the whole document
+                let changes = params["contentChanges"].as_array();
+                if let Some(text) = changes.and_then(|c| c.last()).and_then(|c| c["text"].as_str()) {
+                    self.documents.insert(uri.clone(), text.to_string());
+                    self.publish_diagnostics(&uri)?;
+                }
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                self.publish_diagnostics(&uri)?;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    // The lines of the document with the `I AM NOT DONE` marker
+    fn marker_lines(&self, uri: &str) -> Vec<(usize, &str)> {
+        self.documents
+            .get(uri)
+            .map(|text| {
+                text.lines()
+                    .enumerate()
+                    .filter(|(_, line)| self.marker.is_match(line))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn marker_range(line: usize, text: &str) -> Value {
+        let indent = text.len() - text.trim_start().len();
+        json!({
+            "start": { "line": line, "character": utf16_len(&text[..indent]) },
+            "end": { "line": line, "character": utf16_len(text) },
+        })
+    }
+
+    fn publish_diagnostics(&mut self, uri: &str) -> io::Result<()> {
+        let Some(exercise) = self.exercise_of(uri) else {
+            return Ok(());
+        };
+        let diagnostics: Vec<Value> = self
+            .marker_lines(uri)
+            .into_iter()
+            .map(|(line, text)| {
+                json!({
+                    "range": Self::marker_range(line, text),
+                    "severity": SEVERITY_INFORMATION,
+                    "source": "rustlings",
+                    "message": tr!("lsp_server.marker", exercise = exercise.name),
+                })
+            })
+            .collect();
+        self.notify(
+            "textDocument/publishDiagnostics",
+            json!({ "uri": uri, "diagnostics": diagnostics }),
+        )
+    }
+
+    // The hint of the exercise, when hovering over its marker
+    fn hover(&self, uri: &str, params: &Value) -> Value {
+        let line = params["position"]["line"].as_u64().unwrap_or(u64::MAX) as usize;
+        let (Some(exercise), Some((line, text))) = (
+            self.exercise_of(uri),
+            self.marker_lines(uri).into_iter().find(|(l, _)| *l == line),
+        ) else {
+            return Value::Null;
+        };
+        json!({
+            "contents": {
+                "kind": "markdown",
+                "value": format!("**{}**\n\n{}", tr!("lsp_server.hint_title", exercise = exercise.name), exercise.hint),
+            },
+            "range": Self::marker_range(line, text),
+        })
+    }
+
+    // Run the exercise with `rustlings run`, logging its output and
+    // reporting whether it passed
+    fn run(&mut self, exercise: &Exercise) -> io::Result<Value> {
+        let output = Command::new(env::current_exe()?)
+            .args(["run", &exercise.name])
+            .output()?;
+        let log = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        self.notify("window/logMessage", json!({ "type": MESSAGE_LOG, "message": log }))?;
+        let passed = output.status.success();
+        if passed {
+            self.show_message(MESSAGE_INFO, &tr!("lsp_server.passed", exercise = exercise.name))?;
+        } else {
+            self.show_message(MESSAGE_ERROR, &tr!("lsp_server.failed", exercise = exercise.name))?;
+        }
+        let status = json!({ "exercise": exercise.name, "passed": passed });
+        self.notify("rustlings/status", status.clone())?;
+        Ok(status)
+    }
+
+    fn reset(&mut self, exercise: &Exercise) -> io::Result<Value> {
+        let status = Command::new(env::current_exe()?)
+            .args(["reset", &exercise.name])
+            .output()?
+            .status;
+        if status.success() {
+            self.show_message(MESSAGE_INFO, &tr!("lsp_server.reset", exercise = exercise.name))?;
+        } else {
+            self.show_message(
+                MESSAGE_ERROR,
+                &tr!("lsp_server.reset_failed", exercise = exercise.name),
+            )?;
+        }
+        Ok(Value::Null)
+    }
+}
+
+// The commands for an exercise, as code lenses or code actions
+fn commands(exercise: &Exercise) -> Vec<Value> {
+    [
+        (RUN_COMMAND, tr!("lsp_server.run_title", exercise = exercise.name)),
+        (HINT_COMMAND, tr!("lsp_server.hint_title", exercise = exercise.name)),
+        (RESET_COMMAND, tr!("lsp_server.reset_title", exercise = exercise.name)),
+    ]
+    .into_iter()
+    .map(|(command, title)| json!({ "title": title, "command": command, "arguments": [exercise.name] }))
+    .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::Mode;
+
+    fn exercise() -> Exercise {
+        Exercise {
+            name: "intro1".to_string(),
+            path: PathBuf::from("exercises/intro/intro1.rs"),
+            mode: Mode::Compile,
+            hint: "Remove the marker".to_string(),
+            expected_stdout: None,
+            stdin: None,
+            bench: None,
+            property: None,
+            check_ub: false,
+            depends_on: None,
+        }
+    }
+
+    fn messages(mut output: &[u8]) -> Vec<Value> {
+        let mut messages = vec![];
+        while let Some(message) = read_message(&mut output).unwrap() {
+            messages.push(message);
+        }
+        messages
+    }
+
+    #[test]
+    fn test_uri_to_path() {
+        assert_eq!(
+            uri_to_path("file:///home/ferris/my%20rustlings/exercises/intro/intro1.rs"),
+            Some(PathBuf::from("/home/ferris/my rustlings/exercises/intro/intro1.rs"))
+        );
+        assert_eq!(uri_to_path("file:///C:/rustlings/a.rs"), Some(PathBuf::from("C:/rustlings/a.rs")));
+        assert_eq!(uri_to_path("untitled:1"), None);
+    }
+
+    #[test]
+    fn test_marker_diagnostics_and_hover() {
+        let exercises = [exercise()];
+        let mut server = Server::new(&exercises, Vec::new());
+        let uri = "file:///rustlings/exercises/intro/intro1.rs";
+        let open = json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": { "uri": uri, "text": "fn main() {}\n  // I AM NOT DONE\n" } },
+        });
+        let hover = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "textDocument/hover",
+            "params": { "textDocument": { "uri": uri }, "position": { "line": 1, "character": 4 } },
+        });
+        assert_eq!(server.handle(&open).unwrap(), None);
+        assert_eq!(server.handle(&hover).unwrap(), None);
+        let exit = json!({ "jsonrpc": "2.0", "method": "exit" });
+        assert_eq!(server.handle(&exit).unwrap(), Some(1));
+
+        let messages = messages(&server.out);
+        let diagnostics = &messages[0]["params"]["diagnostics"];
+        assert_eq!(messages[0]["method"], "textDocument/publishDiagnostics");
+        assert_eq!(diagnostics[0]["range"]["start"], json!({ "line": 1, "character": 2 }));
+        assert_eq!(messages[1]["id"], 1);
+        let hint = messages[1]["result"]["contents"]["value"].as_str().unwrap();
+        assert!(hint.ends_with("Remove the marker"), "{hint}");
+    }
+}
@@ -1,15 +1,25 @@
-use crate::exercise::{Exercise, ExerciseList};
+use crate::curriculum::{Curriculum, GraphFormat};
+use crate::exercise::{Exercise, ExerciseList, Mode};
+use crate::i18n::Lang;
+use crate::new::{new_exercise, Scaffold};
+use crate::output::clear_screen;
 use crate::project::RustAnalyzerProject;
+use crate::report::{load_results, merge, Format};
 use crate::run::{reset, run};
+use crate::selftest::selftest;
+use crate::serve::serve;
+use crate::signature::{changed_files, hash_sources, missing_files, sign, verify_signature, KEY_VAR};
+use crate::stats::{export, load_history, record, start_recording, summarize, Outcome, HISTORY_PATH};
 use crate::verify::verify;
 use argh::FromArgs;
-use console::Emoji;
 use notify::DebouncedEvent;
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::ffi::OsStr;
 use std::fs;
 use std::io::{self, prelude::*};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::path::Path;
 use std::process::{Command, Stdio};
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -19,12 +29,27 @@ use std::thread;
 use std::time::Duration;
 use std::time::{UNIX_EPOCH, SystemTime};
 
+#[macro_use]
+mod i18n;
 #[macro_use]
 mod ui;
 
+mod bench;
+mod curriculum;
 mod exercise;
+mod harness;
+mod language_server;
+mod new;
+mod output;
 mod project;
+mod property;
+mod report;
 mod run;
+mod selftest;
+mod serve;
+mod signature;
+mod stats;
+mod ub;
 mod verify;
 
 // In sync with crate version
@@ -39,6 +64,12 @@ struct Args {
     /// show the executable version
     #[argh(switch, short = 'v')]
     version: bool,
+    /// the language of the output: en or zh, taken from the locale by default
+    #[argh(option)]
+    lang: Option<Lang>,
+    /// print plain lines of text for screen readers, without spinners, emoji or screen clears
+    #[argh(switch)]
+    screen_reader: bool,
     #[argh(subcommand)]
     nested: Option<Subcommands>,
 }
@@ -53,12 +84,23 @@ enum Subcommands {
     Hint(HintArgs),
     List(ListArgs),
     Lsp(LspArgs),
+    LspServer(LspServerArgs),
+    New(NewArgs),
+    Selftest(SelftestArgs),
+    Stats(StatsArgs),
+    Report(ReportArgs),
+    Graph(GraphArgs),
+    Serve(ServeArgs),
     CicvVerify(CicvVerifyArgs)
 }
 
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "cicvverify", description = "cicvverify")]
-struct CicvVerifyArgs {}
+struct CicvVerifyArgs {
+    #[argh(option)]
+    /// the name of the student the results belong to, `git config user.name` by default
+    student: Option<String>,
+}
 
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "verify")]
@@ -106,6 +148,119 @@ struct HintArgs {
 /// Enable rust-analyzer for exercises
 struct LspArgs {}
 
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "lsp-server")]
+/// Runs a language server over stdio with hints, status and run/reset actions
+struct LspServerArgs {}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "new")]
+/// Scaffolds a new exercise and registers it in info.toml
+struct NewArgs {
+    #[argh(positional)]
+    /// the chapter (directory under `exercises/`) of the exercise
+    chapter: String,
+    #[argh(positional)]
+    /// the name of the exercise
+    name: String,
+    #[argh(option, short = 'm', default = "Mode::Test")]
+    /// the mode of the exercise: test, compile, clippy or buildscript
+    mode: Mode,
+    #[argh(switch, short = 's')]
+    /// also create a reference solution under `solutions/`
+    solution: bool,
+    #[argh(switch, short = 't')]
+    /// also create a hidden test file under `hidden/`
+    hidden_test: bool,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "selftest")]
+/// Checks that exercises fail as shipped and that their solutions pass
+struct SelftestArgs {
+    #[argh(positional)]
+    /// the names of the exercises to check, all of them if none are given
+    names: Vec<String>,
+    #[argh(switch)]
+    /// treat exercises without a reference solution as failures
+    require_solutions: bool,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "stats")]
+/// Summarizes your attempts recorded by `watch` and `run`
+struct StatsArgs {
+    #[argh(option)]
+    /// write the statistics to a file, as CSV if it ends in `.csv` and as JSON otherwise
+    export: Option<String>,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "graph")]
+/// Draws the exercises and their prerequisites as a graph
+struct GraphArgs {
+    #[argh(option, short = 'f', default = "GraphFormat::Dot")]
+    /// the format of the graph: dot or mermaid
+    format: GraphFormat,
+    #[argh(option, short = 'o')]
+    /// the file to write the graph to, instead of printing it
+    output: Option<String>,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "serve")]
+/// Serves your progress as JSON and a stream of watch events over HTTP
+struct ServeArgs {
+    #[argh(option, short = 'p', default = "8080")]
+    /// the port to listen on
+    port: u16,
+    #[argh(option, default = "IpAddr::V4(Ipv4Addr::LOCALHOST)")]
+    /// the address to listen on, only this machine by default
+    address: IpAddr,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "report")]
+/// Works with the results written by `cicvverify`
+struct ReportArgs {
+    #[argh(subcommand)]
+    nested: ReportSubcommands,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand)]
+enum ReportSubcommands {
+    Merge(ReportMergeArgs),
+    Verify(ReportVerifyArgs),
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "verify")]
+/// Checks the signature of a result file with the key from RUSTLINGS_RESULT_KEY
+struct ReportVerifyArgs {
+    #[argh(positional, default = "String::from(\".github/result/check_result.json\")")]
+    /// the result file to check
+    file: String,
+    #[argh(switch)]
+    /// also check that the exercises on disk are the ones that were graded
+    check_files: bool,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "merge")]
+/// Merges the results of a whole class into pass rates and scores
+struct ReportMergeArgs {
+    #[argh(positional)]
+    /// the directory containing the result files of every student
+    dir: String,
+    #[argh(option, short = 'f', default = "Format::Csv")]
+    /// the format of the report: csv, json or html
+    format: Format,
+    #[argh(option, short = 'o')]
+    /// the file to write the report to, instead of printing it
+    output: Option<String>,
+}
+
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "list")]
 /// Lists the exercises available in Rustlings
@@ -132,7 +287,16 @@ struct ListArgs {
 pub struct ExerciseCheckList {
     pub exercises: Vec<ExerciseResult>,
     pub user_name: Option<String>,
-    pub statistics: ExerciseStatistics
+    pub statistics: ExerciseStatistics,
+    // The version of rustlings that graded the exercises
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    // The SHA-256 hash of info.toml and of every exercise source that was graded
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hashes: BTreeMap<String, String>,
+    // The HMAC-SHA256 of everything above, with the key from `RUSTLINGS_RESULT_KEY`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -158,38 +322,51 @@ async fn main() {
         std::process::exit(0);
     }
 
+    i18n::init(args.lang);
+    output::init(args.screen_reader);
+
     if args.nested.is_none() {
-        println!("\n{WELCOME}\n");
+        println!("\n{}\n{WELCOME}\n", tr!("welcome"));
     }
 
     if !Path::new("info.toml").exists() {
         println!(
-            "{} must be run from the rustlings directory",
-            std::env::current_exe().unwrap().to_str().unwrap()
+            "{}",
+            tr!(
+                "main.wrong_dir",
+                exe = std::env::current_exe().unwrap().to_str().unwrap()
+            )
         );
-        println!("Try `cd rustlings/`!");
         std::process::exit(1);
     }
 
     if !rustc_exists() {
-        println!("We cannot find `rustc`.");
-        println!("Try running `rustc --version` to diagnose your problem.");
-        println!("For instructions on how to install Rust, check the README.");
+        println!("{}", tr!("main.no_rustc"));
         std::process::exit(1);
     }
 
     let toml_str = &fs::read_to_string("info.toml").unwrap();
-    let exercises = toml::from_str::<ExerciseList>(toml_str).unwrap().exercises;
+    let exercise_list = toml::from_str::<ExerciseList>(toml_str).unwrap();
+    let exercises = exercise_list.exercises;
+    let curriculum = Curriculum::new(&exercises, &exercise_list.chapters).unwrap_or_else(|e| {
+        println!("{}", tr!("main.curriculum_error", error = e));
+        std::process::exit(1);
+    });
     let verbose = args.nocapture;
 
     let command = args.nested.unwrap_or_else(|| {
-        println!("{DEFAULT_OUT}\n");
+        println!("{}\n", tr!("default_out"));
         std::process::exit(0);
     });
     match command {
         Subcommands::List(subargs) => {
             if !subargs.paths && !subargs.names {
-                println!("{:<17}\t{:<46}\t{:<7}", "Name", "Path", "Status");
+                println!(
+                    "{:<17}\t{:<46}\t{:<7}",
+                    tr!("list.name"),
+                    tr!("list.path"),
+                    tr!("list.status")
+                );
             }
             let mut exercises_done: u16 = 0;
             let filters = subargs.filter.clone().unwrap_or_default().to_lowercase();
@@ -198,12 +375,12 @@ async fn main() {
                 let filter_cond = filters
                     .split(',')
                     .filter(|f| !f.trim().is_empty())
-                    .any(|f| e.name.contains(&f) || fname.contains(&f));
+                    .any(|f| e.name.contains(f) || fname.contains(f));
                 let status = if e.looks_done() {
                     exercises_done += 1;
-                    "Done"
+                    tr!("list.done")
                 } else {
-                    "Pending"
+                    tr!("list.pending")
                 };
                 let solve_cond = {
                     (e.looks_done() && subargs.solved)
@@ -235,53 +412,193 @@ async fn main() {
             });
             let percentage_progress = exercises_done as f32 / exercises.len() as f32 * 100.0;
             println!(
-                "Progress: You completed {} / {} exercises ({:.1} %).",
-                exercises_done,
-                exercises.len(),
-                percentage_progress
+                "{}",
+                tr!(
+                    "list.progress",
+                    done = exercises_done,
+                    total = exercises.len(),
+                    percentage = format!("{percentage_progress:.1}")
+                )
             );
             std::process::exit(0);
         }
 
         Subcommands::Run(subargs) => {
-            let exercise = find_exercise(&subargs.name, &exercises);
+            let exercise = find_exercise(&subargs.name, &curriculum);
+            start_recording();
             run(exercise, verbose).unwrap_or_else(|_| std::process::exit(1));
         }
 
         Subcommands::Reset(subargs) => {
-            let exercise = find_exercise(&subargs.name, &exercises);
+            let exercise = find_exercise(&subargs.name, &curriculum);
 
             reset(exercise).unwrap_or_else(|_| std::process::exit(1));
         }
 
         Subcommands::Hint(subargs) => {
-            let exercise = find_exercise(&subargs.name, &exercises);
+            let exercise = find_exercise(&subargs.name, &curriculum);
+            start_recording();
+            record(&exercise.name, Outcome::Hint);
 
             println!("{}", exercise.hint);
         }
 
+        Subcommands::Stats(subargs) => {
+            let summary = summarize(&load_history(Path::new(HISTORY_PATH)), &exercises);
+            println!("{summary}");
+            if let Some(path) = subargs.export {
+                export(&summary, Path::new(&path)).unwrap_or_else(|e| {
+                    println!("{}", tr!("stats.export_error", error = e));
+                    std::process::exit(1);
+                });
+                println!("{}", tr!("stats.exported", path = path));
+            }
+        }
+
+        Subcommands::New(subargs) => {
+            let scaffold = Scaffold {
+                chapter: subargs.chapter,
+                name: subargs.name,
+                mode: subargs.mode,
+                solution: subargs.solution,
+                hidden_test: subargs.hidden_test,
+            };
+            new_exercise(&scaffold, &exercises).unwrap_or_else(|e| {
+                println!("{}", tr!("new.error", error = e));
+                std::process::exit(1);
+            });
+        }
+
+        Subcommands::Selftest(subargs) => {
+            let selected: Vec<&Exercise> = if subargs.names.is_empty() {
+                exercises.iter().collect()
+            } else {
+                subargs
+                    .names
+                    .iter()
+                    .map(|name| find_exercise(name, &curriculum))
+                    .collect()
+            };
+            selftest(&selected, subargs.require_solutions)
+                .unwrap_or_else(|_| std::process::exit(1));
+        }
+
+        Subcommands::Graph(subargs) => {
+            let graph = curriculum.render(subargs.format);
+            match subargs.output {
+                Some(path) => fs::write(&path, graph).unwrap_or_else(|e| {
+                    println!("{}", tr!("report.write_file_error", path = path, error = e));
+                    std::process::exit(1);
+                }),
+                None => print!("{graph}"),
+            }
+        }
+
+        Subcommands::Serve(subargs) => {
+            let address = SocketAddr::new(subargs.address, subargs.port);
+            if let Err(e) = serve(exercises.clone(), exercise_list.chapters.clone(), address).await {
+                println!("{}", tr!("serve.error", address = address, error = e));
+                std::process::exit(1);
+            }
+        }
+
+        Subcommands::Report(subargs) => match subargs.nested {
+            ReportSubcommands::Merge(merge_args) => {
+                let results = load_results(Path::new(&merge_args.dir)).unwrap_or_else(|e| {
+                    println!("{}", tr!("report.read_error", error = e));
+                    std::process::exit(1);
+                });
+                let report = merge(&results, &exercises)
+                    .render(merge_args.format)
+                    .unwrap_or_else(|e| {
+                        println!("{}", tr!("report.write_error", error = e));
+                        std::process::exit(1);
+                    });
+                match merge_args.output {
+                    Some(path) => fs::write(&path, report).unwrap_or_else(|e| {
+                        println!("{}", tr!("report.write_file_error", path = path, error = e));
+                        std::process::exit(1);
+                    }),
+                    None => print!("{report}"),
+                }
+            }
+            ReportSubcommands::Verify(verify_args) => {
+                let results: ExerciseCheckList = fs::read_to_string(&verify_args.file)
+                    .map_err(|e| e.to_string())
+                    .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+                    .unwrap_or_else(|e| {
+                        println!("{}", tr!("report.read_file_error", file = verify_args.file, error = e));
+                        std::process::exit(1);
+                    });
+                if let Err(e) = verify_signature(&results) {
+                    println!("{}", tr!("report.not_authentic", file = verify_args.file, error = e));
+                    std::process::exit(1);
+                }
+                println!(
+                    "{}",
+                    tr!(
+                        "report.signed",
+                        student = results
+                            .user_name
+                            .as_deref()
+                            .unwrap_or(tr!("report.unknown_student")),
+                        version = results
+                            .version
+                            .as_deref()
+                            .unwrap_or(tr!("report.unknown_version"))
+                    )
+                );
+                let missing = missing_files(&results.hashes);
+                if !missing.is_empty() {
+                    println!("{}", tr!("report.missing_files"));
+                    for file in missing {
+                        println!("  {file}");
+                    }
+                }
+                if verify_args.check_files {
+                    let changed = changed_files(&results);
+                    if !changed.is_empty() {
+                        println!("{}", tr!("report.changed_files"));
+                        for file in changed {
+                            println!("  {file}");
+                        }
+                        std::process::exit(1);
+                    }
+                    println!("{}", tr!("report.unchanged_files"));
+                }
+            }
+        },
+
         Subcommands::Verify(_subargs) => {
-            verify(&exercises, (0, exercises.len()), verbose, false)
+            verify(curriculum.in_order(), (0, exercises.len()), verbose, false, false)
                 .unwrap_or_else(|_| std::process::exit(1));
         }
 
-        Subcommands::CicvVerify(_subargs) => {
+        Subcommands::CicvVerify(subargs) => {
             // let toml_str = &fs::read_to_string("info.toml").unwrap();
             // exercises = toml::from_str::<ExerciseList>(toml_str).unwrap().exercises;
             let now_start = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+            // Hashed before grading, so that the hashes are of what was graded
+            let hashes = hash_sources(&exercises);
+            for file in missing_files(&hashes) {
+                println!("{}", tr!("cicv.missing_file", path = file));
+            }
             let rights = Arc::new(Mutex::new(0));
             let alls = exercises.len();
 
             let exercise_check_list =  Arc::new(Mutex::new(
                 ExerciseCheckList {
                     exercises: vec![], 
-                    user_name:  None, 
+                    user_name: subargs.student.or_else(git_user_name),
                     statistics: ExerciseStatistics { 
                         total_exercations: alls, 
                         total_succeeds: 0, 
                         total_failures: 0, 
                         total_time: 0, 
-                    }
+                    },
+                    version: Some(VERSION.to_string()),
+                    hashes,
+                    signature: None,
                 }
             ));
 
@@ -291,28 +608,28 @@ async fn main() {
                 let inner_exercise = exercise;
                 let c_mutex = Arc::clone(&rights);
                 let exercise_check_list_ref = Arc::clone(&exercise_check_list);
-                let _verbose = verbose.clone();
+                let _verbose = verbose;
                 let t = tokio::task::spawn( async move {
                     match run(&inner_exercise, true) {
                     // match verify(vec![&inner_exercise], (0, 1), true, true) {
                         Ok(_) => {
                             *c_mutex.lock().unwrap() += 1;
-                            println!("{}执行成功", inner_exercise.name);
-                            println!("总的题目数: {}", alls);
-                            println!("当前做正确的题目数: {}", *c_mutex.lock().unwrap());
+                            println!("{}", tr!("cicv.passed", name = inner_exercise.name));
+                            println!("{}", tr!("cicv.total", total = alls));
+                            println!("{}", tr!("cicv.passed_so_far", passed = *c_mutex.lock().unwrap()));
                             let now_end = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-                            println!("当前修改试卷耗时: {} s", now_end - now_start);
+                            println!("{}", tr!("cicv.exercise_time", seconds = now_end - now_start));
                             exercise_check_list_ref.lock().unwrap().exercises.push(ExerciseResult{ 
                                 name: inner_exercise.name, result: true,
                             });
                             exercise_check_list_ref.lock().unwrap().statistics.total_succeeds += 1;
                         },
                         Err(_) => {
-                            println!("{}执行失败", inner_exercise.name);
-                            println!("总的题目数: {}", alls);
-                            println!("当前做正确的题目数: {}", *c_mutex.lock().unwrap());
+                            println!("{}", tr!("cicv.failed", name = inner_exercise.name));
+                            println!("{}", tr!("cicv.total", total = alls));
+                            println!("{}", tr!("cicv.passed_so_far", passed = *c_mutex.lock().unwrap()));
                             let now_end = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-                            println!("当前修改试卷耗时: {} s", now_end - now_start);
+                            println!("{}", tr!("cicv.exercise_time", seconds = now_end - now_start));
                             exercise_check_list_ref.lock().unwrap().exercises.push(ExerciseResult{ 
                                 name: inner_exercise.name, result: false,
                             });
@@ -325,9 +642,17 @@ async fn main() {
             for task in tasks { task.await.unwrap(); }
             let now_end = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
             let total_time = now_end - now_start;
-            println!("===============================试卷批改完成,总耗时: {} s; ==================================", total_time);
+            println!("{}", tr!("cicv.finished", seconds = total_time));
             let exercise_check_list_ref = Arc::clone(&exercise_check_list);
             exercise_check_list_ref.lock().unwrap().statistics.total_time = total_time as u32;
+            match sign(&mut exercise_check_list.lock().unwrap()) {
+                Ok(true) => {}
+                Ok(false) => println!("{}", tr!("cicv.unsigned", var = KEY_VAR)),
+                Err(e) => {
+                    println!("{}", tr!("cicv.sign_error", error = e));
+                    std::process::exit(1);
+                }
+            }
             let serialized = serde_json::to_string_pretty(&*exercise_check_list.lock().unwrap()).unwrap();
             fs::write(".github/result/check_result.json", serialized).unwrap();
         },
@@ -336,107 +661,117 @@ async fn main() {
             let mut project = RustAnalyzerProject::new();
             project
                 .get_sysroot_src()
-                .expect("Couldn't find toolchain path, do you have `rustc` installed?");
+                .expect(tr!("lsp.no_sysroot"));
             project
                 .exercises_to_json()
-                .expect("Couldn't parse rustlings exercises files");
+                .expect(tr!("lsp.parse_error"));
 
             if project.crates.is_empty() {
-                println!("Failed find any exercises, make sure you're in the `rustlings` folder");
+                println!("{}", tr!("lsp.no_exercises"));
             } else if project.write_to_disk().is_err() {
-                println!("Failed to write rust-project.json to disk for rust-analyzer");
+                println!("{}", tr!("lsp.write_error"));
             } else {
-                println!("Successfully generated rust-project.json");
-                println!("rust-analyzer will now parse exercises, restart your language server or editor")
+                println!("{}", tr!("lsp.done"));
             }
         }
 
-        Subcommands::Watch(_subargs) => match watch(&exercises, verbose, _subargs.success_hints) {
+        Subcommands::LspServer(_subargs) => match language_server::serve_stdio(&exercises) {
+            Ok(code) => std::process::exit(code),
             Err(e) => {
-                println!(
-                    "Error: Could not watch your progress. Error message was {:?}.",
-                    e
-                );
-                println!("Most likely you've run out of disk space or your 'inotify limit' has been reached.");
+                eprintln!("{}", tr!("lsp_server.error", error = e));
+                std::process::exit(1);
+            }
+        },
+
+        Subcommands::Watch(_subargs) => match watch(&curriculum, verbose, _subargs.success_hints) {
+            Err(e) => {
+                println!("{}", tr!("watch.error", error = format!("{e:?}")));
                 std::process::exit(1);
             }
             Ok(WatchStatus::Finished) => {
-                println!(
-                    "{emoji} All exercises completed! {emoji}",
-                    emoji = Emoji("🎉", "★")
-                );
-                println!("\n{FENISH_LINE}\n");
+                println!("{}", tr!("watch.all_done", emoji = output::emoji("🎉", "★")));
+                println!("\n{FENISH_LINE}\n\n{}\n", tr!("finish"));
             }
             Ok(WatchStatus::Unfinished) => {
-                println!("We hope you're enjoying learning about Rust!");
-                println!("If you want to continue working on the exercises at a later point, you can simply run `rustlings watch` again");
+                println!("{}", tr!("watch.unfinished"));
             }
         },
     }
 }
 
 fn spawn_watch_shell(
-    failed_exercise_hint: &Arc<Mutex<Option<String>>>,
+    failed_exercise_hint: &Arc<Mutex<Option<(String, String)>>>,
+    picked: Arc<Mutex<Option<String>>>,
     should_quit: Arc<AtomicBool>,
 ) {
     let failed_exercise_hint = Arc::clone(failed_exercise_hint);
-    println!("Welcome to watch mode! You can type 'help' to get an overview of the commands you can use here.");
+    println!("{}", tr!("shell.welcome"));
     thread::spawn(move || loop {
         let mut input = String::new();
         match io::stdin().read_line(&mut input) {
             Ok(_) => {
                 let input = input.trim();
                 if input == "hint" {
-                    if let Some(hint) = &*failed_exercise_hint.lock().unwrap() {
+                    if let Some((name, hint)) = &*failed_exercise_hint.lock().unwrap() {
+                        record(name, Outcome::Hint);
                         println!("{hint}");
                     }
+                } else if let Some(name) = input
+                    .strip_prefix("pick")
+                    .filter(|rest| rest.is_empty() || rest.starts_with(' '))
+                {
+                    *picked.lock().unwrap() = Some(name.trim().to_string());
                 } else if input == "clear" {
-                    println!("\x1B[2J\x1B[1;1H");
+                    clear_screen();
                 } else if input.eq("quit") {
                     should_quit.store(true, Ordering::SeqCst);
-                    println!("Bye!");
+                    println!("{}", tr!("shell.bye"));
                 } else if input.eq("help") {
-                    println!("Commands available to you in watch mode:");
-                    println!("  hint   - prints the current exercise's hint");
-                    println!("  clear  - clears the screen");
-                    println!("  quit   - quits watch mode");
-                    println!("  !<cmd> - executes a command, like `!rustc --explain E0381`");
-                    println!("  help   - displays this help message");
-                    println!();
-                    println!("Watch mode automatically re-evaluates the current exercise");
-                    println!("when you edit a file's contents.")
+                    println!("{}", tr!("shell.help"));
                 } else if let Some(cmd) = input.strip_prefix('!') {
                     let parts: Vec<&str> = cmd.split_whitespace().collect();
                     if parts.is_empty() {
-                        println!("no command provided");
+                        println!("{}", tr!("shell.no_command"));
                     } else if let Err(e) = Command::new(parts[0]).args(&parts[1..]).status() {
-                        println!("failed to execute command `{}`: {}", cmd, e);
+                        println!("{}", tr!("shell.command_failed", command = cmd, error = e));
                     }
                 } else {
-                    println!("unknown command: {input}");
+                    println!("{}", tr!("shell.unknown", input = input));
                 }
             }
-            Err(error) => println!("error reading command: {error}"),
+            Err(error) => println!("{}", tr!("shell.read_error", error = error)),
         }
     });
 }
 
-fn find_exercise<'a>(name: &str, exercises: &'a [Exercise]) -> &'a Exercise {
+fn find_exercise<'a>(name: &str, curriculum: &Curriculum<'a>) -> &'a Exercise {
     if name.eq("next") {
-        exercises
-            .iter()
-            .find(|e| !e.looks_done())
+        let unlocked = curriculum.unlocked();
+        if unlocked.len() > 1 {
+            let others: Vec<&str> = unlocked[1..].iter().map(|e| e.name.as_str()).collect();
+            println!("{}", tr!("find.also_unlocked", exercises = others.join(", ")));
+        }
+        unlocked
+            .first()
+            .copied()
             .unwrap_or_else(|| {
-                println!("🎉 Congratulations! You have done all the exercises!");
-                println!("🔚 There are no more exercises to do next!");
+                println!(
+                    "{}",
+                    tr!(
+                        "find.all_done",
+                        party = output::emoji("🎉", "*"),
+                        end = output::emoji("🔚", "*")
+                    )
+                );
                 std::process::exit(1)
             })
     } else {
-        exercises
+        curriculum
+            .exercises()
             .iter()
             .find(|e| e.name == name)
             .unwrap_or_else(|| {
-                println!("No exercise found for '{name}'!");
+                println!("{}", tr!("find.not_found", name = name));
                 std::process::exit(1)
             })
     }
@@ -448,72 +783,94 @@ enum WatchStatus {
 }
 
 fn watch(
-    exercises: &[Exercise],
+    curriculum: &Curriculum,
     verbose: bool,
     success_hints: bool,
 ) -> notify::Result<WatchStatus> {
-    /* Clears the terminal with an ANSI escape code.
-    Works in UNIX and newer Windows terminals. */
-    fn clear_screen() {
-        println!("\x1Bc");
-    }
-
     let (tx, rx) = channel();
     let should_quit = Arc::new(AtomicBool::new(false));
+    let picked = Arc::new(Mutex::new(None));
+    start_recording();
 
     let mut watcher: RecommendedWatcher = Watcher::new(tx, Duration::from_secs(1))?;
     watcher.watch(Path::new("./exercises"), RecursiveMode::Recursive)?;
 
     clear_screen();
 
-    let to_owned_hint = |t: &Exercise| t.hint.to_owned();
+    let exercises = curriculum.exercises();
+    let to_owned_hint = |t: &Exercise| (t.name.to_owned(), t.hint.to_owned());
+    // Exercises done before `watch` started are only checked, not attempted
     let failed_exercise_hint = match verify(
-        exercises.iter(),
+        curriculum.in_order(),
         (0, exercises.len()),
         verbose,
         success_hints,
+        false,
     ) {
         Ok(_) => return Ok(WatchStatus::Finished),
-        Err(exercise) => Arc::new(Mutex::new(Some(to_owned_hint(exercise)))),
+        Err(exercise) => {
+            print_unlocked(curriculum, exercise);
+            Arc::new(Mutex::new(Some(to_owned_hint(exercise))))
+        }
     };
-    spawn_watch_shell(&failed_exercise_hint, Arc::clone(&should_quit));
+    spawn_watch_shell(&failed_exercise_hint, Arc::clone(&picked), Arc::clone(&should_quit));
     loop {
+        // Whether to verify again, and which exercise to start with
+        let mut verify_now = false;
+        let mut first = None;
         match rx.recv_timeout(Duration::from_secs(1)) {
             Ok(event) => match event {
-                DebouncedEvent::Create(b) | DebouncedEvent::Chmod(b) | DebouncedEvent::Write(b) => {
-                    if b.extension() == Some(OsStr::new("rs")) && b.exists() {
+                DebouncedEvent::Create(b) | DebouncedEvent::Chmod(b) | DebouncedEvent::Write(b)
+                    if b.extension() == Some(OsStr::new("rs")) && b.exists() => {
                         let filepath = b.as_path().canonicalize().unwrap();
-                        let pending_exercises = exercises
-                            .iter()
-                            .find(|e| filepath.ends_with(&e.path))
-                            .into_iter()
-                            .chain(
-                                exercises
-                                    .iter()
-                                    .filter(|e| !e.looks_done() && !filepath.ends_with(&e.path)),
-                            );
-                        let num_done = exercises.iter().filter(|e| e.looks_done()).count();
-                        clear_screen();
-                        match verify(
-                            pending_exercises,
-                            (num_done, exercises.len()),
-                            verbose,
-                            success_hints,
-                        ) {
-                            Ok(_) => return Ok(WatchStatus::Finished),
-                            Err(exercise) => {
-                                let mut failed_exercise_hint = failed_exercise_hint.lock().unwrap();
-                                *failed_exercise_hint = Some(to_owned_hint(exercise));
-                            }
-                        }
+                        verify_now = true;
+                        first = exercises.iter().find(|e| e.owns_file(&filepath));
                     }
-                }
                 _ => {}
             },
             Err(RecvTimeoutError::Timeout) => {
                 // the timeout expired, just check the `should_quit` variable below then loop again
             }
-            Err(e) => println!("watch error: {e:?}"),
+            Err(e) => println!("{}", tr!("watch.event_error", error = format!("{e:?}"))),
+        }
+        if let Some(name) = picked.lock().unwrap().take() {
+            match exercises.iter().find(|e| e.name == name) {
+                Some(exercise) => {
+                    let missing = curriculum.missing_prerequisites(exercise);
+                    if missing.is_empty() {
+                        verify_now = true;
+                        first = Some(exercise);
+                    } else {
+                        println!(
+                            "{}",
+                            tr!("watch.locked", exercise = name, missing = missing.join(", "))
+                        );
+                    }
+                }
+                None if name.is_empty() => {
+                    let unlocked: Vec<&str> =
+                        curriculum.unlocked().iter().map(|e| e.name.as_str()).collect();
+                    println!("{}", tr!("watch.pick_from", exercises = unlocked.join(", ")));
+                }
+                None => println!("{}", tr!("find.not_found", name = name)),
+            }
+        }
+        if verify_now {
+            let pending = first.into_iter().chain(
+                curriculum
+                    .in_order()
+                    .filter(|e| !e.looks_done() && first.is_none_or(|f| f.name != e.name)),
+            );
+            let num_done = exercises.iter().filter(|e| e.looks_done()).count();
+            clear_screen();
+            match verify(pending, (num_done, exercises.len()), verbose, success_hints, true) {
+                Ok(_) => return Ok(WatchStatus::Finished),
+                Err(exercise) => {
+                    let mut failed_exercise_hint = failed_exercise_hint.lock().unwrap();
+                    *failed_exercise_hint = Some(to_owned_hint(exercise));
+                    print_unlocked(curriculum, exercise);
+                }
+            }
         }
         // Check if we need to exit
         if should_quit.load(Ordering::SeqCst) {
@@ -522,9 +879,32 @@ fn watch(
     }
 }
 
+// Point out the other exercises that could be worked on instead of the current one
+fn print_unlocked(curriculum: &Curriculum, current: &Exercise) {
+    let others: Vec<&str> = curriculum
+        .unlocked()
+        .iter()
+        .filter(|e| e.name != current.name)
+        .map(|e| e.name.as_str())
+        .collect();
+    if !others.is_empty() {
+        println!("{}", tr!("watch.unlocked", exercises = others.join(", ")));
+    }
+}
+
+// The name the student committed their exercises with
+fn git_user_name() -> Option<String> {
+    let output = Command::new("git")
+        .args(["config", "user.name"])
+        .output()
+        .ok()?;
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !name.is_empty()).then_some(name)
+}
+
 fn rustc_exists() -> bool {
     Command::new("rustc")
-        .args(&["--version"])
+        .args(["--version"])
         .stdout(Stdio::null())
         .spawn()
         .and_then(|mut child| child.wait())
@@ -532,34 +912,6 @@ fn rustc_exists() -> bool {
         .unwrap_or(false)
 }
 
-const DEFAULT_OUT: &str = r#"Thanks for installing Rustlings!
-
-Is this your first time? Don't worry, Rustlings was made for beginners! We are
-going to teach you a lot of things about Rust, but before we can get
-started, here's a couple of notes about how Rustlings operates:
-
-1. The central concept behind Rustlings is that you solve exercises. These
-   exercises usually have some sort of syntax error in them, which will cause
-   them to fail compilation or testing. Sometimes there's a logic error instead
-   of a syntax error. No matter what error, it's your job to find it and fix it!
-   You'll know when you fixed it because then, the exercise will compile and
-   Rustlings will be able to move on to the next exercise.
-2. If you run Rustlings in watch mode (which we recommend), it'll automatically
-   start with the first exercise. Don't get confused by an error message popping
-   up as soon as you run Rustlings! This is part of the exercise that you're
-   supposed to solve, so open the exercise file in an editor and start your
-   detective work!
-3. If you're stuck on an exercise, there is a helpful hint you can view by typing
-   'hint' (in watch mode), or running `rustlings hint exercise_name`.
-4. If an exercise doesn't make sense to you, feel free to open an issue on GitHub!
-   (https://github.com/rust-lang/rustlings/issues/new). We look at every issue,
-   and sometimes, other learners do too so you can help each other out!
-5. If you want to use `rust-analyzer` with exercises, which provides features like
-   autocompletion, run the command `rustlings lsp`.
-
-Got all that? Great! To get started, run `rustlings watch` in order to get the first
-exercise. Make sure to have your editor open!"#;
-
 const FENISH_LINE: &str = r#"+----------------------------------------------------+
 |          You made it to the Fe-nish line!          |
 +--------------------------  ------------------------+
@@ -578,17 +930,9 @@ const FENISH_LINE: &str = r#"+--------------------------------------------------
          ▒▒    ▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒    ▒▒
        ▒▒    ▒▒    ▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒    ▒▒    ▒▒
        ▒▒  ▒▒    ▒▒                  ▒▒    ▒▒  ▒▒
-           ▒▒  ▒▒                      ▒▒  ▒▒
-
-We hope you enjoyed learning about the various aspects of Rust!
-If you noticed any issues, please don't hesitate to report them to our repo.
-You can also contribute your own exercises to help the greater community!
-
-Before reporting an issue or contributing, please read our guidelines:
-https://github.com/rust-lang/rustlings/blob/main/CONTRIBUTING.md"#;
+           ▒▒  ▒▒                      ▒▒  ▒▒"#;
 
-const WELCOME: &str = r#"       welcome to...
-                 _   _ _
+const WELCOME: &str = r#"                 _   _ _
   _ __ _   _ ___| |_| (_)_ __   __ _ ___
  | '__| | | / __| __| | | '_ \ / _` / __|
  | |  | |_| \__ \ |_| | | | | | (_| \__ \
@@ -0,0 +1,469 @@
+use crate::exercise::{Exercise, Mode, EXERCISES_DIR, HIDDEN_DIR, SOLUTIONS_DIR};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const INFO_TOML_PATH: &str = "info.toml";
+
+// The files `rustlings new` is going to create for an exercise
+pub struct Scaffold {
+    pub chapter: String,
+    pub name: String,
+    pub mode: Mode,
+    pub solution: bool,
+    pub hidden_test: bool,
+}
+
+impl Scaffold {
+    // The file name of the exercise, which is a directory for Cargo exercises
+    fn file_name(&self) -> String {
+        match self.mode {
+            Mode::Cargo => self.name.clone(),
+            _ => format!("{}.rs", self.name),
+        }
+    }
+
+    fn exercise_path(&self) -> PathBuf {
+        Path::new(EXERCISES_DIR)
+            .join(&self.chapter)
+            .join(self.file_name())
+    }
+
+    fn readme_path(&self) -> PathBuf {
+        Path::new(EXERCISES_DIR).join(&self.chapter).join("README.md")
+    }
+
+    fn solution_path(&self) -> PathBuf {
+        Path::new(SOLUTIONS_DIR)
+            .join(&self.chapter)
+            .join(self.file_name())
+    }
+
+    fn hidden_test_path(&self) -> PathBuf {
+        Path::new(HIDDEN_DIR)
+            .join(&self.chapter)
+            .join(format!("{}.rs", self.name))
+    }
+
+    // Refuse to scaffold anything that would clash with the existing exercises
+    // or that the runner would not be able to execute.
+    fn validate(&self, exercises: &[Exercise]) -> Result<(), Box<dyn Error>> {
+        let checks = [
+            (&self.chapter, tr!("new.invalid_chapter", chapter = self.chapter)),
+            (&self.name, tr!("new.invalid_name", name = self.name)),
+        ];
+        for (value, invalid) in checks {
+            let valid = !value.is_empty()
+                && value
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                return Err(invalid.into());
+            }
+        }
+        if exercises.iter().any(|e| e.name == self.name) {
+            return Err(tr!("new.exercise_exists", name = self.name).into());
+        }
+        // Clippy and build script exercises are run through the Cargo.toml that
+        // lives in their chapter directory, so they can't be put anywhere else.
+        let required_chapter = match self.mode {
+            Mode::Clippy => Some("clippy"),
+            Mode::BuildScript => Some("tests"),
+            _ => None,
+        };
+        if let Some(required) = required_chapter {
+            if self.chapter != required {
+                return Err(tr!(
+                    "new.wrong_chapter",
+                    mode = self.mode.as_str(),
+                    chapter = required
+                )
+                .into());
+            }
+        }
+        for path in self.files().into_iter().map(|(path, _)| path) {
+            if path.exists() {
+                return Err(tr!("new.file_exists", path = path.display()).into());
+            }
+        }
+        Ok(())
+    }
+
+    // Every source file this scaffold creates, together with its contents
+    fn files(&self) -> Vec<(PathBuf, String)> {
+        // Bench exercises are measured against their solution using a hidden workload
+        let bench = self.mode == Mode::Bench;
+        let mut files = self.exercise_files(self.exercise_path(), true);
+        if self.solution || bench {
+            files.extend(self.exercise_files(self.solution_path(), false));
+        }
+        if self.hidden_test || bench || self.mode == Mode::Property {
+            files.push((self.hidden_test_path(), hidden_test_template(self)));
+        }
+        files
+    }
+
+    // A single file, or the manifest, library and integration test of a crate
+    fn exercise_files(&self, path: PathBuf, pending: bool) -> Vec<(PathBuf, String)> {
+        if self.mode != Mode::Cargo {
+            return vec![(path, exercise_template(self, pending))];
+        }
+        vec![
+            (path.join("Cargo.toml"), cargo_toml_template(&self.name)),
+            (path.join("src").join("lib.rs"), exercise_template(self, pending)),
+            (
+                path.join("tests").join(format!("{}.rs", self.name)),
+                integration_test_template(&self.name),
+            ),
+        ]
+    }
+
+    fn info_toml_entry(&self) -> String {
+        format!(
+            r#"[[exercises]]
+name = "{}"
+path = "{}"
+mode = "{}"
+hint = """
+No hints this time!"""
+"#,
+            self.name,
+            self.exercise_path().display(),
+            self.mode.as_str()
+        )
+    }
+}
+
+// Create the exercise (and optionally its solution and hidden tests) from the
+// templates, then register it in info.toml after the last exercise of its chapter.
+pub fn new_exercise(scaffold: &Scaffold, exercises: &[Exercise]) -> Result<(), Box<dyn Error>> {
+    scaffold.validate(exercises)?;
+
+    let info = fs::read_to_string(INFO_TOML_PATH)?;
+    let info = insert_entry(&info, &scaffold.chapter, &scaffold.info_toml_entry());
+
+    for (path, contents) in scaffold.files() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, contents)?;
+        println!("{}", tr!("new.created", path = path.display()));
+    }
+
+    let readme = scaffold.readme_path();
+    if !readme.exists() {
+        fs::write(&readme, readme_template(&scaffold.chapter))?;
+        println!("{}", tr!("new.created", path = readme.display()));
+    }
+
+    fs::write(INFO_TOML_PATH, info)?;
+    println!("{}", tr!("new.registered", name = scaffold.name, file = INFO_TOML_PATH));
+    Ok(())
+}
+
+// Insert an `[[exercises]]` entry into the text of info.toml right after the
+// last exercise living in `exercises/<chapter>/`, or in a new chapter section
+// at the end of the file. The file is edited as text so that comments and
+// formatting are preserved.
+pub fn insert_entry(info: &str, chapter: &str, entry: &str) -> String {
+    let lines: Vec<&str> = info.lines().collect();
+
+    // Lines that are part of a multi-line string (the hints) must never be
+    // mistaken for table headers or comments.
+    let mut in_string = false;
+    let mut structural = Vec::with_capacity(lines.len());
+    for line in &lines {
+        structural.push(!in_string);
+        if line.matches(r#"""""#).count() % 2 == 1 {
+            in_string = !in_string;
+        }
+    }
+
+    let block_starts: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|&(i, line)| structural[i] && line.trim() == "[[exercises]]")
+        .map(|(i, _)| i)
+        .collect();
+
+    let chapter_path = format!("path = \"exercises/{chapter}/");
+    let last_block_end = block_starts
+        .iter()
+        .enumerate()
+        .rev()
+        .find_map(|(n, &start)| {
+            let end = block_starts.get(n + 1).copied().unwrap_or(lines.len());
+            let in_chapter = (start..end)
+                .any(|i| structural[i] && lines[i].trim_start().starts_with(&chapter_path));
+            in_chapter.then_some((start, end))
+        })
+        .map(|(start, end)| {
+            // Trailing blank lines and comments introduce whatever comes next
+            let mut end = end;
+            while end > start + 1 {
+                let line = lines[end - 1].trim();
+                if structural[end - 1] && (line.is_empty() || line.starts_with('#')) {
+                    end -= 1;
+                } else {
+                    break;
+                }
+            }
+            end
+        });
+
+    let mut out = String::with_capacity(info.len() + entry.len() + 32);
+    match last_block_end {
+        Some(end) => {
+            out.push_str(&lines[..end].join("\n"));
+            out.push_str("\n\n");
+            out.push_str(entry.trim_end());
+            out.push('\n');
+            if end < lines.len() {
+                if !lines[end].trim().is_empty() {
+                    out.push('\n');
+                }
+                out.push_str(&lines[end..].join("\n"));
+                if info.ends_with('\n') {
+                    out.push('\n');
+                }
+            }
+        }
+        None => {
+            out.push_str(info.trim_end());
+            out.push_str(&format!("\n\n# {}\n\n", chapter.to_uppercase()));
+            out.push_str(entry.trim_end());
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn exercise_template(scaffold: &Scaffold, pending: bool) -> String {
+    let name = &scaffold.name;
+    let mut s = if pending {
+        format!(
+            "// {name}.rs
+//
+// TODO: describe what the student has to do here.
+//
+// Execute `rustlings hint {name}` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+"
+        )
+    } else {
+        format!(
+            "// {name}.rs
+//
+// Reference solution for exercises/{}/{name}.rs
+
+",
+            scaffold.chapter
+        )
+    };
+    match scaffold.mode {
+        Mode::Compile | Mode::Clippy => s.push_str(
+            "fn main() {
+    // TODO: write the exercise here
+}
+",
+        ),
+        Mode::Test | Mode::BuildScript | Mode::Cargo | Mode::Bench | Mode::Property => s.push_str(
+            "#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_works() {
+        // TODO: write the exercise's tests here
+    }
+}
+",
+        ),
+    }
+    s
+}
+
+fn hidden_test_template(scaffold: &Scaffold) -> String {
+    if scaffold.mode == Mode::Bench {
+        return format!(
+            "// Benchmark workload for exercises/{}/{}.rs
+//
+// This file is appended to the exercise and to its reference solution, which
+// are then compiled with optimizations. The time `workload` takes to run is
+// what gets compared.
+
+fn workload() {{
+    // TODO: call the exercise's code with a large input here
+}}
+",
+            scaffold.chapter, scaffold.name
+        );
+    }
+    if scaffold.mode == Mode::Property {
+        return format!(
+            "// Property for exercises/{}/{}.rs
+//
+// This file is appended to the exercise, and `property` is called with many
+// random inputs drawn from `g`. Return an error describing the input when the
+// exercise gets it wrong; failing inputs are shrunk before they're reported.
+
+fn property(g: &mut rustlings_property::Gen) -> Result<(), String> {{
+    let input = g.vec_i32(100, -1000..1000);
+    // TODO: compare the exercise against an oracle or check an invariant here
+    let _ = &input;
+    Ok(())
+}}
+",
+            scaffold.chapter, scaffold.name
+        );
+    }
+    format!(
+        "// Hidden tests for exercises/{}/{}.rs
+//
+// These tests are not shipped to students. Test exercises are only graded on
+// the tests in the exercise file for now, so nothing runs this module yet.
+
+#[cfg(test)]
+mod hidden_tests {{
+    use super::*;
+
+    #[test]
+    fn it_works() {{
+        // TODO: write the hidden tests here
+    }}
+}}
+",
+        scaffold.chapter, scaffold.name
+    )
+}
+
+// The empty workspace keeps Cargo from looking for one in the rustlings root
+fn cargo_toml_template(name: &str) -> String {
+    format!(
+        r#"[package]
+name = "{name}"
+version = "0.0.1"
+edition = "2021"
+publish = false
+
+[workspace]
+
+[dev-dependencies]
+"#
+    )
+}
+
+fn integration_test_template(name: &str) -> String {
+    format!(
+        "// Integration tests for the {name} crate
+
+#[test]
+fn it_works() {{
+    // TODO: test the public API of the crate here, e.g. `{name}::...`
+}}
+"
+    )
+}
+
+fn readme_template(chapter: &str) -> String {
+    format!(
+        "# {chapter}
+
+TODO: introduce the topic of this chapter.
+
+## Further information
+
+- [The Rust Programming Language](https://doc.rust-lang.org/book/)
+"
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INFO: &str = r#"# INTRO
+
+[[exercises]]
+name = "intro1"
+path = "exercises/intro/intro1.rs"
+mode = "compile"
+hint = """
+# not a comment
+[[exercises]]"""
+
+# VARIABLES
+
+[[exercises]]
+name = "variables1"
+path = "exercises/variables/variables1.rs"
+mode = "compile"
+hint = """"""
+"#;
+
+    const ENTRY: &str = "[[exercises]]\nname = \"new1\"\n";
+
+    #[test]
+    fn test_insert_after_chapter() {
+        let out = insert_entry(INFO, "intro", ENTRY);
+        let expected = INFO.replace(
+            "[[exercises]]\"\"\"\n\n# VARIABLES",
+            "[[exercises]]\"\"\"\n\n[[exercises]]\nname = \"new1\"\n\n# VARIABLES",
+        );
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_insert_at_end_of_last_chapter() {
+        let out = insert_entry(INFO, "variables", ENTRY);
+        assert_eq!(out, format!("{INFO}\n{ENTRY}"));
+    }
+
+    #[test]
+    fn test_insert_new_chapter() {
+        let out = insert_entry(INFO, "graphs", ENTRY);
+        assert_eq!(out, format!("{INFO}\n# GRAPHS\n\n{ENTRY}"));
+    }
+
+    #[test]
+    fn test_templates_require_confirmation() {
+        let scaffold = Scaffold {
+            chapter: "algorithm".into(),
+            name: "algorithm11".into(),
+            mode: Mode::Test,
+            solution: true,
+            hidden_test: true,
+        };
+        let files = scaffold.files();
+        assert_eq!(files.len(), 3);
+        assert!(files[0].1.contains("// I AM NOT DONE"));
+        assert!(!files[1].1.contains("I AM NOT DONE"));
+        assert_eq!(files[1].0, Path::new("solutions/algorithm/algorithm11.rs"));
+        assert_eq!(files[2].0, Path::new("hidden/algorithm/algorithm11.rs"));
+    }
+
+    #[test]
+    fn test_cargo_exercise_is_a_crate() {
+        let scaffold = Scaffold {
+            chapter: "modules".into(),
+            name: "modules4".into(),
+            mode: Mode::Cargo,
+            solution: false,
+            hidden_test: false,
+        };
+        let paths: Vec<PathBuf> = scaffold.files().into_iter().map(|(path, _)| path).collect();
+        assert_eq!(
+            paths,
+            [
+                "exercises/modules/modules4/Cargo.toml",
+                "exercises/modules/modules4/src/lib.rs",
+                "exercises/modules/modules4/tests/modules4.rs",
+            ]
+            .map(PathBuf::from)
+        );
+        assert!(scaffold
+            .info_toml_entry()
+            .contains("path = \"exercises/modules/modules4\""));
+    }
+}
@@ -0,0 +1,119 @@
+use indicatif::{ProgressBar, ProgressStyle};
+use std::env;
+use std::io::{stdout, IsTerminal};
+use std::sync::OnceLock;
+
+// How rustlings writes to the terminal, decided once at startup
+#[derive(Debug, PartialEq, Eq)]
+pub struct Output {
+    // Whether to style the output with colors, which NO_COLOR turns off
+    pub color: bool,
+    // Whether to decorate the output with emoji, which NO_EMOJI turns off
+    pub emoji: bool,
+    // Whether to draw spinners and progress bars and to clear the screen.
+    // Screen readers and pipes get plain lines of text instead.
+    pub interactive: bool,
+}
+
+impl Output {
+    fn detect(screen_reader: bool, tty: bool) -> Output {
+        let screen_reader = screen_reader || is_set("RUSTLINGS_SCREEN_READER");
+        Output {
+            // https://no-color.org asks to ignore an empty NO_COLOR
+            color: tty && !is_set("NO_COLOR"),
+            emoji: env::var_os("NO_EMOJI").is_none() && !screen_reader,
+            interactive: tty && !screen_reader,
+        }
+    }
+}
+
+fn is_set(var: &str) -> bool {
+    env::var_os(var).is_some_and(|value| !value.is_empty())
+}
+
+static OUTPUT: OnceLock<Output> = OnceLock::new();
+
+pub fn init(screen_reader: bool) {
+    let output = Output::detect(screen_reader, stdout().is_terminal());
+    console::set_colors_enabled(output.color);
+    console::set_colors_enabled_stderr(output.color);
+    let _ = OUTPUT.set(output);
+}
+
+pub fn output() -> &'static Output {
+    OUTPUT.get_or_init(|| Output::detect(false, stdout().is_terminal()))
+}
+
+// The emoji, or its plain text fallback without emoji
+pub fn emoji(emoji: &'static str, fallback: &'static str) -> &'static str {
+    if output().emoji {
+        emoji
+    } else {
+        fallback
+    }
+}
+
+// The `--color` arguments for rustc and cargo, whose output is shown as is
+pub fn color_args() -> &'static [&'static str] {
+    if output().color {
+        &["--color", "always"]
+    } else {
+        &["--color", "never"]
+    }
+}
+
+// A spinner showing the message, or the message on a line of its own
+pub fn spinner(message: String) -> ProgressBar {
+    if !output().interactive {
+        println!("{message}");
+        return ProgressBar::hidden();
+    }
+    let spinner = ProgressBar::new_spinner();
+    spinner.set_message(message);
+    spinner.enable_steady_tick(100);
+    spinner
+}
+
+// A progress bar for the exercises done so far, if it can be drawn
+pub fn progress_bar(label: &str, done: usize, total: usize) -> ProgressBar {
+    if !output().interactive {
+        return ProgressBar::hidden();
+    }
+    let bar_style = if output().color { "{bar:60.green/red}" } else { "{bar:60}" };
+    let bar = ProgressBar::new(total as u64);
+    bar.set_style(
+        ProgressStyle::default_bar()
+            .template(&format!("{label} [{bar_style}] {{pos}}/{{len}} {{msg}}"))
+            .progress_chars("#>-"),
+    );
+    bar.set_position(done as u64);
+    bar
+}
+
+// Clears the terminal with an ANSI escape code, which works in UNIX and
+// newer Windows terminals. Screen readers would lose their place, so the
+// screen is only cleared when the output is interactive.
+pub fn clear_screen() {
+    if output().interactive {
+        println!("\x1Bc");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_screen_reader_output() {
+        let output = Output::detect(true, true);
+        assert!(!output.emoji);
+        assert!(!output.interactive);
+    }
+
+    #[test]
+    fn test_non_tty_output() {
+        let output = Output::detect(false, false);
+        assert!(!output.color);
+        assert!(!output.interactive);
+    }
+}
@@ -1,5 +1,7 @@
+use crate::exercise::module_files;
 use glob::glob;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::env;
 use std::error::Error;
 use std::path::PathBuf;
@@ -57,10 +59,15 @@ impl RustAnalyzerProject {
 
     /// Parse the exercises folder for .rs files, any matches will create
     /// a new `crate` in rust-project.json which allows rust-analyzer to
-    /// treat it like a normal binary
+    /// treat it like a normal binary. Modules that exercises declare with
+    /// `mod` are part of those crates rather than crates of their own.
     pub fn exercises_to_json(&mut self) -> Result<(), Box<dyn Error>> {
-        for path in glob("./exercises/**/*")? {
-            self.path_to_json(path?)?;
+        let paths = glob("./exercises/**/*")?.collect::<Result<Vec<_>, _>>()?;
+        let modules: HashSet<PathBuf> = paths.iter().flat_map(|path| module_files(path)).collect();
+        for path in paths {
+            if !modules.contains(&path) {
+                self.path_to_json(path)?;
+            }
         }
         Ok(())
     }
@@ -84,9 +91,9 @@ impl RustAnalyzerProject {
 
         let toolchain = whitespace_iter.next().unwrap_or(&toolchain);
 
-        println!("Determined toolchain: {}\n", &toolchain);
+        println!("{}\n", tr!("lsp.toolchain", toolchain = toolchain));
 
-        self.sysroot_src = (std::path::Path::new(&*toolchain)
+        self.sysroot_src = (std::path::Path::new(toolchain)
             .join("lib")
             .join("rustlib")
             .join("src")
@@ -97,3 +104,18 @@ impl RustAnalyzerProject {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_shared_modules_are_not_crates() {
+        let mut project = RustAnalyzerProject::new();
+        project.exercises_to_json().unwrap();
+        let roots: Vec<&str> = project.crates.iter().map(|c| c.root_module.as_str()).collect();
+        assert!(roots.iter().any(|root| root.ends_with("algorithm10.rs")));
+        assert!(!roots.iter().any(|root| root.ends_with("graph.rs")));
+        assert!(!roots.iter().any(|root| root.ends_with("random.rs")));
+    }
+}
@@ -0,0 +1,293 @@
+use crate::exercise::{temp_file, Exercise};
+use crate::harness::{build, GeneratedFiles};
+use serde::Deserialize;
+use std::fs;
+use std::process::Command;
+
+const DEFAULT_SEED: u64 = 0x5EED;
+const DEFAULT_CASES: u64 = 256;
+
+// The harness appended to the exercise and its hidden `property` function.
+// Every random value the property draws from `Gen` is recorded as a "choice",
+// so that a failing case can be replayed and shrunk by deleting and lowering
+// choices until no smaller sequence of choices fails anymore. This works for
+// any input the property builds, without knowing anything about its type.
+const HARNESS: &str = r#"
+#[allow(dead_code)]
+mod rustlings_property {
+    use std::ops::Range;
+    use std::panic::{self, AssertUnwindSafe};
+
+    // The maximum number of attempts made to shrink a failing case
+    const SHRINK_BUDGET: usize = 10_000;
+
+    pub struct Gen {
+        state: u64,
+        replay: Option<Vec<u64>>,
+        choices: Vec<u64>,
+    }
+
+    impl Gen {
+        fn random(seed: u64) -> Gen {
+            Gen { state: seed, replay: None, choices: Vec::new() }
+        }
+
+        fn replay(choices: &[u64]) -> Gen {
+            Gen { state: 0, replay: Some(choices.to_vec()), choices: Vec::new() }
+        }
+
+        // splitmix64, or the recorded choices when replaying.
+        // Replaying past the recorded choices yields zeros, the simplest choice.
+        fn choice(&mut self) -> u64 {
+            let choice = match &self.replay {
+                Some(replay) => replay.get(self.choices.len()).copied().unwrap_or(0),
+                None => {
+                    self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+                    let mut z = self.state;
+                    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+                    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+                    z ^ (z >> 31)
+                }
+            };
+            self.choices.push(choice);
+            choice
+        }
+
+        /// A value in `0..bound`, or 0 if `bound` is 0
+        pub fn below(&mut self, bound: u64) -> u64 {
+            let choice = self.choice();
+            if bound == 0 { 0 } else { choice % bound }
+        }
+
+        /// A value in the given range, which shrinks towards its start
+        pub fn usize_in(&mut self, range: Range<usize>) -> usize {
+            range.start + self.below(range.end.saturating_sub(range.start) as u64) as usize
+        }
+
+        /// A value in the given range, which shrinks towards its start
+        pub fn i64_in(&mut self, range: Range<i64>) -> i64 {
+            let span = range.end.wrapping_sub(range.start) as u64;
+            range.start.wrapping_add(self.below(span) as i64)
+        }
+
+        /// A value in the given range, which shrinks towards its start
+        pub fn i32_in(&mut self, range: Range<i32>) -> i32 {
+            self.i64_in(range.start as i64..range.end as i64) as i32
+        }
+
+        pub fn bool(&mut self) -> bool {
+            self.below(2) == 1
+        }
+
+        /// One of the given items, which shrinks towards the first one
+        pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+            &items[self.below(items.len() as u64) as usize]
+        }
+
+        /// A vector of at most `max_len` elements generated by `element`
+        pub fn vec<T>(&mut self, max_len: usize, mut element: impl FnMut(&mut Gen) -> T) -> Vec<T> {
+            // Every element is preceded by a choice to keep going, so that
+            // deleting choices while shrinking removes whole elements.
+            let mut v = Vec::new();
+            while v.len() < max_len && self.below(16) != 0 {
+                v.push(element(self));
+            }
+            v
+        }
+
+        /// A vector of values in the given range, like `vec`
+        pub fn vec_i32(&mut self, max_len: usize, range: Range<i32>) -> Vec<i32> {
+            self.vec(max_len, |g| g.i32_in(range.clone()))
+        }
+    }
+
+    fn check(property: fn(&mut Gen) -> Result<(), String>, gen: &mut Gen) -> Result<(), String> {
+        match panic::catch_unwind(AssertUnwindSafe(|| property(gen))) {
+            Ok(result) => result,
+            Err(payload) => Err(match payload.downcast_ref::<&str>() {
+                Some(message) => format!("panicked: {message}"),
+                None => match payload.downcast_ref::<String>() {
+                    Some(message) => format!("panicked: {message}"),
+                    None => "panicked".to_string(),
+                },
+            }),
+        }
+    }
+
+    // The choices of a failing case if replaying `choices` still fails
+    fn fails(property: fn(&mut Gen) -> Result<(), String>, choices: &[u64]) -> Option<Vec<u64>> {
+        let mut gen = Gen::replay(choices);
+        check(property, &mut gen).err().map(|_| gen.choices)
+    }
+
+    fn simpler(a: &[u64], b: &[u64]) -> bool {
+        a.len() < b.len() || (a.len() == b.len() && a < b)
+    }
+
+    fn shrink(property: fn(&mut Gen) -> Result<(), String>, mut best: Vec<u64>) -> Vec<u64> {
+        let mut budget = SHRINK_BUDGET;
+        let mut improved = true;
+        while improved && budget > 0 {
+            improved = false;
+            for size in [8, 4, 2, 1] {
+                let mut start = 0;
+                while start + size <= best.len() && budget > 0 {
+                    budget -= 1;
+                    let mut candidate = best.clone();
+                    candidate.drain(start..start + size);
+                    match fails(property, &candidate) {
+                        Some(choices) if simpler(&choices, &best) => {
+                            best = choices;
+                            improved = true;
+                        }
+                        _ => start += 1,
+                    }
+                }
+            }
+            for i in 0..best.len() {
+                for smaller in [0, best[i] / 2, best[i].saturating_sub(1)] {
+                    if budget == 0 || i >= best.len() || smaller >= best[i] {
+                        continue;
+                    }
+                    budget -= 1;
+                    let mut candidate = best.clone();
+                    candidate[i] = smaller;
+                    if let Some(choices) = fails(property, &candidate) {
+                        if simpler(&choices, &best) {
+                            best = choices;
+                            improved = true;
+                        }
+                    }
+                }
+            }
+            // Failures often need two values to stay equal, like duplicates
+            for i in 0..best.len() {
+                for j in i + 1..best.len() {
+                    if budget == 0 || j >= best.len() {
+                        break;
+                    }
+                    for smaller in [0, best[i].min(best[j]) / 2] {
+                        if j >= best.len() || (smaller >= best[i] && smaller >= best[j]) {
+                            continue;
+                        }
+                        budget -= 1;
+                        let mut candidate = best.clone();
+                        candidate[i] = smaller;
+                        candidate[j] = smaller;
+                        if let Some(choices) = fails(property, &candidate) {
+                            if simpler(&choices, &best) {
+                                best = choices;
+                                improved = true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    pub fn run(property: fn(&mut Gen) -> Result<(), String>) {
+        let mut args = std::env::args().skip(1);
+        let seed: u64 = args.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+        let cases: u64 = args.next().and_then(|s| s.parse().ok()).unwrap_or(100);
+        panic::set_hook(Box::new(|_| {}));
+        for case in 0..cases {
+            let case_seed = seed.wrapping_add(case.wrapping_mul(0xA076_1D64_78BD_642F));
+            let mut gen = Gen::random(case_seed);
+            if check(property, &mut gen).is_ok() {
+                continue;
+            }
+            let minimal = shrink(property, gen.choices);
+            // The runner words the report, this only prints the failing case
+            // and the message of the minimized input, if it failed again
+            let message = check(property, &mut Gen::replay(&minimal)).err().unwrap_or_default();
+            println!("{}\n{message}", case + 1);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn main() {
+    rustlings_property::run(property);
+}
+"#;
+
+// The settings of a Property exercise, given in info.toml as
+// `property = { seed = 42, cases = 256 }`
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct PropertySettings {
+    // The seed of the random inputs, fixed so that every run is reproducible
+    #[serde(default = "default_seed")]
+    pub seed: u64,
+    // How many random inputs the property is checked against
+    #[serde(default = "default_cases")]
+    pub cases: u64,
+}
+
+fn default_seed() -> u64 {
+    DEFAULT_SEED
+}
+
+fn default_cases() -> u64 {
+    DEFAULT_CASES
+}
+
+impl Default for PropertySettings {
+    fn default() -> Self {
+        PropertySettings {
+            seed: DEFAULT_SEED,
+            cases: DEFAULT_CASES,
+        }
+    }
+}
+
+// Check the hidden `property` function of the exercise against random inputs.
+// On success this returns the summary printed by the harness, on failure the
+// failing seed and the minimized input.
+pub fn check_property(exercise: &Exercise) -> Result<String, String> {
+    let settings = exercise.property.clone().unwrap_or_default();
+    let property_path = exercise.hidden_path();
+    let property = fs::read_to_string(&property_path).map_err(|e| {
+        tr!(
+            "property.unreadable",
+            path = property_path.display(),
+            error = e
+        )
+    })?;
+
+    let binary = format!("{}_property", temp_file());
+    let mut files = GeneratedFiles::default();
+    build(
+        &exercise.path,
+        &property,
+        HARNESS,
+        &binary,
+        &["-C", "opt-level=1", "-C", "debug-assertions=on"],
+        &mut files,
+    )?;
+
+    let output = Command::new(&binary)
+        .args([settings.seed.to_string(), settings.cases.to_string()])
+        .output()
+        .map_err(|e| tr!("property.run_failed", error = e))?;
+    if output.status.success() {
+        return Ok(tr!("property.held", cases = settings.cases, seed = settings.seed));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout).trim_end().to_string();
+    let (case, message) = stdout.split_once('\n').unwrap_or((&stdout, ""));
+    let Ok(case) = case.parse::<u64>() else {
+        // The harness didn't get to report a case, like when it crashed
+        return Err(stdout);
+    };
+    let message = if message.is_empty() {
+        tr!("property.not_reproduced").to_string()
+    } else {
+        message.to_string()
+    };
+    Err(format!(
+        "{}\n{}",
+        tr!("property.failed", case = case, cases = settings.cases, seed = settings.seed),
+        tr!("property.minimized", input = message)
+    ))
+}
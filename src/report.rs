@@ -0,0 +1,380 @@
+use crate::exercise::Exercise;
+use crate::ExerciseCheckList;
+use glob::glob;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+// The number of exercises listed as the hardest ones
+const LOWEST_PASS_RATES: usize = 10;
+
+// The formats a class report can be written in
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+    Html,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            "html" => Ok(Format::Html),
+            _ => Err(tr!("main.unknown_format", format = s, expected = "csv, json, html")),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct StudentScore {
+    pub student: String,
+    pub passed: usize,
+    pub total: usize,
+    // Whether the student passed each exercise of the report, in its order.
+    // Exercises missing from the student's results are `None`.
+    pub results: Vec<Option<bool>>,
+    // The percentage of exercises passed
+    pub score: f64,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ExercisePassRate {
+    pub exercise: String,
+    pub passed: usize,
+    // The students whose results include the exercise
+    pub attempted: usize,
+    // The percentage of those students who passed it
+    pub pass_rate: f64,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ClassReport {
+    // Sorted by the name of the student
+    pub students: Vec<StudentScore>,
+    // In the order of info.toml, followed by exercises it doesn't know
+    pub exercises: Vec<ExercisePassRate>,
+    // The names of the exercises with the lowest pass rates, lowest first
+    pub lowest_pass_rates: Vec<String>,
+}
+
+fn percentage(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / whole as f64
+    }
+}
+
+// Read every result file below `dir`, keyed by the name of the student.
+// Results without a user name are keyed by the name of their file, or by
+// the directory of their file for the usual `.github/result/check_result.json`.
+pub fn load_results(dir: &Path) -> Result<BTreeMap<String, ExerciseCheckList>, Box<dyn Error>> {
+    let pattern = dir.join("**").join("*.json");
+    let mut results = BTreeMap::new();
+    for path in glob(&pattern.to_string_lossy())?.filter_map(Result::ok) {
+        let list: ExerciseCheckList = match serde_json::from_str(&fs::read_to_string(&path)?) {
+            Ok(list) => list,
+            Err(e) => {
+                println!("{}", tr!("report.not_a_result", path = path.display(), error = e));
+                continue;
+            }
+        };
+        let student = list
+            .user_name
+            .clone()
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| fallback_name(dir, &path));
+        if results.insert(student.clone(), list).is_some() {
+            println!(
+                "{}",
+                tr!("report.duplicate_result", student = student, path = path.display())
+            );
+        }
+    }
+    if results.is_empty() {
+        return Err(tr!("report.no_results", dir = dir.display()).into());
+    }
+    Ok(results)
+}
+
+fn fallback_name(dir: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(dir).unwrap_or(path);
+    let first = relative.components().next().map(|c| c.as_os_str());
+    match first {
+        Some(first) if relative.components().count() > 1 => first.to_string_lossy().to_string(),
+        _ => path
+            .file_stem()
+            .map_or_else(String::new, |stem| stem.to_string_lossy().to_string()),
+    }
+}
+
+pub fn merge(results: &BTreeMap<String, ExerciseCheckList>, exercises: &[Exercise]) -> ClassReport {
+    let mut names: Vec<String> = exercises.iter().map(|e| e.name.clone()).collect();
+    let mut unknown: Vec<String> = results
+        .values()
+        .flat_map(|list| &list.exercises)
+        .map(|result| result.name.clone())
+        .filter(|name| !names.contains(name))
+        .collect();
+    unknown.sort();
+    unknown.dedup();
+    names.extend(unknown);
+
+    let students: Vec<StudentScore> = results
+        .iter()
+        .map(|(student, list)| {
+            let results: Vec<Option<bool>> = names
+                .iter()
+                .map(|name| {
+                    list.exercises
+                        .iter()
+                        .find(|result| &result.name == name)
+                        .map(|result| result.result)
+                })
+                .collect();
+            let passed = results.iter().filter(|r| **r == Some(true)).count();
+            let total = list.statistics.total_exercations.max(list.exercises.len());
+            StudentScore {
+                student: student.clone(),
+                passed,
+                total,
+                results,
+                score: percentage(passed, total),
+            }
+        })
+        .collect();
+
+    let exercises: Vec<ExercisePassRate> = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let passed = students.iter().filter(|s| s.results[i] == Some(true)).count();
+            let attempted = students.iter().filter(|s| s.results[i].is_some()).count();
+            ExercisePassRate {
+                exercise: name.clone(),
+                passed,
+                attempted,
+                pass_rate: percentage(passed, attempted),
+            }
+        })
+        .filter(|rate| rate.attempted > 0)
+        .collect();
+    // Drop the columns of exercises nobody has results for
+    let kept: Vec<bool> = names
+        .iter()
+        .map(|name| exercises.iter().any(|rate| &rate.exercise == name))
+        .collect();
+    let students = students
+        .into_iter()
+        .map(|mut score| {
+            let mut keep = kept.iter();
+            score.results.retain(|_| *keep.next().unwrap());
+            score
+        })
+        .collect();
+
+    let mut lowest: Vec<&ExercisePassRate> = exercises.iter().collect();
+    lowest.sort_by(|a, b| a.pass_rate.total_cmp(&b.pass_rate));
+    let lowest_pass_rates = lowest
+        .into_iter()
+        .take(LOWEST_PASS_RATES)
+        .map(|rate| rate.exercise.clone())
+        .collect();
+
+    ClassReport {
+        students,
+        exercises,
+        lowest_pass_rates,
+    }
+}
+
+impl ClassReport {
+    pub fn render(&self, format: Format) -> Result<String, Box<dyn Error>> {
+        Ok(match format {
+            Format::Csv => self.csv(),
+            Format::Json => serde_json::to_string_pretty(self)?,
+            Format::Html => self.html(),
+        })
+    }
+
+    // One row per student and one column per exercise, with the pass rate
+    // of every exercise in the last row
+    fn csv(&self) -> String {
+        let mut csv = String::from("student,passed,total,score");
+        for rate in &self.exercises {
+            csv.push(',');
+            csv.push_str(&csv_field(&rate.exercise));
+        }
+        csv.push('\n');
+        for student in &self.students {
+            let _ = write!(
+                csv,
+                "{},{},{},{:.1}",
+                csv_field(&student.student),
+                student.passed,
+                student.total,
+                student.score
+            );
+            for result in &student.results {
+                csv.push_str(match result {
+                    Some(true) => ",1",
+                    Some(false) => ",0",
+                    None => ",",
+                });
+            }
+            csv.push('\n');
+        }
+        csv.push_str("pass rate,,,");
+        for rate in &self.exercises {
+            let _ = write!(csv, ",{:.1}", rate.pass_rate);
+        }
+        csv.push('\n');
+        csv
+    }
+
+    fn html(&self) -> String {
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Rustlings class report</title>\n\
+             <style>\nbody { font-family: sans-serif; }\ntable { border-collapse: collapse; margin-bottom: 2em; }\n\
+             th, td { border: 1px solid #ccc; padding: 0.2em 0.6em; text-align: right; }\n\
+             th:first-child, td:first-child { text-align: left; }\n.passed { background: #cfc; }\n.failed { background: #fcc; }\n\
+             </style>\n</head>\n<body>\n<h1>Rustlings class report</h1>\n",
+        );
+
+        html.push_str("<h2>Lowest pass rates</h2>\n<table>\n<tr><th>Exercise</th><th>Passed</th><th>Pass rate</th></tr>\n");
+        for name in &self.lowest_pass_rates {
+            if let Some(rate) = self.exercises.iter().find(|rate| &rate.exercise == name) {
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{}</td><td>{}/{}</td><td>{:.1} %</td></tr>",
+                    escape(name),
+                    rate.passed,
+                    rate.attempted,
+                    rate.pass_rate
+                );
+            }
+        }
+        html.push_str("</table>\n");
+
+        html.push_str("<h2>Students</h2>\n<table>\n<tr><th>Student</th><th>Passed</th><th>Score</th>");
+        for rate in &self.exercises {
+            let _ = write!(html, "<th>{}</th>", escape(&rate.exercise));
+        }
+        html.push_str("</tr>\n");
+        for student in &self.students {
+            let _ = write!(
+                html,
+                "<tr><td>{}</td><td>{}/{}</td><td>{:.1} %</td>",
+                escape(&student.student),
+                student.passed,
+                student.total,
+                student.score
+            );
+            for result in &student.results {
+                html.push_str(match result {
+                    Some(true) => "<td class=\"passed\">✓</td>",
+                    Some(false) => "<td class=\"failed\">✗</td>",
+                    None => "<td></td>",
+                });
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("<tr><td>Pass rate</td><td></td><td></td>");
+        for rate in &self.exercises {
+            let _ = write!(html, "<td>{:.1} %</td>", rate.pass_rate);
+        }
+        html.push_str("</tr>\n</table>\n</body>\n</html>\n");
+        html
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::Mode;
+    use crate::{ExerciseResult, ExerciseStatistics};
+    use std::path::PathBuf;
+
+    fn results(user_name: &str, passed: &[(&str, bool)]) -> ExerciseCheckList {
+        ExerciseCheckList {
+            exercises: passed
+                .iter()
+                .map(|(name, result)| ExerciseResult {
+                    name: name.to_string(),
+                    result: *result,
+                })
+                .collect(),
+            user_name: Some(user_name.to_string()),
+            statistics: ExerciseStatistics {
+                total_exercations: passed.len(),
+                total_succeeds: passed.iter().filter(|(_, result)| *result).count(),
+                total_failures: passed.iter().filter(|(_, result)| !*result).count(),
+                total_time: 0,
+            },
+            version: None,
+            hashes: BTreeMap::new(),
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let exercises: Vec<Exercise> = ["intro1", "vecs1", "never"]
+            .iter()
+            .map(|name| Exercise {
+                name: name.to_string(),
+                path: PathBuf::from(format!("exercises/{name}.rs")),
+                mode: Mode::Test,
+                hint: String::new(),
+                expected_stdout: None,
+                stdin: None,
+                bench: None,
+                property: None,
+                check_ub: false,
+                depends_on: None,
+            })
+            .collect();
+        let mut all = BTreeMap::new();
+        all.insert("bob".to_string(), results("bob", &[("vecs1", false), ("intro1", true)]));
+        all.insert("alice".to_string(), results("alice", &[("intro1", true), ("vecs1", true), ("extra", false)]));
+
+        let report = merge(&all, &exercises);
+        let names: Vec<&str> = report.exercises.iter().map(|r| r.exercise.as_str()).collect();
+        assert_eq!(names, ["intro1", "vecs1", "extra"]);
+        assert_eq!(report.students[0].student, "alice");
+        assert_eq!(report.students[0].results, [Some(true), Some(true), Some(false)]);
+        assert_eq!(report.students[1].results, [Some(true), Some(false), None]);
+        assert_eq!(report.students[1].score, 50.0);
+        assert_eq!(report.exercises[1].pass_rate, 50.0);
+        assert_eq!(report.lowest_pass_rates, ["extra", "vecs1", "intro1"]);
+    }
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field("alice"), "alice");
+        assert_eq!(csv_field("Doe, \"Jane\""), "\"Doe, \"\"Jane\"\"\"");
+    }
+}
@@ -1,8 +1,9 @@
 use std::process::Command;
 
 use crate::exercise::{Exercise, Mode};
+use crate::output::spinner;
+use crate::stats::{record, Outcome};
 use crate::verify::test;
-use indicatif::ProgressBar;
 
 // Invoke the rust compiler on the path of the given exercise,
 // and run the ensuing binary.
@@ -14,6 +15,9 @@ pub fn run(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
         Mode::Compile => compile_and_run(exercise)?,
         Mode::Clippy => compile_and_run(exercise)?,
         Mode::BuildScript => test(exercise, verbose)?,
+        Mode::Cargo => test(exercise, verbose)?,
+        Mode::Bench => test(exercise, verbose)?,
+        Mode::Property => test(exercise, verbose)?,
     }
     Ok(())
 }
@@ -35,39 +39,37 @@ pub fn reset(exercise: &Exercise) -> Result<(), ()> {
 // and run the ensuing binary.
 // This is strictly for non-test binaries, so output is displayed
 fn compile_and_run(exercise: &Exercise) -> Result<(), ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {exercise}..."));
-    progress_bar.enable_steady_tick(100);
+    let progress_bar = spinner(tr!("verify.compiling", exercise = exercise));
 
     let compilation_result = exercise.compile();
     let compilation = match compilation_result {
         Ok(compilation) => compilation,
         Err(output) => {
             progress_bar.finish_and_clear();
-            warn!(
-                "Compilation of {} failed!, Compiler error message:\n",
-                exercise
-            );
+            record(&exercise.name, Outcome::compile_error(&output.stderr));
+            warn!("{}", tr!("run.compilation_failed", exercise = exercise));
             println!("{}", output.stderr);
             return Err(());
         }
     };
 
-    progress_bar.set_message(format!("Running {exercise}..."));
+    progress_bar.set_message(tr!("verify.running", exercise = exercise));
     let result = compilation.run();
     progress_bar.finish_and_clear();
 
     match result {
         Ok(output) => {
+            record(&exercise.name, Outcome::Passed);
             println!("{}", output.stdout);
-            success!("Successfully ran {}", exercise);
+            success!("{}", tr!("run.ran", exercise = exercise));
             Ok(())
         }
         Err(output) => {
+            record(&exercise.name, Outcome::Failed);
             println!("{}", output.stdout);
             println!("{}", output.stderr);
 
-            warn!("Ran {} with errors", exercise);
+            warn!("{}", tr!("verify.ran_with_errors", exercise = exercise));
             Err(())
         }
     }
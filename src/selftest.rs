@@ -0,0 +1,103 @@
+use crate::exercise::{Exercise, Mode};
+use crate::output::spinner;
+
+// What self-testing found out about a single exercise
+enum Finding {
+    // The exercise is broken as shipped and its solution passes
+    Ok,
+    // The exercise is broken as shipped but there is no solution to check
+    NoSolution,
+    // The exercise passes as shipped, so it doesn't teach anything
+    AlreadySolved,
+    // The reference solution doesn't pass
+    SolutionFails,
+}
+
+// Check that every given exercise fails as shipped and that its reference
+// solution in `solutions/` passes. This is meant to be run in CI against the
+// course content itself, not by students.
+pub fn selftest(exercises: &[&Exercise], require_solutions: bool) -> Result<(), ()> {
+    let mut failures = 0;
+    for exercise in exercises {
+        let finding = check(exercise);
+        let failed = match finding {
+            Finding::Ok => {
+                success!("{}", tr!("selftest.ok", exercise = exercise));
+                false
+            }
+            Finding::NoSolution => {
+                let msg = tr!(
+                    "selftest.no_solution",
+                    exercise = exercise,
+                    path = exercise.solution_path().display()
+                );
+                warn!("{}", msg);
+                require_solutions
+            }
+            Finding::AlreadySolved => {
+                warn!("{}", tr!("selftest.already_solved", exercise = exercise));
+                true
+            }
+            Finding::SolutionFails => {
+                let msg = tr!(
+                    "selftest.solution_fails",
+                    exercise = exercise,
+                    path = exercise.solution_path().display()
+                );
+                warn!("{}", msg);
+                true
+            }
+        };
+        if failed {
+            failures += 1;
+        }
+    }
+
+    println!();
+    println!(
+        "{}",
+        tr!(
+            "selftest.summary",
+            total = exercises.len(),
+            ok = exercises.len() - failures,
+            failed = failures
+        )
+    );
+    if failures == 0 {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+fn check(exercise: &Exercise) -> Finding {
+    if passes(exercise) {
+        return Finding::AlreadySolved;
+    }
+    let solution_path = exercise.solution_path();
+    if !solution_path.exists() {
+        return Finding::NoSolution;
+    }
+    let solution = Exercise {
+        path: solution_path,
+        ..exercise.clone()
+    };
+    if passes(&solution) {
+        Finding::Ok
+    } else {
+        Finding::SolutionFails
+    }
+}
+
+// Whether the exercise passes under its mode, the same way `verify` would
+// judge it, minus the `I AM NOT DONE` marker.
+fn passes(exercise: &Exercise) -> bool {
+    let progress_bar = spinner(tr!("selftest.checking", exercise = exercise));
+
+    let passed = match exercise.compile() {
+        Ok(compiled) => exercise.mode == Mode::Clippy || compiled.run().is_ok(),
+        Err(_) => false,
+    };
+    progress_bar.finish_and_clear();
+    passed
+}
@@ -0,0 +1,341 @@
+use crate::curriculum::{Chapter, Curriculum};
+use crate::exercise::{ContextLine, Exercise, State};
+use crate::stats::{load_history, Event, HISTORY_PATH};
+use serde::Serialize;
+use std::io::{self, SeekFrom};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+// The largest request that is read, which is plenty for a GET without a body
+const MAX_REQUEST: usize = 8 * 1024;
+// How often the history is checked for new events
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+// How many polls without events before a comment keeps the stream alive
+const KEEP_ALIVE_POLLS: u32 = 30;
+
+// The course being served, shared by every connection
+struct Course {
+    exercises: Vec<Exercise>,
+    chapters: Vec<Chapter>,
+}
+
+#[derive(Serialize)]
+struct ExerciseSummary<'a> {
+    name: &'a str,
+    path: String,
+    mode: &'static str,
+    chapter: String,
+    done: bool,
+    // Whether the exercise isn't done yet, but its prerequisites are
+    unlocked: bool,
+}
+
+#[derive(Serialize)]
+struct ExerciseState<'a> {
+    name: &'a str,
+    done: bool,
+    // The lines around the `I AM NOT DONE` marker of a pending exercise
+    pending_lines: Vec<ContextLine>,
+    // The latest time the exercise was compiled, run or tested in watch mode
+    latest: Option<Event>,
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+// A GET request, which is all the server answers
+#[derive(Debug, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    // The id of the last event a reconnecting event stream has seen
+    last_event_id: Option<usize>,
+}
+
+enum Response {
+    Json(&'static str, String),
+    Events(Option<usize>),
+}
+
+// Serve the progress of the exercises over HTTP until the process is stopped
+pub async fn serve(
+    exercises: Vec<Exercise>,
+    chapters: Vec<Chapter>,
+    address: SocketAddr,
+) -> io::Result<()> {
+    let listener = TcpListener::bind(address).await?;
+    println!("{}", tr!("serve.listening", address = listener.local_addr()?));
+    let course = Arc::new(Course { exercises, chapters });
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let course = Arc::clone(&course);
+        tokio::spawn(async move {
+            // A client going away in the middle of a response is fine
+            let _ = handle(stream, course).await;
+        });
+    }
+}
+
+async fn handle(mut stream: TcpStream, course: Arc<Course>) -> io::Result<()> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0; 1024];
+    while !buffer.windows(4).any(|w| w == b"\r\n\r\n") {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 || buffer.len() + read > MAX_REQUEST {
+            return Ok(());
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+    let response = match parse_request(&String::from_utf8_lossy(&buffer)) {
+        // Routes read the exercises and the history, which blocks
+        Some(request) if request.method == "GET" => {
+            tokio::task::spawn_blocking(move || route(&request, &course))
+                .await
+                .unwrap_or_else(|e| error("500 Internal Server Error", &e.to_string()))
+        }
+        Some(_) => error("405 Method Not Allowed", tr!("serve.only_get")),
+        None => error("400 Bad Request", tr!("serve.malformed")),
+    };
+    match response {
+        Response::Json(status, body) => {
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+                 Access-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).await
+        }
+        Response::Events(last_event_id) => stream_events(stream, last_event_id).await,
+    }
+}
+
+fn parse_request(request: &str) -> Option<Request> {
+    let mut lines = request.lines();
+    let mut parts = lines.next()?.split_whitespace();
+    let method = parts.next()?.to_string();
+    let target = parts.next()?;
+    let path = target.split('?').next()?;
+    let path = match path.trim_end_matches('/') {
+        "" => "/",
+        trimmed => trimmed,
+    }
+    .to_string();
+    let last_event_id = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("last-event-id"))
+        .and_then(|(_, value)| value.trim().parse().ok());
+    Some(Request {
+        method,
+        path,
+        last_event_id,
+    })
+}
+
+fn json(status: &'static str, body: &impl Serialize) -> Response {
+    match serde_json::to_string_pretty(body) {
+        Ok(body) => Response::Json(status, body),
+        Err(e) => error("500 Internal Server Error", &e.to_string()),
+    }
+}
+
+fn error(status: &'static str, message: &str) -> Response {
+    let body = ErrorBody {
+        error: message.to_string(),
+    };
+    Response::Json(status, serde_json::to_string(&body).unwrap_or_default())
+}
+
+// Every time an exercise was compiled, run or tested, oldest first
+fn attempts() -> Vec<Event> {
+    load_history(Path::new(HISTORY_PATH))
+        .into_iter()
+        .filter(|event| event.outcome.is_attempt())
+        .collect()
+}
+
+fn route(request: &Request, course: &Course) -> Response {
+    match request.path.as_str() {
+        "/exercises" => {
+            let curriculum = match Curriculum::new(&course.exercises, &course.chapters) {
+                Ok(curriculum) => curriculum,
+                Err(e) => return error("500 Internal Server Error", &e),
+            };
+            let unlocked = curriculum.unlocked();
+            let summaries: Vec<ExerciseSummary> = course
+                .exercises
+                .iter()
+                .map(|exercise| ExerciseSummary {
+                    name: &exercise.name,
+                    path: exercise.path.to_string_lossy().replace('\\', "/"),
+                    mode: exercise.mode.as_str(),
+                    chapter: exercise.chapter(),
+                    done: exercise.looks_done(),
+                    unlocked: unlocked.iter().any(|e| e.name == exercise.name),
+                })
+                .collect();
+            json("200 OK", &summaries)
+        }
+        "/result" => json("200 OK", &attempts().pop()),
+        "/events" => Response::Events(request.last_event_id),
+        path => match path
+            .strip_prefix("/exercises/")
+            .and_then(|name| course.exercises.iter().find(|e| e.name == name))
+        {
+            Some(exercise) => {
+                let (done, pending_lines) = match exercise.state() {
+                    State::Done => (true, vec![]),
+                    State::Pending(context) => (false, context),
+                };
+                let latest = attempts()
+                    .into_iter()
+                    .rev()
+                    .find(|event| event.exercise == exercise.name);
+                json(
+                    "200 OK",
+                    &ExerciseState {
+                        name: &exercise.name,
+                        done,
+                        pending_lines,
+                        latest,
+                    },
+                )
+            }
+            None => error("404 Not Found", &tr!("serve.not_found", path = path)),
+        },
+    }
+}
+
+// Where an event stream is in the history
+struct HistoryCursor {
+    path: PathBuf,
+    // How many bytes were read, up to the end of the last complete line
+    offset: u64,
+    // How many lines were read, which is the id of the last one
+    lines: usize,
+    // The id of the last event the client has seen
+    seen: usize,
+}
+
+impl HistoryCursor {
+    fn new(path: impl Into<PathBuf>, seen: usize) -> Self {
+        HistoryCursor {
+            path: path.into(),
+            offset: 0,
+            lines: 0,
+            seen,
+        }
+    }
+
+    // The complete lines appended to the history since the last call, with
+    // their ids, leaving out those the client has seen and a line that is
+    // still being written
+    async fn new_lines(&mut self) -> Vec<(usize, String)> {
+        let mut bytes = Vec::new();
+        if let Ok(mut file) = File::open(&self.path).await {
+            // The history was deleted or cut short, so start over with the new one
+            if file.metadata().await.map_or(0, |m| m.len()) < self.offset {
+                self.offset = 0;
+                self.lines = 0;
+                self.seen = 0;
+            }
+            if file.seek(SeekFrom::Start(self.offset)).await.is_ok() {
+                let _ = file.read_to_end(&mut bytes).await;
+            }
+        }
+        let complete = bytes.iter().rposition(|b| *b == b'\n').map_or(0, |end| end + 1);
+        self.offset += complete as u64;
+        let mut new = Vec::new();
+        for line in String::from_utf8_lossy(&bytes[..complete]).lines() {
+            self.lines += 1;
+            if self.lines > self.seen {
+                new.push((self.lines, line.to_string()));
+            }
+        }
+        self.seen = self.seen.max(self.lines);
+        new
+    }
+}
+
+// Send the events that `watch` and `run` append to the history as
+// server-sent events, with the line number of the event as its id
+async fn stream_events(mut stream: TcpStream, last_event_id: Option<usize>) -> io::Result<()> {
+    stream
+        .write_all(
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n\
+              Access-Control-Allow-Origin: *\r\nConnection: keep-alive\r\n\r\n",
+        )
+        .await?;
+    let mut history = HistoryCursor::new(HISTORY_PATH, last_event_id.unwrap_or(0));
+    // Without an id to resume from, only the events from now on are sent
+    if last_event_id.is_none() {
+        history.new_lines().await;
+    }
+    let mut idle_polls = 0;
+    loop {
+        let mut message = String::new();
+        for (id, line) in history.new_lines().await {
+            if let Ok(event) = serde_json::from_str::<Event>(&line) {
+                message.push_str(&format!(
+                    "id: {id}\nevent: {}\ndata: {line}\n\n",
+                    event.outcome.name()
+                ));
+            }
+        }
+        if message.is_empty() {
+            idle_polls += 1;
+            if idle_polls >= KEEP_ALIVE_POLLS {
+                message.push_str(": keep-alive\n\n");
+            }
+        }
+        if !message.is_empty() {
+            idle_polls = 0;
+            stream.write_all(message.as_bytes()).await?;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_request() {
+        let request = parse_request(
+            "GET /exercises/intro1/?pretty HTTP/1.1\r\nHost: localhost\r\nLast-Event-ID: 12\r\n\r\n",
+        );
+        assert_eq!(
+            request,
+            Some(Request {
+                method: "GET".to_string(),
+                path: "/exercises/intro1".to_string(),
+                last_event_id: Some(12),
+            })
+        );
+        assert_eq!(parse_request(""), None);
+    }
+
+    #[tokio::test]
+    async fn test_history_cursor_reads_only_new_lines() {
+        let path = std::env::temp_dir().join(format!("rustlings-history-{}.jsonl", std::process::id()));
+        std::fs::write(&path, "a\nb\nc").unwrap();
+        let mut history = HistoryCursor::new(&path, 1);
+        assert_eq!(history.new_lines().await, vec![(2, "b".to_string())]);
+
+        std::fs::write(&path, "a\nb\nc\nd\n").unwrap();
+        let new = history.new_lines().await;
+        assert_eq!(new, vec![(3, "c".to_string()), (4, "d".to_string())]);
+        assert!(history.new_lines().await.is_empty());
+
+        std::fs::write(&path, "e\n").unwrap();
+        assert_eq!(history.new_lines().await, vec![(1, "e".to_string())]);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
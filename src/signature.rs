@@ -0,0 +1,191 @@
+use crate::exercise::Exercise;
+use crate::ExerciseCheckList;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::env;
+use std::error::Error;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// The environment variable holding the secret that results are signed with.
+// In CI this is a secret of the classroom, so students can't sign results
+// they edited by hand.
+pub const KEY_VAR: &str = "RUSTLINGS_RESULT_KEY";
+
+// Stands in for the hash of a file that couldn't be read while grading, so a
+// missing exercise only fails itself instead of the whole class
+pub const MISSING: &str = "missing";
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(hex, "{byte:02x}");
+    }
+    hex
+}
+
+fn unhex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+// The files graded by `cicvverify`, with the path as written in info.toml
+fn graded_files(exercises: &[Exercise]) -> Vec<PathBuf> {
+    let mut files = vec![PathBuf::from("info.toml")];
+    for exercise in exercises {
+        files.extend(exercise.source_files());
+    }
+    files
+}
+
+fn file_key(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+fn hash_file(path: &Path) -> String {
+    fs::read(path).map_or_else(|_| MISSING.to_string(), |contents| hex(&Sha256::digest(contents)))
+}
+
+// The SHA-256 hash of info.toml and of every exercise source, by path
+pub fn hash_sources(exercises: &[Exercise]) -> BTreeMap<String, String> {
+    graded_files(exercises)
+        .iter()
+        .map(|path| (file_key(path), hash_file(path)))
+        .collect()
+}
+
+// The files that couldn't be read when the results were written
+pub fn missing_files(hashes: &BTreeMap<String, String>) -> Vec<&str> {
+    hashes
+        .iter()
+        .filter(|(_, hash)| *hash == MISSING)
+        .map(|(path, _)| path.as_str())
+        .collect()
+}
+
+// The bytes that are signed: the results as compact JSON, without a signature
+fn signed_bytes(results: &ExerciseCheckList) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut value = serde_json::to_value(results)?;
+    if let Some(object) = value.as_object_mut() {
+        object.remove("signature");
+    }
+    Ok(serde_json::to_vec(&value)?)
+}
+
+fn mac(key: &str, results: &ExerciseCheckList) -> Result<HmacSha256, Box<dyn Error>> {
+    let mut mac = HmacSha256::new_from_slice(key.as_bytes())?;
+    mac.update(&signed_bytes(results)?);
+    Ok(mac)
+}
+
+// Sign the results with the key from `RUSTLINGS_RESULT_KEY`, returning
+// whether there was a key to sign them with
+pub fn sign(results: &mut ExerciseCheckList) -> Result<bool, Box<dyn Error>> {
+    let key = match env::var(KEY_VAR) {
+        Ok(key) if !key.is_empty() => key,
+        _ => return Ok(false),
+    };
+    results.signature = Some(hex(&mac(&key, results)?.finalize().into_bytes()));
+    Ok(true)
+}
+
+// Check the signature of the results against the key from `RUSTLINGS_RESULT_KEY`
+pub fn verify_signature(results: &ExerciseCheckList) -> Result<(), Box<dyn Error>> {
+    let key = env::var(KEY_VAR)
+        .map_err(|_| tr!("signature.no_key", var = KEY_VAR))?;
+    let signature = results
+        .signature
+        .as_deref()
+        .ok_or(tr!("signature.unsigned"))?;
+    let signature = unhex(signature).ok_or(tr!("signature.malformed"))?;
+    mac(&key, results)?
+        .verify_slice(&signature)
+        .map_err(|_| tr!("signature.mismatch").into())
+}
+
+// The files whose contents differ from when the results were written
+pub fn changed_files(results: &ExerciseCheckList) -> Vec<String> {
+    results
+        .hashes
+        .iter()
+        .filter(|(path, hash)| hash_file(Path::new(path)) != **hash)
+        .map(|(path, _)| path.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::Mode;
+    use crate::{ExerciseResult, ExerciseStatistics};
+
+    fn results() -> ExerciseCheckList {
+        ExerciseCheckList {
+            exercises: vec![ExerciseResult {
+                name: "intro1".into(),
+                result: true,
+            }],
+            user_name: Some("alice".into()),
+            statistics: ExerciseStatistics {
+                total_exercations: 1,
+                total_succeeds: 1,
+                total_failures: 0,
+                total_time: 2,
+            },
+            version: Some("5.5.1".into()),
+            hashes: BTreeMap::from([("info.toml".to_string(), "00".to_string())]),
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(hex(&[0, 15, 255]), "000fff");
+        assert_eq!(unhex("000fff"), Some(vec![0, 15, 255]));
+        assert_eq!(unhex("0g"), None);
+        assert_eq!(unhex("abc"), None);
+    }
+
+    #[test]
+    fn test_unreadable_files_are_missing() {
+        let exercise = Exercise {
+            name: "gone".into(),
+            path: PathBuf::from("tests/fixture/signature/gone.rs"),
+            mode: Mode::Compile,
+            hint: String::new(),
+            expected_stdout: None,
+            stdin: None,
+            bench: None,
+            property: None,
+            check_ub: false,
+            depends_on: None,
+        };
+        let hashes = hash_sources(&[exercise]);
+        assert_eq!(hashes["tests/fixture/signature/gone.rs"], MISSING);
+        assert_ne!(hashes["info.toml"], MISSING);
+        assert_eq!(missing_files(&hashes), vec!["tests/fixture/signature/gone.rs"]);
+    }
+
+    #[test]
+    fn test_tampering_breaks_the_signature() {
+        let key = "classroom secret";
+        let mut signed = results();
+        signed.signature = Some(hex(&mac(key, &signed).unwrap().finalize().into_bytes()));
+        let signature = unhex(signed.signature.as_deref().unwrap()).unwrap();
+        assert!(mac(key, &signed).unwrap().verify_slice(&signature).is_ok());
+
+        signed.exercises[0].result = false;
+        assert!(mac(key, &signed).unwrap().verify_slice(&signature).is_err());
+        signed.exercises[0].result = true;
+        assert!(mac("another key", &signed).unwrap().verify_slice(&signature).is_err());
+    }
+}
@@ -0,0 +1,422 @@
+use crate::exercise::Exercise;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// The local, append-only history of attempts, with one JSON event per line
+pub const HISTORY_PATH: &str = ".rustlings-history.jsonl";
+// A pause longer than this between two events is a break, not time spent
+const IDLE_CUTOFF: u64 = 15 * 60;
+const ERROR_CODE_REGEX: &str = r"error\[(E\d{4})\]";
+
+// Only `watch`, `run` and `hint` record events, so that grading every
+// exercise at once doesn't show up as attempts of the student.
+static RECORDING: AtomicBool = AtomicBool::new(false);
+
+pub fn start_recording() {
+    RECORDING.store(true, Ordering::SeqCst);
+}
+
+// Run `f` without recording its events, like re-checking an exercise the
+// student already finished
+pub fn without_recording<T>(f: impl FnOnce() -> T) -> T {
+    let recording = RECORDING.swap(false, Ordering::SeqCst);
+    let result = f();
+    RECORDING.store(recording, Ordering::SeqCst);
+    result
+}
+
+// What happened when the student worked on an exercise
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Outcome {
+    // The exercise didn't compile, with the error codes rustc reported
+    CompileError { error_codes: Vec<String> },
+    // The exercise compiled, but running or testing it failed
+    Failed,
+    // The exercise compiled and ran or tested successfully
+    Passed,
+    // The hint of the exercise was shown
+    Hint,
+}
+
+impl Outcome {
+    // A failed compilation with the error codes found in rustc's output
+    pub fn compile_error(stderr: &str) -> Outcome {
+        let re = Regex::new(ERROR_CODE_REGEX).unwrap();
+        let mut error_codes: Vec<String> = re
+            .captures_iter(stderr)
+            .map(|captures| captures[1].to_string())
+            .collect();
+        error_codes.sort();
+        error_codes.dedup();
+        Outcome::CompileError { error_codes }
+    }
+
+    // Whether the exercise was compiled, run or tested, rather than looked at
+    pub fn is_attempt(&self) -> bool {
+        *self != Outcome::Hint
+    }
+
+    // The name of the event, as it is written in the history
+    pub fn name(&self) -> &'static str {
+        match self {
+            Outcome::CompileError { .. } => "compile_error",
+            Outcome::Failed => "failed",
+            Outcome::Passed => "passed",
+            Outcome::Hint => "hint",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Event {
+    // Seconds since the Unix epoch
+    pub time: u64,
+    pub exercise: String,
+    #[serde(flatten)]
+    pub outcome: Outcome,
+}
+
+// Append an event to the history, if recording. Statistics are a nicety,
+// so failing to write them never gets in the way of the exercises.
+pub fn record(exercise: &str, outcome: Outcome) {
+    if !RECORDING.load(Ordering::SeqCst) {
+        return;
+    }
+    let event = Event {
+        time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+        exercise: exercise.to_string(),
+        outcome,
+    };
+    let Ok(line) = serde_json::to_string(&event) else {
+        return;
+    };
+    if let Ok(mut file) = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_PATH)
+    {
+        let _ignored = writeln!(file, "{line}");
+    }
+}
+
+// Read the history, skipping lines that aren't events, like a line cut
+// short by an interrupted write
+pub fn load_history(path: &Path) -> Vec<Event> {
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ExerciseStats {
+    pub name: String,
+    pub chapter: String,
+    // Compilations and runs, whatever their outcome
+    pub attempts: usize,
+    pub compile_errors: usize,
+    pub hints: usize,
+    pub solved: bool,
+    pub seconds_spent: u64,
+    pub average_seconds_between_attempts: Option<u64>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ChapterStats {
+    pub chapter: String,
+    // The exercises of the chapter that were attempted at all
+    pub exercises: usize,
+    pub attempts: usize,
+    pub seconds_spent: u64,
+}
+
+impl ChapterStats {
+    fn attempts_per_exercise(&self) -> f64 {
+        self.attempts as f64 / self.exercises.max(1) as f64
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Summary {
+    pub exercises: Vec<ExerciseStats>,
+    // Sorted from the chapter needing the most attempts per exercise
+    pub chapters: Vec<ChapterStats>,
+    // Sorted from the most common error code
+    pub error_codes: Vec<(String, usize)>,
+    pub seconds_spent: u64,
+}
+
+// Summarize the history of the exercises in info.toml, in their order.
+// The time between two events counts towards the exercise of the later one.
+pub fn summarize(events: &[Event], exercises: &[Exercise]) -> Summary {
+    let mut events = events.to_vec();
+    events.sort_by_key(|event| event.time);
+
+    let mut spent: BTreeMap<&str, u64> = BTreeMap::new();
+    for pair in events.windows(2) {
+        let gap = pair[1].time - pair[0].time;
+        if gap <= IDLE_CUTOFF {
+            *spent.entry(&pair[1].exercise).or_default() += gap;
+        }
+    }
+
+    let mut error_codes: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut stats = Vec::new();
+    for exercise in exercises {
+        let own: Vec<&Event> = events.iter().filter(|e| e.exercise == exercise.name).collect();
+        if own.is_empty() {
+            continue;
+        }
+        let attempt_times: Vec<u64> = own
+            .iter()
+            .filter(|e| e.outcome.is_attempt())
+            .map(|e| e.time)
+            .collect();
+        let gaps: Vec<u64> = attempt_times
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .filter(|gap| *gap <= IDLE_CUTOFF)
+            .collect();
+        let mut compile_errors = 0;
+        for event in &own {
+            if let Outcome::CompileError { error_codes: codes } = &event.outcome {
+                compile_errors += 1;
+                for code in codes {
+                    *error_codes.entry(code).or_default() += 1;
+                }
+            }
+        }
+        stats.push(ExerciseStats {
+            name: exercise.name.clone(),
+            chapter: exercise.chapter(),
+            attempts: attempt_times.len(),
+            compile_errors,
+            hints: own.iter().filter(|e| e.outcome == Outcome::Hint).count(),
+            solved: own.iter().any(|e| e.outcome == Outcome::Passed),
+            seconds_spent: spent.get(exercise.name.as_str()).copied().unwrap_or(0),
+            average_seconds_between_attempts: (!gaps.is_empty())
+                .then(|| gaps.iter().sum::<u64>() / gaps.len() as u64),
+        });
+    }
+
+    let mut chapters: Vec<ChapterStats> = Vec::new();
+    for exercise in &stats {
+        match chapters.iter_mut().find(|c| c.chapter == exercise.chapter) {
+            Some(chapter) => {
+                chapter.exercises += 1;
+                chapter.attempts += exercise.attempts;
+                chapter.seconds_spent += exercise.seconds_spent;
+            }
+            None => chapters.push(ChapterStats {
+                chapter: exercise.chapter.clone(),
+                exercises: 1,
+                attempts: exercise.attempts,
+                seconds_spent: exercise.seconds_spent,
+            }),
+        }
+    }
+    chapters.sort_by(|a, b| b.attempts_per_exercise().total_cmp(&a.attempts_per_exercise()));
+
+    let mut error_codes: Vec<(String, usize)> = error_codes
+        .into_iter()
+        .map(|(code, count)| (code.to_string(), count))
+        .collect();
+    error_codes.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+    Summary {
+        seconds_spent: stats.iter().map(|e| e.seconds_spent).sum(),
+        exercises: stats,
+        chapters,
+        error_codes,
+    }
+}
+
+fn duration(seconds: u64) -> String {
+    match seconds {
+        0..=59 => format!("{seconds}s"),
+        60..=3599 => format!("{}m {}s", seconds / 60, seconds % 60),
+        _ => format!("{}h {}m", seconds / 3600, seconds % 3600 / 60),
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.exercises.is_empty() {
+            return write!(f, "{}", tr!("stats.empty"));
+        }
+        writeln!(
+            f,
+            "{:<17}\t{:>8}\t{:>14}\t{:>5}\t{:>10}\t{:<7}",
+            tr!("list.name"),
+            tr!("stats.attempts"),
+            tr!("stats.compile_errors"),
+            tr!("stats.hints"),
+            tr!("stats.time_spent"),
+            tr!("list.status")
+        )?;
+        for e in &self.exercises {
+            writeln!(
+                f,
+                "{:<17}\t{:>8}\t{:>14}\t{:>5}\t{:>10}\t{:<7}",
+                e.name,
+                e.attempts,
+                e.compile_errors,
+                e.hints,
+                duration(e.seconds_spent),
+                if e.solved {
+                    tr!("stats.solved")
+                } else {
+                    tr!("list.pending")
+                }
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(f, "{}", tr!("stats.hardest_chapters"))?;
+        for chapter in self.chapters.iter().take(5) {
+            writeln!(
+                f,
+                "{}",
+                tr!(
+                    "stats.chapter",
+                    chapter = chapter.chapter,
+                    attempts = format!("{:.1}", chapter.attempts_per_exercise()),
+                    time = duration(chapter.seconds_spent)
+                )
+            )?;
+        }
+        if !self.error_codes.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "{}", tr!("stats.common_errors"))?;
+            for (code, count) in self.error_codes.iter().take(5) {
+                writeln!(f, "{}", tr!("stats.error_code", code = code, count = count))?;
+            }
+        }
+        writeln!(f)?;
+        write!(f, "{}", tr!("stats.total_time", time = duration(self.seconds_spent)))
+    }
+}
+
+// Write the summary for instructors, as CSV with one row per exercise if the
+// path ends in `.csv`, and as JSON otherwise
+pub fn export(summary: &Summary, path: &Path) -> Result<(), Box<dyn Error>> {
+    let contents = if path.extension().is_some_and(|ext| ext == "csv") {
+        let mut csv = String::from(
+            "name,chapter,attempts,compile_errors,hints,solved,seconds_spent,average_seconds_between_attempts\n",
+        );
+        for e in &summary.exercises {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{}\n",
+                e.name,
+                e.chapter,
+                e.attempts,
+                e.compile_errors,
+                e.hints,
+                e.solved,
+                e.seconds_spent,
+                e.average_seconds_between_attempts
+                    .map(|s| s.to_string())
+                    .unwrap_or_default()
+            ));
+        }
+        csv
+    } else {
+        serde_json::to_string_pretty(summary)?
+    };
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::Mode;
+    use std::path::PathBuf;
+
+    fn exercise(name: &str, path: &str) -> Exercise {
+        Exercise {
+            name: name.into(),
+            path: PathBuf::from(path),
+            mode: Mode::Test,
+            hint: String::new(),
+            expected_stdout: None,
+            stdin: None,
+            bench: None,
+            property: None,
+            check_ub: false,
+            depends_on: None,
+        }
+    }
+
+    fn event(time: u64, exercise: &str, outcome: Outcome) -> Event {
+        Event {
+            time,
+            exercise: exercise.into(),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_compile_error_codes() {
+        let stderr = "error[E0308]: mismatched types\nerror[E0425]: cannot find value\nerror[E0308]: mismatched types";
+        assert_eq!(
+            Outcome::compile_error(stderr),
+            Outcome::CompileError {
+                error_codes: vec!["E0308".into(), "E0425".into()]
+            }
+        );
+    }
+
+    #[test]
+    fn test_event_format() {
+        let line = r#"{"time":10,"exercise":"move1","event":"compile_error","error_codes":["E0382"]}"#;
+        let parsed: Event = serde_json::from_str(line).unwrap();
+        assert_eq!(
+            parsed,
+            event(10, "move1", Outcome::CompileError { error_codes: vec!["E0382".into()] })
+        );
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), line);
+    }
+
+    #[test]
+    fn test_summarize() {
+        let exercises = [
+            exercise("vecs1", "exercises/vecs/vecs1.rs"),
+            exercise("move1", "exercises/move_semantics/move1.rs"),
+        ];
+        let events = [
+            event(100, "vecs1", Outcome::Passed),
+            event(130, "move1", Outcome::compile_error("error[E0382]: use of moved value")),
+            event(150, "move1", Outcome::Hint),
+            event(190, "move1", Outcome::Failed),
+            // A break longer than IDLE_CUTOFF doesn't count as time spent
+            event(190 + IDLE_CUTOFF + 1, "move1", Outcome::Passed),
+        ];
+        let summary = summarize(&events, &exercises);
+
+        assert_eq!(summary.exercises[0].name, "vecs1");
+        assert_eq!(summary.exercises[0].attempts, 1);
+        let move1 = &summary.exercises[1];
+        assert_eq!(move1.chapter, "move_semantics");
+        assert_eq!(move1.attempts, 3);
+        assert_eq!(move1.compile_errors, 1);
+        assert_eq!(move1.hints, 1);
+        assert!(move1.solved);
+        assert_eq!(move1.seconds_spent, 90);
+        assert_eq!(move1.average_seconds_between_attempts, Some(60));
+        assert_eq!(summary.chapters[0].chapter, "move_semantics");
+        assert_eq!(summary.error_codes, vec![("E0382".to_string(), 1)]);
+        assert_eq!(summary.seconds_spent, 90);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::exercise::{temp_file, Exercise, Mode, RUSTC_EDITION_ARGS};
+use crate::harness::GeneratedFiles;
+use crate::output::color_args;
+use std::env;
+use std::fs;
+use std::process::{Command, Output};
+
+// Leaking memory is safe, and most linked structures in the exercises only
+// learn to free their nodes later on, so only actual undefined behavior fails.
+const MIRIFLAGS: &str = "-Zmiri-ignore-leaks";
+const ASAN_OPTIONS: &str = "detect_leaks=0";
+
+// The outcome of checking an exercise for undefined behavior
+pub enum UbCheck {
+    // The tests ran clean under the named tool
+    Passed(&'static str),
+    // The tool reported undefined behavior, with its report and backtrace
+    Failed(&'static str, String),
+    // No tool able to check the exercise is installed
+    Skipped(String),
+}
+
+// Run the tests of an exercise with `check_ub = true` under Miri, or under
+// AddressSanitizer if only a nightly toolchain without Miri is available.
+pub fn check_ub(exercise: &Exercise) -> UbCheck {
+    if let Some(toolchain) = miri_toolchain() {
+        return miri(exercise, toolchain);
+    }
+    if exercise.mode == Mode::Cargo {
+        return UbCheck::Skipped(tr!("ub.no_miri").into());
+    }
+    if succeeds(Command::new("rustc").args(["+nightly", "--version"])) {
+        return address_sanitizer(exercise);
+    }
+    UbCheck::Skipped(tr!("ub.no_tool").into())
+}
+
+fn succeeds(command: &mut Command) -> bool {
+    command.output().is_ok_and(|output| output.status.success())
+}
+
+// The toolchain argument Miri can be run with, if it's installed at all
+fn miri_toolchain() -> Option<&'static [&'static str]> {
+    [&[][..], &["+nightly"][..]]
+        .into_iter()
+        .find(|toolchain| succeeds(Command::new("cargo").args(*toolchain).args(["miri", "--version"])))
+}
+
+fn miri(exercise: &Exercise, toolchain: &[&str]) -> UbCheck {
+    let mut command = Command::new("cargo");
+    command
+        .args(toolchain)
+        .args(["miri", "test", "--offline"])
+        .args(color_args())
+        .env("MIRIFLAGS", MIRIFLAGS)
+        .env("RUST_BACKTRACE", "1");
+
+    // Single file exercises get a throwaway crate around them, which lives
+    // outside of the exercises so that it doesn't clash with other manifests.
+    let crate_dir = env::temp_dir().join(format!("rustlings-miri-{}", exercise.name));
+    if exercise.mode == Mode::Cargo {
+        command.current_dir(&exercise.path);
+    } else {
+        let path = match fs::canonicalize(&exercise.path) {
+            Ok(path) => path,
+            Err(e) => return UbCheck::Failed("Miri", tr!("ub.exercise_not_found", error = e)),
+        };
+        let cargo_toml = format!(
+            r#"[package]
+name = "{}"
+version = "0.0.1"
+edition = "2021"
+[lib]
+path = "{}"
+doctest = false
+[workspace]
+"#,
+            exercise.name.to_lowercase(),
+            path.to_string_lossy().replace('\\', "/")
+        );
+        let manifest = crate_dir.join("Cargo.toml");
+        if let Err(e) = fs::create_dir_all(&crate_dir).and_then(|()| fs::write(&manifest, cargo_toml)) {
+            return UbCheck::Failed("Miri", tr!("ub.crate_not_written", error = e));
+        }
+        command.arg("--manifest-path").arg(&manifest);
+    }
+
+    let outcome = report("Miri", command.output());
+    if exercise.mode != Mode::Cargo {
+        let _ignored = fs::remove_dir_all(&crate_dir);
+    }
+    outcome
+}
+
+fn address_sanitizer(exercise: &Exercise) -> UbCheck {
+    let binary = format!("{}_asan", temp_file());
+    let mut files = GeneratedFiles::default();
+    files.push(binary.clone());
+    let compiled = Command::new("rustc")
+        .args(["+nightly", "-Zsanitizer=address", "-C", "debuginfo=1", "--test"])
+        .arg(&exercise.path)
+        .args(["-o", &binary])
+        .args(RUSTC_EDITION_ARGS)
+        .args(["--cap-lints", "allow"])
+        .output();
+    match compiled {
+        Ok(output) if output.status.success() => {}
+        // The exercise compiled fine before, so this is the target or the
+        // toolchain not supporting the sanitizer rather than the student's fault
+        _ => {
+            return UbCheck::Skipped(tr!("ub.no_asan").into())
+        }
+    }
+    report(
+        "AddressSanitizer",
+        Command::new(&binary).env("ASAN_OPTIONS", ASAN_OPTIONS).output(),
+    )
+}
+
+fn report(tool: &'static str, output: std::io::Result<Output>) -> UbCheck {
+    match output {
+        Ok(output) if output.status.success() => UbCheck::Passed(tool),
+        Ok(output) => UbCheck::Failed(
+            tool,
+            format!(
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            ),
+        ),
+        Err(e) => UbCheck::Failed(tool, tr!("ub.run_failed", tool = tool, error = e)),
+    }
+}
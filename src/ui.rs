@@ -1,33 +1,23 @@
 macro_rules! warn {
     ($fmt:literal, $ex:expr) => {{
-        use console::{style, Emoji};
-        use std::env;
+        use console::style;
         let formatstr = format!($fmt, $ex);
-        if env::var("NO_EMOJI").is_ok() {
-            println!("{} {}", style("!").red(), style(formatstr).red());
-        } else {
-            println!(
-                "{} {}",
-                style(Emoji("⚠️ ", "!")).red(),
-                style(formatstr).red()
-            );
-        }
+        println!(
+            "{} {}",
+            style(crate::output::emoji("⚠️ ", "!")).red(),
+            style(formatstr).red()
+        );
     }};
 }
 
 macro_rules! success {
     ($fmt:literal, $ex:expr) => {{
-        use console::{style, Emoji};
-        use std::env;
+        use console::style;
         let formatstr = format!($fmt, $ex);
-        if env::var("NO_EMOJI").is_ok() {
-            println!("{} {}", style("✓").green(), style(formatstr).green());
-        } else {
-            println!(
-                "{} {}",
-                style(Emoji("✅", "✓")).green(),
-                style(formatstr).green()
-            );
-        }
+        println!(
+            "{} {}",
+            style(crate::output::emoji("✅", "✓")).green(),
+            style(formatstr).green()
+        );
     }};
 }
@@ -1,36 +1,54 @@
 use crate::exercise::{CompiledExercise, Exercise, Mode, State};
+use crate::output::{output, progress_bar, spinner};
+use crate::stats::{record, without_recording, Outcome};
 use console::style;
-use indicatif::{ProgressBar, ProgressStyle};
-use std::env;
+use indicatif::ProgressBar;
 
 // Verify that the provided container of Exercise objects
 // can be compiled and run without any failures.
 // Any such failures will be reported to the end user.
 // If the Exercise being verified is a test, the verbose boolean
 // determines whether or not the test harness outputs are displayed.
+// Unless record_done is set, exercises that are already done are checked
+// without adding to the history, as the student isn't working on them.
 pub fn verify<'a>(
     exercises: impl IntoIterator<Item = &'a Exercise>,
     progress: (usize, usize),
     verbose: bool,
     success_hints: bool,
+    record_done: bool,
 ) -> Result<(), &'a Exercise> {
     let (num_done, total) = progress;
-    let bar = ProgressBar::new(total as u64);
+    let bar = progress_bar(tr!("verify.progress"), num_done, total);
     let mut percentage = num_done as f32 / total as f32 * 100.0;
-    bar.set_style(ProgressStyle::default_bar()
-        .template("Progress: [{bar:60.green/red}] {pos}/{len} {msg}")
-        .progress_chars("#>-")
-    );
-    bar.set_position(num_done as u64);
     bar.set_message(format!("({:.1} %)", percentage));
+    // Without a bar to draw, the progress is printed once as a line of text
+    if !output().interactive {
+        println!(
+            "{}",
+            tr!(
+                "list.progress",
+                done = num_done,
+                total = total,
+                percentage = format!("{percentage:.1}")
+            )
+        );
+    }
 
     for exercise in exercises {
-        let compile_result = match exercise.mode {
+        let check = || match exercise.mode {
             Mode::Test => compile_and_test(exercise, RunMode::Interactive, verbose, success_hints),
             Mode::Compile => compile_and_run_interactively(exercise, success_hints),
             Mode::Clippy => compile_only(exercise, success_hints),
             Mode::BuildScript => compile_and_test(exercise, RunMode::Interactive, verbose, success_hints),
-
+            Mode::Cargo => compile_and_test(exercise, RunMode::Interactive, verbose, success_hints),
+            Mode::Bench => compile_and_test(exercise, RunMode::Interactive, verbose, success_hints),
+            Mode::Property => compile_and_test(exercise, RunMode::Interactive, verbose, success_hints),
+        };
+        let compile_result = if record_done || !exercise.looks_done() {
+            check()
+        } else {
+            without_recording(check)
         };
         if !compile_result.unwrap_or(false) {
             return Err(exercise);
@@ -55,32 +73,33 @@ pub fn test(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
 
 // Invoke the rust compiler without running the resulting binary
 fn compile_only(exercise: &Exercise, success_hints: bool) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {exercise}..."));
-    progress_bar.enable_steady_tick(100);
+    let progress_bar = spinner(tr!("verify.compiling", exercise = exercise));
 
     let _ = compile(exercise, &progress_bar)?;
     progress_bar.finish_and_clear();
+    record(&exercise.name, Outcome::Passed);
 
     Ok(prompt_for_completion(exercise, None, success_hints))
 }
 
 // Compile the given Exercise and run the resulting binary in an interactive mode
 fn compile_and_run_interactively(exercise: &Exercise, success_hints: bool) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {exercise}..."));
-    progress_bar.enable_steady_tick(100);
+    let progress_bar = spinner(tr!("verify.compiling", exercise = exercise));
 
     let compilation = compile(exercise, &progress_bar)?;
 
-    progress_bar.set_message(format!("Running {exercise}..."));
+    progress_bar.set_message(tr!("verify.running", exercise = exercise));
     let result = compilation.run();
     progress_bar.finish_and_clear();
 
     let output = match result {
-        Ok(output) => output,
+        Ok(output) => {
+            record(&exercise.name, Outcome::Passed);
+            output
+        }
         Err(output) => {
-            warn!("Ran {} with errors", exercise);
+            record(&exercise.name, Outcome::Failed);
+            warn!("{}", tr!("verify.ran_with_errors", exercise = exercise));
             println!("{}", output.stdout);
             println!("{}", output.stderr);
             return Err(());
@@ -93,9 +112,7 @@ fn compile_and_run_interactively(exercise: &Exercise, success_hints: bool) -> Re
 // Compile the given Exercise as a test harness and display
 // the output if verbose is set to true
 fn compile_and_test(exercise: &Exercise, run_mode: RunMode, verbose: bool, success_hints: bool) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Testing {exercise}..."));
-    progress_bar.enable_steady_tick(100);
+    let progress_bar = spinner(tr!("verify.testing", exercise = exercise));
 
     let compilation = compile(exercise, &progress_bar)?;
     let result = compilation.run();
@@ -103,7 +120,10 @@ fn compile_and_test(exercise: &Exercise, run_mode: RunMode, verbose: bool, succe
 
     match result {
         Ok(output) => {
-            if verbose {
+            record(&exercise.name, Outcome::Passed);
+            // Benchmark timings, property summaries and undefined behavior
+            // checks are worth seeing even on success
+            if verbose || exercise.check_ub || matches!(exercise.mode, Mode::Bench | Mode::Property) {
                 println!("{}", output.stdout);
             }
             if let RunMode::Interactive = run_mode {
@@ -113,10 +133,8 @@ fn compile_and_test(exercise: &Exercise, run_mode: RunMode, verbose: bool, succe
             }
         }
         Err(output) => {
-            warn!(
-                "Testing of {} failed! Please try again. Here's the output:",
-                exercise
-            );
+            record(&exercise.name, Outcome::Failed);
+            warn!("{}", tr!("verify.testing_failed", exercise = exercise));
             println!("{}", output.stdout);
             Err(())
         }
@@ -125,9 +143,9 @@ fn compile_and_test(exercise: &Exercise, run_mode: RunMode, verbose: bool, succe
 
 // Compile the given Exercise and return an object with information
 // about the state of the compilation
-fn compile<'a, 'b>(
+fn compile<'a>(
     exercise: &'a Exercise,
-    progress_bar: &'b ProgressBar,
+    progress_bar: &ProgressBar,
 ) -> Result<CompiledExercise<'a>, ()> {
     let compilation_result = exercise.compile();
 
@@ -135,10 +153,8 @@ fn compile<'a, 'b>(
         Ok(compilation) => Ok(compilation),
         Err(output) => {
             progress_bar.finish_and_clear();
-            warn!(
-                "Compiling of {} failed! Please try again. Here's the output:",
-                exercise
-            );
+            record(&exercise.name, Outcome::compile_error(&output.stderr));
+            warn!("{}", tr!("verify.compiling_failed", exercise = exercise));
             println!("{}", output.stderr);
             Err(())
         }
@@ -151,53 +167,58 @@ fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>, suc
         State::Pending(context) => context,
     };
     match exercise.mode {
-        Mode::Compile => success!("Successfully ran {}!", exercise),
-        Mode::Test => success!("Successfully tested {}!", exercise),
-        Mode::Clippy => success!("Successfully compiled {}!", exercise),
-        Mode::BuildScript => success!("Successfully compiled {}!", exercise),
+        Mode::Compile => success!("{}", tr!("verify.ran", exercise = exercise)),
+        Mode::Test => success!("{}", tr!("verify.tested", exercise = exercise)),
+        Mode::Clippy => success!("{}", tr!("verify.compiled", exercise = exercise)),
+        Mode::BuildScript => success!("{}", tr!("verify.compiled", exercise = exercise)),
+        Mode::Cargo => success!("{}", tr!("verify.tested", exercise = exercise)),
+        Mode::Bench => success!("{}", tr!("verify.benchmarked", exercise = exercise)),
+        Mode::Property => success!("{}", tr!("verify.tested", exercise = exercise)),
     }
 
-    let no_emoji = env::var("NO_EMOJI").is_ok();
+    let emoji = output().emoji;
 
-    let clippy_success_msg = if no_emoji {
-        "The code is compiling, and Clippy is happy!"
+    let clippy_success_msg = if emoji {
+        tr!("verify.msg.clippy")
     } else {
-        "The code is compiling, and 📎 Clippy 📎 is happy!"
+        tr!("verify.msg.clippy_no_emoji")
     };
 
     let success_msg = match exercise.mode {
-        Mode::Compile => "The code is compiling!",
-        Mode::Test => "The code is compiling, and the tests pass!",
+        Mode::Compile => tr!("verify.msg.compile"),
+        Mode::Test => tr!("verify.msg.test"),
         Mode::Clippy => clippy_success_msg,
-        Mode::BuildScript => "Build script works!",
+        Mode::BuildScript => tr!("verify.msg.buildscript"),
+        Mode::Cargo => tr!("verify.msg.cargo"),
+        Mode::Bench => tr!("verify.msg.bench"),
+        Mode::Property => tr!("verify.msg.property"),
     };
     println!();
-    if no_emoji {
-        println!("~*~ {success_msg} ~*~")
-    } else {
+    if emoji {
         println!("🎉 🎉  {success_msg} 🎉 🎉")
+    } else {
+        println!("~*~ {success_msg} ~*~")
     }
     println!();
 
     if let Some(output) = prompt_output {
-        println!("Output:");
+        println!("{}", tr!("verify.output"));
         println!("{}", separator());
         println!("{output}");
         println!("{}", separator());
         println!();
     }
     if success_hints {
-        println!("Hints:");
+        println!("{}", tr!("verify.hints"));
         println!("{}", separator());
         println!("{}", exercise.hint);
         println!("{}", separator());
         println!();
     }
 
-    println!("You can keep working on this exercise,");
     println!(
-        "or jump into the next one by removing the {} comment:",
-        style("`I AM NOT DONE`").bold()
+        "{}",
+        tr!("verify.keep_working", marker = style("`I AM NOT DONE`").bold())
     );
     println!();
     for context_line in context {
@@ -0,0 +1,11 @@
+fn workload() -> Vec<u32> {
+    let mut seed: u32 = 42;
+    let mut array: Vec<u32> = (0..5000)
+        .map(|_| {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            seed
+        })
+        .collect();
+    sort(&mut array);
+    array
+}
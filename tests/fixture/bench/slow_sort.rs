@@ -0,0 +1,22 @@
+fn sort(array: &mut [u32]) {
+    let n = array.len();
+    for i in 0..n {
+        for j in 0..n - i - 1 {
+            if array[j] > array[j + 1] {
+                array.swap(j, j + 1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorts() {
+        let mut array = [3, 1, 2];
+        sort(&mut array);
+        assert_eq!(array, [1, 2, 3]);
+    }
+}
@@ -0,0 +1,15 @@
+fn sort(array: &mut [u32]) {
+    array.sort_unstable();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorts() {
+        let mut array = [3, 1, 2];
+        sort(&mut array);
+        assert_eq!(array, [1, 2, 3]);
+    }
+}
@@ -0,0 +1,13 @@
+pub fn square_area(side: u32) -> u32 {
+    side + side
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn area_of_a_square() {
+        assert_eq!(square_area(3), 9);
+    }
+}
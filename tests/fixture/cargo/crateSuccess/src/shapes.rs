@@ -0,0 +1,5 @@
+// I AM NOT DONE
+
+pub fn square_area(side: u32) -> u32 {
+    side * side
+}
@@ -0,0 +1,7 @@
+use crate_success::shapes::square_area;
+
+#[test]
+fn area_of_a_square() {
+    println!("THE CRATE'S TESTS PASS");
+    assert_eq!(square_area(3), 9);
+}
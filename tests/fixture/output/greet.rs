@@ -0,0 +1,7 @@
+use std::io::stdin;
+
+fn main() {
+    let mut name = String::new();
+    stdin().read_line(&mut name).unwrap();
+    println!("Hello {}!", name.trim());
+}
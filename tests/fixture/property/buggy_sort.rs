@@ -0,0 +1,18 @@
+use std::collections::BTreeSet;
+
+// Passes the hand-written test, but loses duplicates
+fn sort(array: &mut Vec<i32>) {
+    *array = array.iter().copied().collect::<BTreeSet<_>>().into_iter().collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorts() {
+        let mut array = vec![3, 1, 2];
+        sort(&mut array);
+        assert_eq!(array, [1, 2, 3]);
+    }
+}
@@ -0,0 +1,12 @@
+fn property(g: &mut rustlings_property::Gen) -> Result<(), String> {
+    let input = g.vec_i32(50, -1000..1000);
+    let mut expected = input.clone();
+    expected.sort_unstable();
+    let mut actual = input.clone();
+    sort(&mut actual);
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(format!("sorting {input:?} gave {actual:?}"))
+    }
+}
@@ -0,0 +1,7 @@
+// fake_exercise
+
+mod shared_module;
+
+fn main() {
+    shared_module::greet();
+}
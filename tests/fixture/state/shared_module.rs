@@ -0,0 +1,3 @@
+// I AM NOT DONE
+
+pub fn greet() {}
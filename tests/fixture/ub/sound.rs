@@ -0,0 +1,6 @@
+#[test]
+fn read_through_raw_pointer() {
+    let value = Box::into_raw(Box::new(5));
+    assert_eq!(unsafe { *value }, 5);
+    drop(unsafe { Box::from_raw(value) });
+}
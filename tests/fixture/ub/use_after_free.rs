@@ -0,0 +1,7 @@
+#[test]
+fn read_after_free() {
+    let value = Box::into_raw(Box::new(5));
+    drop(unsafe { Box::from_raw(value) });
+    // The test passes, but reading freed memory is undefined behavior
+    assert!(unsafe { *value } >= 0);
+}
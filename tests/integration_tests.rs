@@ -2,8 +2,9 @@ use assert_cmd::prelude::*;
 use glob::glob;
 use predicates::boolean::PredicateBooleanExt;
 use std::fs::File;
-use std::io::Read;
-use std::process::Command;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
 
 #[test]
 fn runs_without_arguments() {
@@ -44,7 +45,7 @@ fn verify_fails_if_some_fails() {
 fn run_single_compile_success() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["run", "compSuccess"])
+        .args(["run", "compSuccess"])
         .current_dir("tests/fixture/success/")
         .assert()
         .success();
@@ -54,7 +55,7 @@ fn run_single_compile_success() {
 fn run_single_compile_failure() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["run", "compFailure"])
+        .args(["run", "compFailure"])
         .current_dir("tests/fixture/failure/")
         .assert()
         .code(1);
@@ -64,7 +65,7 @@ fn run_single_compile_failure() {
 fn run_single_test_success() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["run", "testSuccess"])
+        .args(["run", "testSuccess"])
         .current_dir("tests/fixture/success/")
         .assert()
         .success();
@@ -74,7 +75,7 @@ fn run_single_test_success() {
 fn run_single_test_failure() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["run", "testFailure"])
+        .args(["run", "testFailure"])
         .current_dir("tests/fixture/failure/")
         .assert()
         .code(1);
@@ -84,7 +85,7 @@ fn run_single_test_failure() {
 fn run_single_test_not_passed() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["run", "testNotPassed.rs"])
+        .args(["run", "testNotPassed.rs"])
         .current_dir("tests/fixture/failure/")
         .assert()
         .code(1);
@@ -104,7 +105,7 @@ fn run_single_test_no_filename() {
 fn run_single_test_no_exercise() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["run", "compNoExercise.rs"])
+        .args(["run", "compNoExercise.rs"])
         .current_dir("tests/fixture/failure")
         .assert()
         .code(1);
@@ -114,7 +115,7 @@ fn run_single_test_no_exercise() {
 fn reset_single_exercise() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["reset", "intro1"])
+        .args(["reset", "intro1"])
         .assert()
         .code(0);
 }
@@ -135,7 +136,7 @@ fn reset_no_exercise() {
 fn get_hint_for_single_test() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["hint", "testFailure"])
+        .args(["hint", "testFailure"])
         .current_dir("tests/fixture/failure")
         .assert()
         .code(0)
@@ -171,7 +172,7 @@ fn all_exercises_require_confirmation() {
 fn run_compile_exercise_does_not_prompt() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["run", "pending_exercise"])
+        .args(["run", "pending_exercise"])
         .current_dir("tests/fixture/state")
         .assert()
         .code(0)
@@ -182,7 +183,7 @@ fn run_compile_exercise_does_not_prompt() {
 fn run_test_exercise_does_not_prompt() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["run", "pending_test_exercise"])
+        .args(["run", "pending_test_exercise"])
         .current_dir("tests/fixture/state")
         .assert()
         .code(0)
@@ -193,7 +194,7 @@ fn run_test_exercise_does_not_prompt() {
 fn run_single_test_success_with_output() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["--nocapture", "run", "testSuccess"])
+        .args(["--nocapture", "run", "testSuccess"])
         .current_dir("tests/fixture/success/")
         .assert()
         .code(0)
@@ -204,7 +205,7 @@ fn run_single_test_success_with_output() {
 fn run_single_test_success_without_output() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["run", "testSuccess"])
+        .args(["run", "testSuccess"])
         .current_dir("tests/fixture/success/")
         .assert()
         .code(0)
@@ -215,7 +216,7 @@ fn run_single_test_success_without_output() {
 fn run_rustlings_list() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["list"])
+        .args(["list"])
         .current_dir("tests/fixture/success")
         .assert()
         .success();
@@ -225,7 +226,7 @@ fn run_rustlings_list() {
 fn run_rustlings_list_no_pending() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["list"])
+        .args(["--lang", "en", "list"])
         .current_dir("tests/fixture/success")
         .assert()
         .success()
@@ -236,7 +237,7 @@ fn run_rustlings_list_no_pending() {
 fn run_rustlings_list_both_done_and_pending() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["list"])
+        .args(["--lang", "en", "list"])
         .current_dir("tests/fixture/state")
         .assert()
         .success()
@@ -247,7 +248,7 @@ fn run_rustlings_list_both_done_and_pending() {
 fn run_rustlings_list_without_pending() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["list", "--solved"])
+        .args(["--lang", "en", "list", "--solved"])
         .current_dir("tests/fixture/state")
         .assert()
         .success()
@@ -258,9 +259,443 @@ fn run_rustlings_list_without_pending() {
 fn run_rustlings_list_without_done() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["list", "--unsolved"])
+        .args(["--lang", "en", "list", "--unsolved"])
         .current_dir("tests/fixture/state")
         .assert()
         .success()
         .stdout(predicates::str::contains("Done").not());
 }
+
+#[test]
+fn selftest_broken_exercise_with_solution() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["selftest", "broken"])
+        .current_dir("tests/fixture/selftest")
+        .assert()
+        .success();
+}
+
+#[test]
+fn selftest_reports_solved_and_unsolvable_exercises() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--lang", "en", "selftest"])
+        .current_dir("tests/fixture/selftest")
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("solved.rs already passes as shipped")
+                .and(predicates::str::contains("the solution of unsolvable.rs")),
+        );
+}
+
+#[test]
+fn run_cargo_exercise_success() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--nocapture", "run", "crateSuccess"])
+        .current_dir("tests/fixture/cargo")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("THE CRATE'S TESTS PASS"));
+}
+
+#[test]
+fn run_cargo_exercise_failure() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "crateFailure"])
+        .current_dir("tests/fixture/cargo")
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn run_compile_exercise_with_expected_output() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "greetExact"])
+        .current_dir("tests/fixture/output")
+        .assert()
+        .success();
+}
+
+#[test]
+fn run_compile_exercise_with_expected_output_file() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "greetFile"])
+        .current_dir("tests/fixture/output")
+        .assert()
+        .success();
+}
+
+#[test]
+fn run_compile_exercise_with_unexpected_output() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--lang", "en", "run", "greetRegex"])
+        .current_dir("tests/fixture/output")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("did not print what was expected"));
+}
+
+#[test]
+fn run_bench_exercise_within_budget() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--lang", "en", "run", "fastSort"])
+        .current_dir("tests/fixture/bench")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Benchmark results over 5 iterations"));
+}
+
+#[test]
+fn run_bench_exercise_too_slow() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--lang", "en", "run", "slowSort"])
+        .current_dir("tests/fixture/bench")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("too slow"));
+}
+
+#[test]
+fn run_property_exercise_success() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--lang", "en", "--nocapture", "run", "goodSort"])
+        .current_dir("tests/fixture/property")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Property held for 64 random inputs"));
+}
+
+#[test]
+fn run_property_exercise_reports_minimized_input() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--lang", "en", "run", "buggySort"])
+        .current_dir("tests/fixture/property")
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("with seed 7")
+                .and(predicates::str::contains("sorting [-1000, -1000] gave [-1000]")),
+        );
+}
+
+#[test]
+fn run_check_ub_exercise_success() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "sound"])
+        .current_dir("tests/fixture/ub")
+        .assert()
+        .success();
+}
+
+#[test]
+fn run_check_ub_exercise_reports_undefined_behavior() {
+    let output = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--lang", "en", "run", "useAfterFree"])
+        .current_dir("tests/fixture/ub")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // Without Miri or a nightly toolchain the check is skipped, not failed
+    if stdout.contains("Skipped the undefined behavior check") {
+        assert!(output.status.success());
+    } else {
+        assert!(!output.status.success());
+        assert!(stdout.contains("found undefined behavior in your solution"));
+    }
+}
+
+#[test]
+fn stats_summarize_history() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--lang", "en", "stats"])
+        .current_dir("tests/fixture/stats")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("move_semantics: 3.0 attempts per exercise, 2m 30s spent")
+                .and(predicates::str::contains("E0382: 2 times"))
+                .and(predicates::str::contains("Total time spent: 2m 30s")),
+        );
+}
+
+#[test]
+fn watch_does_not_record_done_exercises() {
+    let history = "tests/fixture/stats/.rustlings-history.jsonl";
+    let before = std::fs::read_to_string(history).unwrap();
+    let status = Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("watch")
+        .current_dir("tests/fixture/stats")
+        .stdin(Stdio::null())
+        .output()
+        .unwrap()
+        .status;
+    let after = std::fs::read_to_string(history).unwrap();
+    // Restore the fixture before asserting, so a failure doesn't leave it changed
+    std::fs::write(history, &before).unwrap();
+    assert!(status.success());
+    assert_eq!(after, before);
+}
+
+#[test]
+fn stats_export_csv() {
+    let export = std::env::temp_dir().join(format!("rustlings_stats_{}.csv", std::process::id()));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["stats", "--export"])
+        .arg(&export)
+        .current_dir("tests/fixture/stats")
+        .assert()
+        .success();
+    let csv = std::fs::read_to_string(&export).unwrap();
+    std::fs::remove_file(&export).unwrap();
+    assert!(csv.contains("move_semantics1,move_semantics,3,2,1,true,150,60"));
+}
+
+#[test]
+fn report_merge_csv() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["report", "merge", "results"])
+        .current_dir("tests/fixture/report")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("Alice,2,2,100.0,1,1")
+                .and(predicates::str::contains("Bob,1,2,50.0,1,0"))
+                // Results without a user name are named after their directory
+                .and(predicates::str::contains("carol,0,2,0.0,0,0"))
+                .and(predicates::str::contains("pass rate,,,,66.7,33.3")),
+        );
+}
+
+#[test]
+fn report_merge_json() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["report", "merge", "results", "--format", "json"])
+        .current_dir("tests/fixture/report")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            r#""lowest_pass_rates": [
+    "vecs1",
+    "intro1"
+  ]"#,
+        ));
+}
+
+#[test]
+fn report_verify_signed_results() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--lang", "en", "report", "verify", "signed.json", "--check-files"])
+        .env("RUSTLINGS_RESULT_KEY", "fixture key")
+        .current_dir("tests/fixture/signature")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("The results of alice were signed by rustlings 5.5.1.")
+                .and(predicates::str::contains("The exercises on disk are the ones that were graded.")),
+        );
+}
+
+#[test]
+fn report_verify_fails_on_tampered_results() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--lang", "en", "report", "verify", "tampered.json"])
+        .env("RUSTLINGS_RESULT_KEY", "fixture key")
+        .current_dir("tests/fixture/signature")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("The signature does not match"));
+}
+
+#[test]
+fn report_verify_fails_with_another_key() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["report", "verify", "signed.json"])
+        .env("RUSTLINGS_RESULT_KEY", "student guess")
+        .current_dir("tests/fixture/signature")
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn get_localized_hint() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--lang", "zh", "hint", "compFailure"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .code(0)
+        .stdout("把 let 语句写完整。\n");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--lang", "en", "hint", "compFailure"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .code(0)
+        .stdout("Finish the let statement.\n");
+}
+
+#[test]
+fn output_follows_the_locale() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("list")
+        .env("LC_ALL", "")
+        .env("LC_MESSAGES", "")
+        .env("LANG", "zh_CN.UTF-8")
+        .current_dir("tests/fixture/success")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("进度：你完成了"));
+}
+
+#[test]
+fn screen_reader_output_is_plain_text() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--screen-reader", "run", "testSuccess"])
+        .env("LC_ALL", "C")
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Testing testSuccess.rs...\n"))
+        .stdout(predicates::str::contains("\x1B").not());
+}
+
+#[test]
+fn next_offers_every_unlocked_exercise() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--lang", "en", "hint", "next"])
+        .current_dir("tests/fixture/curriculum")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("basics2 hint"))
+        .stdout(predicates::str::contains("Other exercises you can do next: iterators1"));
+}
+
+#[test]
+fn graph_shows_prerequisites() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["graph", "-f", "mermaid"])
+        .current_dir("tests/fixture/curriculum")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("basics1 --> chapter_iterators"))
+        .stdout(predicates::str::contains("chapter_basics --> quiz"))
+        .stdout(predicates::str::contains("class basics2,iterators1 unlocked"));
+}
+
+#[test]
+fn graph_as_dot() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["graph"])
+        .current_dir("tests/fixture/curriculum")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("digraph curriculum {"))
+        .stdout(predicates::str::contains("\"basics1\" -> \"basics2\";"));
+}
+
+// Send a GET request to the server and return the whole response
+fn get(address: &str, path: &str) -> String {
+    let mut stream = TcpStream::connect(address).unwrap();
+    write!(stream, "GET {path} HTTP/1.1\r\nHost: {address}\r\n\r\n").unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+#[test]
+fn serve_progress_as_json() {
+    let mut server = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--lang", "en", "serve", "--port", "0"])
+        .current_dir("tests/fixture/curriculum")
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // Kept open until the server is stopped, so that it can keep printing
+    let mut stdout = BufReader::new(server.stdout.take().unwrap());
+    let mut line = String::new();
+    stdout.read_line(&mut line).unwrap();
+    let address = line.trim().rsplit("http://").next().unwrap().to_string();
+
+    let exercises = get(&address, "/exercises");
+    let basics2 = get(&address, "/exercises/basics2");
+    let missing = get(&address, "/exercises/missing");
+    server.kill().unwrap();
+    server.wait().unwrap();
+
+    assert!(exercises.starts_with("HTTP/1.1 200 OK"), "{exercises}");
+    assert!(exercises.contains("\"name\": \"iterators1\""), "{exercises}");
+    assert!(exercises.contains("\"unlocked\": true"), "{exercises}");
+    assert!(basics2.contains("\"done\": false"), "{basics2}");
+    assert!(basics2.contains("I AM NOT DONE"), "{basics2}");
+    assert!(missing.starts_with("HTTP/1.1 404 Not Found"), "{missing}");
+}
+
+#[test]
+fn lsp_server_reports_the_marker() {
+    let dir = std::fs::canonicalize("tests/fixture/curriculum").unwrap();
+    let file = dir.join("exercises/basics/basics2.rs");
+    let uri = format!("file://{}", file.display());
+    let text = std::fs::read_to_string(&file).unwrap();
+    let messages = [
+        serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": { "uri": uri, "languageId": "rust", "version": 1, "text": text } },
+        }),
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "textDocument/codeAction",
+            "params": { "textDocument": { "uri": uri } },
+        }),
+        serde_json::json!({ "jsonrpc": "2.0", "id": 3, "method": "shutdown" }),
+        serde_json::json!({ "jsonrpc": "2.0", "method": "exit" }),
+    ];
+    let input: String = messages
+        .iter()
+        .map(|message| {
+            let body = message.to_string();
+            format!("Content-Length: {}\r\n\r\n{body}", body.len())
+        })
+        .collect();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--lang", "en", "lsp-server"])
+        .current_dir(&dir)
+        .with_stdin()
+        .buffer(input)
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains("textDocument/publishDiagnostics"))
+        .stdout(predicates::str::contains("\"command\":\"rustlings.run\""))
+        .stdout(predicates::str::contains("Remove this line once basics2 works"));
+}